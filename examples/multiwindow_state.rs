@@ -0,0 +1,53 @@
+//! Share state between windows
+//!
+//! Every window runs its own VirtualDom, so context and signals from one window aren't visible in the others. This
+//! example shares a signal with every window through the app context, and uses window labels to open a single
+//! settings window and bring it back to the front if it is already open.
+
+use dioxus::desktop::{use_app_context, window, Config};
+use dioxus::prelude::*;
+
+/// The accent color of the app. A newtype keeps it from clashing with other shared values.
+#[derive(Clone, Copy)]
+struct Accent(Signal<String>);
+
+fn use_accent() -> Signal<String> {
+    use_app_context(|| Accent(Signal::new("tomato".to_string()))).0
+}
+
+fn main() {
+    dioxus::LaunchBuilder::desktop()
+        .with_cfg(Config::new().with_window_label("main"))
+        .launch(app);
+}
+
+fn app() -> Element {
+    let accent = use_accent();
+
+    let open_settings = move |_| {
+        let desktop = window();
+        // Only open one settings window
+        if !desktop.focus_window("settings") {
+            let cfg = Config::new().with_window_label("settings");
+            desktop.new_window(VirtualDom::new(settings), cfg);
+        }
+    };
+
+    rsx! {
+        h1 { color: "{accent}", "Hello from the main window" }
+        button { onclick: open_settings, "Settings" }
+        button { onclick: move |_| _ = window().close_window_by_label("settings"), "Close settings" }
+    }
+}
+
+fn settings() -> Element {
+    let mut accent = use_accent();
+    let open_windows = window().windows().len();
+
+    rsx! {
+        p { "{open_windows} windows are open" }
+        for color in ["tomato", "seagreen", "royalblue"] {
+            button { onclick: move |_| accent.set(color.to_string()), "{color}" }
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Run the tests of a crate on the host and report each result as structured output.
+//!
+//! Component tests (functions annotated with `#[dioxus::test]`) run in a headless VirtualDom, so they don't need a
+//! renderer or a browser. We just run `cargo test` and parse the output of the libtest harness as it streams in.
+
+use super::*;
+use crate::{Result, TestOutcome, TraceSrc};
+
+/// Run the tests of the project on the host.
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct TestArgs {
+    /// Only run tests whose names contain this string
+    pub(crate) filter: Option<String>,
+
+    /// Run the tests in release mode [default: false]
+    #[clap(long, short)]
+    pub(crate) release: bool,
+
+    /// The package to test
+    #[clap(short, long)]
+    pub(crate) package: Option<String>,
+
+    /// Space separated list of features to activate
+    #[clap(long)]
+    pub(crate) features: Vec<String>,
+
+    /// Don't include the default features in the build
+    #[clap(long)]
+    pub(crate) no_default_features: bool,
+
+    /// Overwrite snapshots checked with `dioxus_testing::assert_snapshot!` instead of failing when they change
+    #[clap(long)]
+    pub(crate) accept_snapshots: bool,
+
+    /// Extra arguments passed to the test binaries
+    #[clap(last = true)]
+    pub(crate) test_args: Vec<String>,
+}
+
+impl TestArgs {
+    pub(crate) async fn test(self) -> Result<StructuredOutput> {
+        use tokio::io::AsyncBufReadExt;
+
+        let mut cmd = tokio::process::Command::new("cargo");
+        cmd.arg("test")
+            .arg("--message-format")
+            .arg("json-diagnostic-rendered-ansi")
+            // Keep running the other test binaries if one fails so every result is reported
+            .arg("--no-fail-fast")
+            .args(self.cargo_arguments())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        if self.accept_snapshots {
+            cmd.env("DIOXUS_SNAPSHOTS", "accept");
+        }
+
+        tracing::trace!(dx_src = ?TraceSrc::Build, "Rust cargo args: {:#?}", cmd);
+
+        let mut child = cmd.spawn().context("Failed to spawn cargo test")?;
+        let mut stdout = tokio::io::BufReader::new(child.stdout.take().unwrap()).lines();
+        let mut stderr = tokio::io::BufReader::new(child.stderr.take().unwrap()).lines();
+
+        let mut parser = TestOutputParser::default();
+        let mut build_failed = false;
+
+        loop {
+            use cargo_metadata::Message;
+
+            let line = tokio::select! {
+                Ok(Some(line)) = stdout.next_line() => line,
+                Ok(Some(line)) = stderr.next_line() => {
+                    tracing::trace!(dx_src = ?TraceSrc::Cargo, "{line}");
+                    continue;
+                },
+                else => break,
+            };
+
+            let Some(Ok(message)) = Message::parse_stream(std::io::Cursor::new(line)).next() else {
+                continue;
+            };
+
+            match message {
+                Message::CompilerMessage(message) => {
+                    if let Some(rendered) = &message.message.rendered {
+                        tracing::info!(dx_src = ?TraceSrc::Cargo, json = ?StructuredOutput::CargoOutput { message: message.clone() }, "{rendered}");
+                    }
+                }
+                Message::BuildFinished(finished) => build_failed |= !finished.success,
+                Message::TextLine(line) => match parser.parse_line(&line) {
+                    ParsedLine::Results(results) => results.into_iter().for_each(report_result),
+                    ParsedLine::Consumed => {}
+                    // Output from tests that run with --nocapture
+                    ParsedLine::Unknown => tracing::info!("{line}"),
+                },
+                _ => {}
+            }
+        }
+
+        parser.finish().into_iter().for_each(report_result);
+        let status = child.wait().await?;
+
+        if build_failed {
+            return Err(anyhow::anyhow!("Failed to build the tests").into());
+        }
+
+        let TestSummary {
+            passed,
+            failed,
+            ignored,
+        } = parser.summary;
+        tracing::info!(
+            json = ?StructuredOutput::TestsFinished { passed, failed, ignored },
+            "{passed} passed; {failed} failed; {ignored} ignored"
+        );
+
+        if failed > 0 {
+            return Err(anyhow::anyhow!("{failed} tests failed").into());
+        }
+
+        // A test binary can crash or exit early without reporting a failed test
+        if !status.success() {
+            return Err(anyhow::anyhow!("cargo test exited with {status}").into());
+        }
+
+        Ok(StructuredOutput::Success)
+    }
+
+    fn cargo_arguments(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.release {
+            args.push("--release".to_string());
+        }
+
+        if let Some(package) = &self.package {
+            args.push("-p".to_string());
+            args.push(package.clone());
+        }
+
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(" "));
+        }
+
+        args.push("--".to_string());
+        args.extend(self.filter.clone());
+        args.extend(self.test_args.iter().cloned());
+
+        args
+    }
+}
+
+fn report_result(result: TestResult) {
+    let TestResult {
+        name,
+        outcome,
+        output,
+    } = result;
+    let json = StructuredOutput::TestResult {
+        name: name.clone(),
+        outcome,
+        output: output.clone(),
+    };
+    match outcome {
+        TestOutcome::Failed => match output {
+            Some(output) => tracing::error!(json = ?json, "test {name} ... FAILED\n{output}"),
+            None => tracing::error!(json = ?json, "test {name} ... FAILED"),
+        },
+        TestOutcome::Ignored => tracing::info!(json = ?json, "test {name} ... ignored"),
+        _ => tracing::info!(json = ?json, "test {name} ... ok"),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct TestResult {
+    name: String,
+    outcome: TestOutcome,
+    output: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum ParsedLine {
+    /// The line finished one or more tests
+    Results(Vec<TestResult>),
+    /// The line is part of the harness output, but doesn't finish a test
+    Consumed,
+    /// The line was not printed by the harness
+    Unknown,
+}
+
+/// Parses the human readable output of the libtest harness.
+///
+/// libtest prints the captured output of failed tests after every test in the binary has finished, so failed tests
+/// are held back until their output is read.
+#[derive(Default)]
+struct TestOutputParser {
+    summary: TestSummary,
+    failed: Vec<TestResult>,
+    /// The failed test whose captured output we are currently reading
+    reading_output: Option<usize>,
+}
+
+impl TestOutputParser {
+    fn parse_line(&mut self, line: &str) -> ParsedLine {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            self.reading_output = self.failed.iter().position(|test| test.name == name);
+            return ParsedLine::Consumed;
+        }
+
+        if line == "failures:" {
+            self.reading_output = None;
+            return ParsedLine::Consumed;
+        }
+
+        if let Some(index) = self.reading_output {
+            let output = self.failed[index].output.get_or_insert_with(String::new);
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            output.push_str(line);
+            return ParsedLine::Consumed;
+        }
+
+        if line.starts_with("test result: ") {
+            return ParsedLine::Results(self.finish());
+        }
+
+        if let Some((name, outcome)) = line
+            .strip_prefix("test ")
+            .and_then(|line| line.rsplit_once(" ... "))
+        {
+            let name = name.trim_end_matches(" - should panic").to_string();
+            let outcome = match outcome {
+                "ok" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                ignored if ignored.starts_with("ignored") => TestOutcome::Ignored,
+                _ => return ParsedLine::Unknown,
+            };
+
+            let result = TestResult {
+                name,
+                outcome,
+                output: None,
+            };
+            return match outcome {
+                TestOutcome::Failed => {
+                    self.summary.failed += 1;
+                    self.failed.push(result);
+                    ParsedLine::Consumed
+                }
+                TestOutcome::Ignored => {
+                    self.summary.ignored += 1;
+                    ParsedLine::Results(vec![result])
+                }
+                _ => {
+                    self.summary.passed += 1;
+                    ParsedLine::Results(vec![result])
+                }
+            };
+        }
+
+        let is_harness_output = line.trim().is_empty()
+            || line.starts_with("running ")
+            // The list of failed tests after the captured output
+            || line.starts_with("    ");
+        match is_harness_output {
+            true => ParsedLine::Consumed,
+            false => ParsedLine::Unknown,
+        }
+    }
+
+    /// Take the failed tests that are waiting for their output
+    fn finish(&mut self) -> Vec<TestResult> {
+        self.reading_output = None;
+        let mut failed = std::mem::take(&mut self.failed);
+        for test in &mut failed {
+            if let Some(output) = &mut test.output {
+                output.truncate(output.trim_end().len());
+            }
+        }
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"
+running 4 tests
+test counter::increments ... ok
+test counter::renders - should panic ... ok
+test slow ... ignored, takes too long
+test counter::decrements ... FAILED
+
+failures:
+
+---- counter::decrements stdout ----
+thread 'counter::decrements' panicked at src/lib.rs:10:5:
+assertion `left == right` failed
+
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    counter::decrements
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"#;
+
+    #[test]
+    fn parses_libtest_output() {
+        let mut parser = TestOutputParser::default();
+        let results: Vec<_> = OUTPUT
+            .lines()
+            .filter_map(|line| match parser.parse_line(line) {
+                ParsedLine::Results(results) => Some(results),
+                ParsedLine::Consumed => None,
+                ParsedLine::Unknown => panic!("unexpected line {line:?}"),
+            })
+            .flatten()
+            .collect();
+
+        assert_eq!(
+            results,
+            vec![
+                TestResult {
+                    name: "counter::increments".to_string(),
+                    outcome: TestOutcome::Passed,
+                    output: None,
+                },
+                TestResult {
+                    name: "counter::renders".to_string(),
+                    outcome: TestOutcome::Passed,
+                    output: None,
+                },
+                TestResult {
+                    name: "slow".to_string(),
+                    outcome: TestOutcome::Ignored,
+                    output: None,
+                },
+                TestResult {
+                    name: "counter::decrements".to_string(),
+                    outcome: TestOutcome::Failed,
+                    output: Some(
+                        "thread 'counter::decrements' panicked at src/lib.rs:10:5:\nassertion `left == right` failed\n\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace".to_string()
+                    ),
+                },
+            ]
+        );
+        assert_eq!(
+            parser.summary,
+            TestSummary {
+                passed: 2,
+                failed: 1,
+                ignored: 1
+            }
+        );
+    }
+
+    #[test]
+    fn output_from_uncaptured_tests_is_unknown() {
+        let mut parser = TestOutputParser::default();
+        assert_eq!(parser.parse_line("hello from a test"), ParsedLine::Unknown);
+    }
+}
@@ -341,8 +341,10 @@ impl Output {
         let res = serde_json::from_str::<ClientMsg>(text.as_str());
 
         // Client logs being errors aren't fatal, but we should still report them them
-        let ClientMsg::Log { level, messages } = match res {
-            Ok(msg) => msg,
+        let (level, messages) = match res {
+            Ok(ClientMsg::Log { level, messages }) => (level, messages),
+            // Reactive graphs are served by the devserver, not logged
+            Ok(ClientMsg::ReactiveGraph(_)) => return,
             Err(err) => {
                 tracing::error!(dx_src = ?TraceSrc::Dev, "Error parsing message from {}: {}", platform, err);
                 return;
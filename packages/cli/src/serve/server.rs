@@ -19,7 +19,7 @@ use axum::{
     routing::{get, get_service},
     Extension, Router,
 };
-use dioxus_devtools_types::{DevserverMsg, HotReloadMsg};
+use dioxus_devtools_types::{ClientMsg, DevserverMsg, HotReloadMsg, ReactiveGraph};
 use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures_util::{
     future,
@@ -57,10 +57,14 @@ pub(crate) struct WebServer {
     new_hot_reload_sockets: UnboundedReceiver<WebSocket>,
     new_build_status_sockets: UnboundedReceiver<WebSocket>,
     build_status: SharedStatus,
+    reactive_graph: SharedReactiveGraph,
     application_name: String,
     platform: Platform,
 }
 
+/// The last reactive graph an app sent, served at `/_dioxus/reactive_graph`
+type SharedReactiveGraph = Arc<RwLock<Option<ReactiveGraph>>>;
+
 impl WebServer {
     /// Start the development server.
     /// This will set up the default http server if there's no server specified (usually via fullstack).
@@ -111,6 +115,7 @@ impl WebServer {
 
         // Set up the router with some shared state that we'll update later to reflect the current state of the build
         let build_status = SharedStatus::new_with_starting_build();
+        let reactive_graph = SharedReactiveGraph::default();
         let router = build_devserver_router(
             args,
             krate,
@@ -118,6 +123,7 @@ impl WebServer {
             build_status_sockets_tx,
             proxied_address,
             build_status.clone(),
+            reactive_graph.clone(),
         )?;
 
         // And finally, start the server mainloop
@@ -129,6 +135,7 @@ impl WebServer {
 
         Ok(Self {
             build_status,
+            reactive_graph,
             proxied_port,
             devserver_bind_ip,
             devserver_exposed_ip,
@@ -180,7 +187,16 @@ impl WebServer {
             }
             Some((idx, message)) = new_message.next() => {
                 match message {
-                    Some(Ok(message)) => return ServeUpdate::WsMessage(message),
+                    Some(Ok(message)) => {
+                        // Reactive graph snapshots are kept for the reactive graph page instead of being logged
+                        if let Message::Text(text) = &message {
+                            if let Ok(ClientMsg::ReactiveGraph(graph)) = serde_json::from_str(text) {
+                                *self.reactive_graph.write().unwrap() = Some(graph);
+                                return ServeUpdate::Redraw;
+                            }
+                        }
+                        return ServeUpdate::WsMessage(message);
+                    }
                     _ => {
                         drop(new_message);
                         _ = self.hot_reload_sockets.remove(idx);
@@ -422,6 +438,7 @@ fn build_devserver_router(
     build_status_sockets: UnboundedSender<WebSocket>,
     fullstack_address: Option<SocketAddr>,
     build_status: SharedStatus,
+    reactive_graph: SharedReactiveGraph,
 ) -> Result<Router> {
     let mut router = Router::new();
 
@@ -493,7 +510,9 @@ fn build_devserver_router(
                     },
                 ),
             )
-            .layer(Extension(build_status_sockets)),
+            .layer(Extension(build_status_sockets))
+            .route("/reactive_graph", get(serve_reactive_graph))
+            .layer(Extension(reactive_graph)),
     );
 
     // Setup cors
@@ -509,6 +528,33 @@ fn build_devserver_router(
     Ok(router)
 }
 
+/// Serve a page with the last reactive graph the app sent that refreshes itself every second
+async fn serve_reactive_graph(
+    Extension(graph): Extension<SharedReactiveGraph>,
+) -> impl IntoResponse {
+    let text = match &*graph.read().unwrap() {
+        Some(graph) => graph.to_string(),
+        None => "No reactive graph was received yet. Enable the `reactive-graph` feature of dioxus to send the reactive graph of your app to the devserver.".to_string(),
+    };
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    axum::response::Html(format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<title>Reactive graph</title>
+<meta http-equiv="refresh" content="1">
+</head>
+<body>
+<pre>{escaped}</pre>
+</body>
+</html>"#
+    ))
+}
+
 fn build_serve_dir(args: &ServeArgs, cfg: &DioxusCrate) -> axum::routing::MethodRouter {
     use tower::ServiceBuilder;
 
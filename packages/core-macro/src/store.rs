@@ -0,0 +1,92 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Fields, GenericParam};
+
+/// Generate an extension trait for `Store<T>` with a method that selects each field of a struct
+pub fn derive_store(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "Store can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Store can only be derived for structs",
+            ))
+        }
+    };
+
+    if let Some(lifetime) = input.generics.lifetimes().next() {
+        return Err(syn::Error::new_spanned(
+            lifetime,
+            "Store cannot be derived for structs with lifetimes because stores must be 'static",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let ext = format_ident!("{}StoreExt", name);
+
+    // Every type in a store must be 'static
+    let mut generics = input.generics.clone();
+    let static_bounds: Vec<_> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                Some(parse_quote!(#ident: 'static))
+            }
+            _ => None,
+        })
+        .collect::<Vec<syn::WherePredicate>>();
+    generics
+        .make_where_clause()
+        .predicates
+        .extend(static_bounds);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut declarations = Vec::new();
+    let mut implementations = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let field_name = field.ident.as_ref().expect("named fields have identifiers");
+        let ty = &field.ty;
+        let index = index as u64;
+        let doc = format!("Select the `{field_name}` field of the store.");
+
+        declarations.push(quote! {
+            #[doc = #doc]
+            fn #field_name(&self) -> dioxus_signals::Store<#ty>;
+        });
+        implementations.push(quote! {
+            fn #field_name(&self) -> dioxus_signals::Store<#ty> {
+                self.project(
+                    #index,
+                    |value| ::core::option::Option::Some(&value.#field_name),
+                    |value| ::core::option::Option::Some(&mut value.#field_name),
+                )
+            }
+        });
+    }
+
+    let trait_doc = format!(
+        "Methods that select the fields of a [`Store`](dioxus_signals::Store) of [`{name}`]."
+    );
+
+    Ok(quote! {
+        #[doc = #trait_doc]
+        #vis trait #ext #impl_generics #where_clause {
+            #(#declarations)*
+        }
+
+        impl #impl_generics #ext #ty_generics for dioxus_signals::Store<#name #ty_generics> #where_clause {
+            #(#implementations)*
+        }
+    })
+}
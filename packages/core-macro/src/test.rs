@@ -0,0 +1,62 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse::Parse, ItemFn};
+
+/// A test function annotated with `#[dioxus::test]`
+pub struct TestBody {
+    item_fn: ItemFn,
+}
+
+impl Parse for TestBody {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let item_fn: ItemFn = input.parse()?;
+
+        if let Some(arg) = item_fn.sig.inputs.first() {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "dioxus tests cannot take arguments",
+            ));
+        }
+
+        if !item_fn.sig.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &item_fn.sig.generics,
+                "dioxus tests cannot be generic",
+            ));
+        }
+
+        Ok(Self { item_fn })
+    }
+}
+
+impl ToTokens for TestBody {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ItemFn {
+            attrs,
+            vis,
+            sig,
+            block,
+        } = &self.item_fn;
+
+        let mut sig = sig.clone();
+        let body = match sig.asyncness.take() {
+            // Async tests run to completion on a single threaded runtime, just like the VirtualDom does in most renderers
+            Some(_) => quote! {
+                {
+                    ::tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("Failed to build the tokio runtime for a dioxus test")
+                        .block_on(async move #block)
+                }
+            },
+            None => block.to_token_stream(),
+        };
+
+        tokens.extend(quote! {
+            #[::core::prelude::v1::test]
+            #(#attrs)*
+            #vis #sig #body
+        });
+    }
+}
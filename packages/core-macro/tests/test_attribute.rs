@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+
+fn counter() -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! {
+        button { onclick: move |_| count += 1, "Count: {count}" }
+    }
+}
+
+#[dioxus::test]
+fn sync_test_runs() {
+    let mut dom = VirtualDom::new(counter);
+    dom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&dom), "<button>Count: 0</button>");
+}
+
+#[dioxus::test]
+async fn async_test_runs_on_a_runtime() {
+    fn app() -> Element {
+        let value = use_resource(|| async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            "resolved"
+        })
+        .suspend()?;
+        rsx! { "{value}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.wait_for_suspense().await;
+    assert_eq!(dioxus_ssr::render(&dom), "resolved");
+}
+
+#[dioxus::test]
+fn tests_can_return_results() -> Result<(), std::fmt::Error> {
+    Ok(())
+}
+
+#[dioxus::test]
+#[should_panic]
+fn attributes_are_kept() {
+    panic!("this test should panic");
+}
@@ -0,0 +1,127 @@
+use crate::innerlude::*;
+
+/// The element ids a renderer needs to hydrate html that was rendered from a [`VirtualDom`] with `dioxus-ssr`.
+///
+/// Renderers that hydrate a pre-rendered page rebuild their VirtualDom without creating any nodes, then attach the ids
+/// to the nodes the server rendered, in the order `dioxus-ssr` numbered them.
+#[derive(Debug, Default)]
+pub struct HydrationIds {
+    /// The element id of every node with a hydration marker, in the order of the markers
+    pub ids: Vec<u32>,
+    /// Elements with an `onmounted` listener. The pre-rendered page doesn't send the event, so the renderer needs to
+    /// send it after hydrating.
+    pub mounted: Vec<ElementId>,
+}
+
+impl HydrationIds {
+    /// Collect the ids of the nodes under a scope of a VirtualDom that was just rebuilt.
+    ///
+    /// `on_suspended` is called with every suspense boundary that is still pending, in the order they are found. Their
+    /// children are hydrated later, once the server streams them in.
+    ///
+    /// Returns `None` if some nodes were never mounted.
+    pub fn collect(
+        dom: &VirtualDom,
+        scope: &ScopeState,
+        on_suspended: impl FnMut(ScopeId),
+    ) -> Option<Self> {
+        let mut collector = Collector {
+            dom,
+            on_suspended,
+            hydration: Self::default(),
+        };
+        collector.scope(scope)?;
+        Some(collector.hydration)
+    }
+}
+
+struct Collector<'a, F> {
+    dom: &'a VirtualDom,
+    on_suspended: F,
+    hydration: HydrationIds,
+}
+
+impl<F: FnMut(ScopeId)> Collector<'_, F> {
+    fn scope(&mut self, scope: &ScopeState) -> Option<()> {
+        // If this scope is a suspense boundary that is pending, its children will be hydrated later
+        if let Some(suspense) =
+            SuspenseContext::downcast_suspense_boundary_from_scope(&self.dom.runtime(), scope.id())
+        {
+            if suspense.has_suspended_tasks() {
+                (self.on_suspended)(scope.id());
+            }
+        }
+
+        self.vnode(scope.root_node())
+    }
+
+    fn vnode(&mut self, vnode: &VNode) -> Option<()> {
+        for (i, root) in vnode.template.roots.iter().enumerate() {
+            self.template_node(vnode, root, Some(vnode.mounted_root(i, self.dom)?))?;
+        }
+        Some(())
+    }
+
+    fn template_node(
+        &mut self,
+        vnode: &VNode,
+        node: &TemplateNode,
+        root_id: Option<ElementId>,
+    ) -> Option<()> {
+        match node {
+            TemplateNode::Element {
+                children, attrs, ..
+            } => {
+                let mut mounted_id = root_id;
+                for attr in *attrs {
+                    if let TemplateAttribute::Dynamic { id } = attr {
+                        let attributes = &*vnode.dynamic_attrs[*id];
+                        let id = vnode.mounted_dynamic_attribute(*id, self.dom)?;
+                        for attribute in attributes {
+                            mounted_id = Some(id);
+                            if let AttributeValue::Listener(_) = &attribute.value {
+                                if attribute.name == "onmounted" {
+                                    self.hydration.mounted.push(id);
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some(id) = mounted_id {
+                    self.hydration.ids.push(id.0 as u32);
+                }
+                for child in *children {
+                    self.template_node(vnode, child, None)?;
+                }
+            }
+            TemplateNode::Dynamic { id } => {
+                self.dynamic_node(vnode, &vnode.dynamic_nodes[*id], *id)?
+            }
+            TemplateNode::Text { .. } => {
+                if let Some(id) = root_id {
+                    self.hydration.ids.push(id.0 as u32);
+                }
+            }
+        }
+        Some(())
+    }
+
+    fn dynamic_node(&mut self, vnode: &VNode, dynamic: &DynamicNode, index: usize) -> Option<()> {
+        match dynamic {
+            DynamicNode::Text(_) | DynamicNode::Placeholder(_) => {
+                let id = vnode.mounted_dynamic_node(index, self.dom)?;
+                self.hydration.ids.push(id.0 as u32);
+            }
+            DynamicNode::Component(comp) => {
+                let scope = comp.mounted_scope(index, vnode, self.dom)?;
+                self.scope(scope)?;
+            }
+            DynamicNode::Fragment(fragment) => {
+                for vnode in fragment {
+                    self.vnode(vnode)?;
+                }
+            }
+        }
+        Some(())
+    }
+}
@@ -12,11 +12,13 @@ mod events;
 mod fragment;
 mod generational_box;
 mod global_context;
+mod hydration;
 mod launch;
 mod mutations;
 mod nodes;
 mod properties;
 mod reactive_context;
+mod reactive_graph;
 mod render_error;
 mod root_wrapper;
 mod runtime;
@@ -56,11 +58,16 @@ pub(crate) mod innerlude {
     pub use crate::fragment::*;
     pub use crate::generational_box::*;
     pub use crate::global_context::*;
+    pub use crate::hydration::*;
     pub use crate::launch::*;
     pub use crate::mutations::*;
     pub use crate::nodes::*;
     pub use crate::properties::*;
     pub use crate::reactive_context::*;
+    pub use crate::reactive_graph::{
+        ReactiveContextKind, ReactiveContextNode, ReactiveGraph, ReactiveValueKind,
+        ReactiveValueNode,
+    };
     pub use crate::render_error::*;
     pub use crate::runtime::{Runtime, RuntimeGuard};
     pub use crate::scheduler::*;
@@ -81,10 +88,11 @@ pub(crate) mod innerlude {
 pub use crate::innerlude::{
     fc_to_builder, generation, schedule_update, schedule_update_any, use_hook, vdom_is_rendering,
     AnyValue, Attribute, AttributeValue, CapturedError, Component, ComponentFunction, DynamicNode,
-    Element, ElementId, Event, Fragment, HasAttributes, IntoDynNode, LaunchConfig, MarkerWrapper,
-    Mutation, Mutations, NoOpMutations, Ok, Properties, Result, Runtime, ScopeId, ScopeState,
-    SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner,
-    VPlaceholder, VText, VirtualDom, WriteMutations,
+    Element, ElementId, Event, Fragment, HasAttributes, HydrationIds, IntoDynNode, LaunchConfig,
+    MarkerWrapper, Mutation, Mutations, NoOpMutations, Ok, Properties, ReactiveContextKind,
+    ReactiveContextNode, ReactiveGraph, ReactiveValueKind, ReactiveValueNode, Result, Runtime,
+    ScopeId, ScopeState, SpawnIfAsync, Task, Template, TemplateAttribute, TemplateNode, VComponent,
+    VNode, VNodeInner, VPlaceholder, VText, VirtualDom, WriteMutations,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
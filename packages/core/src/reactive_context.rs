@@ -5,7 +5,7 @@ use crate::{
     Runtime,
 };
 use futures_channel::mpsc::UnboundedReceiver;
-use generational_box::{BorrowMutError, GenerationalBox, SyncStorage};
+use generational_box::{BorrowMutError, GenerationalBox, Owner, SyncStorage};
 use std::{
     cell::RefCell,
     collections::HashSet,
@@ -79,6 +79,20 @@ impl ReactiveContext {
     pub fn new_with_callback(
         callback: impl FnMut() + Send + Sync + 'static,
         scope: ScopeId,
+        origin: &'static std::panic::Location<'static>,
+    ) -> Self {
+        Self::new_with_owner(callback, scope, &scope.owner(), origin)
+    }
+
+    /// Create a new reactive context that is owned by `owner` instead of a scope. The context is dropped when the
+    /// owner is dropped.
+    ///
+    /// Unlike [`ReactiveContext::new_with_callback`], this doesn't need the dioxus runtime, so it can be used to
+    /// observe `Send + Sync` signals from other threads.
+    pub fn new_with_owner(
+        callback: impl FnMut() + Send + Sync + 'static,
+        scope: ScopeId,
+        owner: &Owner<SyncStorage>,
         #[allow(unused)] origin: &'static std::panic::Location<'static>,
     ) -> Self {
         let inner = Inner {
@@ -91,8 +105,6 @@ impl ReactiveContext {
             scope: None,
         };
 
-        let owner = scope.owner();
-
         let self_ = Self {
             scope,
             inner: owner.insert(inner),
@@ -100,6 +112,14 @@ impl ReactiveContext {
 
         self_.inner.write().self_ = Some(self_);
 
+        #[cfg(feature = "reactive-graph")]
+        crate::reactive_graph::record_new_context(
+            &self_,
+            crate::ReactiveContextKind::Other,
+            None,
+            Some(origin),
+        );
+
         self_
     }
 
@@ -137,6 +157,14 @@ impl ReactiveContext {
 
         self_.inner.write().self_ = Some(self_);
 
+        #[cfg(feature = "reactive-graph")]
+        crate::reactive_graph::record_new_context(
+            &self_,
+            crate::ReactiveContextKind::Scope,
+            Some(scope.name),
+            None,
+        );
+
         self_
     }
 
@@ -211,6 +239,8 @@ impl ReactiveContext {
     /// This will set the current reactive context to this context for the duration of the function.
     /// You can then get information about the current subscriptions.
     pub fn run_in<O>(&self, f: impl FnOnce() -> O) -> O {
+        #[cfg(feature = "reactive-graph")]
+        crate::reactive_graph::record_run(self);
         CURRENT.with(|current| current.borrow_mut().push(*self));
         let out = f();
         CURRENT.with(|current| current.borrow_mut().pop());
@@ -246,6 +276,11 @@ impl ReactiveContext {
         match self.inner.try_write() {
             Ok(mut inner) => {
                 subscriptions.lock().unwrap().insert(*self);
+                crate::ReactiveGraph::record_value(
+                    &subscriptions,
+                    crate::ReactiveValueKind::Other,
+                    None,
+                );
                 inner.subscribers.insert(PointerHash(subscriptions));
             }
             // If the context was dropped, we don't need to subscribe to it anymore
@@ -262,6 +297,12 @@ impl ReactiveContext {
     pub fn origin_scope(&self) -> ScopeId {
         self.scope
     }
+
+    /// The id of the generational box that holds this context
+    #[cfg(feature = "reactive-graph")]
+    pub(crate) fn id(&self) -> generational_box::GenerationalBoxId {
+        self.inner.id()
+    }
 }
 
 impl Hash for ReactiveContext {
@@ -323,5 +364,8 @@ impl Drop for Inner {
                 subscriber.remove(&self_);
             }
         }
+
+        #[cfg(feature = "reactive-graph")]
+        crate::reactive_graph::forget_context(&self_);
     }
 }
@@ -0,0 +1,493 @@
+use crate::{innerlude::ScopeId, prelude::ReactiveContext};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    panic::Location,
+    sync::{Arc, Mutex},
+};
+
+type Subscribers = Arc<Mutex<HashSet<ReactiveContext>>>;
+
+/// A snapshot of the dependency graph between tracked values (signals, memos and stores) and the reactive contexts
+/// (components, memos, effects and resources) that read them.
+///
+/// The graph is only recorded when the `reactive-graph` feature is enabled. Without it, [`ReactiveGraph::snapshot`]
+/// always returns an empty graph and recording is free.
+///
+/// The graph answers questions like "why did this component re-render": every context records how many times it
+/// ran, and the value whose write last marked it dirty.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::ReactiveGraph;
+/// fn app() -> Element {
+///     let mut count = use_signal(|| 0);
+///     let doubled = use_memo(move || count() * 2);
+///
+///     rsx! {
+///         button { onclick: move |_| count += 1, "{doubled}" }
+///         button { onclick: move |_| println!("{}", ReactiveGraph::snapshot()), "Print the graph" }
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReactiveGraph {
+    /// Every tracked value that is alive and has been read or written, ordered by creation
+    pub values: Vec<ReactiveValueNode>,
+    /// Every reactive context that is alive, ordered by creation
+    pub contexts: Vec<ReactiveContextNode>,
+}
+
+/// The kind of a tracked value in the [`ReactiveGraph`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReactiveValueKind {
+    /// A signal
+    Signal,
+    /// The value computed by a memo
+    Memo,
+    /// One part of a store
+    Store,
+    /// A value that was only seen through [`ReactiveContext::subscribe`]
+    Other,
+}
+
+/// The kind of a reactive context in the [`ReactiveGraph`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReactiveContextKind {
+    /// The context that re-renders a component
+    Scope,
+    /// The context that recomputes a memo
+    Memo,
+    /// The context that reruns an effect
+    Effect,
+    /// The context that restarts a resource
+    Resource,
+    /// Any other context, like a context created with [`ReactiveContext::new`]
+    Other,
+}
+
+/// A tracked value in the [`ReactiveGraph`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactiveValueNode {
+    /// The id of the value. Ids are unique between values and contexts.
+    pub id: u64,
+    /// The kind of the value
+    pub kind: ReactiveValueKind,
+    /// The location the value was created at, if it is known
+    pub origin: Option<String>,
+    /// The number of times the value was written
+    pub writes: u64,
+    /// The ids of the contexts that currently read the value
+    pub subscribers: Vec<u64>,
+}
+
+/// A reactive context in the [`ReactiveGraph`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReactiveContextNode {
+    /// The id of the context. Ids are unique between values and contexts.
+    pub id: u64,
+    /// The kind of the context
+    pub kind: ReactiveContextKind,
+    /// The scope the context belongs to
+    pub scope: ScopeId,
+    /// The name of the component, for scope contexts
+    pub name: Option<String>,
+    /// The location the context was created at, if it is known
+    pub origin: Option<String>,
+    /// The number of times the context ran
+    pub runs: u64,
+    /// The number of times the context was marked dirty by a write
+    pub dirtied: u64,
+    /// The id of the value whose write last marked the context dirty
+    pub last_dirtied_by: Option<u64>,
+    /// The id of the value the context computes, for memos
+    pub output: Option<u64>,
+}
+
+impl ReactiveGraph {
+    /// Check if the reactive graph is recorded. This is true if the `reactive-graph` feature is enabled.
+    pub const fn is_enabled() -> bool {
+        cfg!(feature = "reactive-graph")
+    }
+
+    /// Take a snapshot of the current reactive graph of every virtual dom in the process
+    pub fn snapshot() -> Self {
+        #[cfg(feature = "reactive-graph")]
+        {
+            registry::snapshot()
+        }
+        #[cfg(not(feature = "reactive-graph"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Record a tracked value with the set of contexts that subscribe to it. Tracked values should call this before
+    /// they are first read so the graph knows what kind of value the subscribers belong to.
+    ///
+    /// Recording the same subscribers again only replaces the kind and origin if they were unknown.
+    #[allow(unused)]
+    pub fn record_value(
+        subscribers: &Subscribers,
+        kind: ReactiveValueKind,
+        origin: Option<&'static Location<'static>>,
+    ) {
+        #[cfg(feature = "reactive-graph")]
+        registry::record_value(subscribers, kind, origin);
+    }
+
+    /// Record a write to a tracked value
+    #[allow(unused)]
+    pub fn record_write(subscribers: &Subscribers) {
+        #[cfg(feature = "reactive-graph")]
+        registry::record_write(subscribers);
+    }
+
+    /// Record that a write to a tracked value marked a context as dirty
+    #[allow(unused)]
+    pub fn record_dirty(subscribers: &Subscribers, context: &ReactiveContext) {
+        #[cfg(feature = "reactive-graph")]
+        registry::record_dirty(subscribers, context);
+    }
+
+    /// Record what a reactive context is used for. `output` is the value the context computes, like the value of a
+    /// memo.
+    #[allow(unused)]
+    pub fn record_context(
+        context: &ReactiveContext,
+        kind: ReactiveContextKind,
+        output: Option<&Subscribers>,
+    ) {
+        #[cfg(feature = "reactive-graph")]
+        registry::record_context(context, kind, output);
+    }
+
+    fn value(&self, id: u64) -> Option<&ReactiveValueNode> {
+        self.values.iter().find(|value| value.id == id)
+    }
+}
+
+impl Display for ReactiveValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signal => write!(f, "signal"),
+            Self::Memo => write!(f, "memo"),
+            Self::Store => write!(f, "store"),
+            Self::Other => write!(f, "value"),
+        }
+    }
+}
+
+impl Display for ReactiveContextKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scope => write!(f, "component"),
+            Self::Memo => write!(f, "memo"),
+            Self::Effect => write!(f, "effect"),
+            Self::Resource => write!(f, "resource"),
+            Self::Other => write!(f, "context"),
+        }
+    }
+}
+
+impl Display for ReactiveGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Print each context with the values it reads, so the output reads like "this re-renders because of that"
+        for context in &self.contexts {
+            write!(f, "#{} {}", context.id, context.kind)?;
+            if let Some(name) = &context.name {
+                write!(f, " {name}")?;
+            }
+            if let Some(origin) = &context.origin {
+                write!(f, " at {origin}")?;
+            }
+            write!(
+                f,
+                " in {:?}: {} runs, dirtied {} times",
+                context.scope, context.runs, context.dirtied
+            )?;
+            if let Some(value) = context.last_dirtied_by {
+                write!(f, ", last by #{value}")?;
+            }
+            writeln!(f)?;
+
+            if let Some(output) = context.output.and_then(|id| self.value(id)) {
+                writeln!(f, "    computes {} #{}", output.kind, output.id)?;
+            }
+            for value in self
+                .values
+                .iter()
+                .filter(|value| value.subscribers.contains(&context.id))
+            {
+                write!(f, "    reads {} #{}", value.kind, value.id)?;
+                if let Some(origin) = &value.origin {
+                    write!(f, " at {origin}")?;
+                }
+                writeln!(f, " ({} writes)", value.writes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "reactive-graph")]
+pub(crate) use registry::{forget_context, record_new_context, record_run};
+
+#[cfg(feature = "reactive-graph")]
+mod registry {
+    use super::*;
+    use generational_box::GenerationalBoxId;
+    use std::{
+        collections::HashMap,
+        sync::{OnceLock, Weak},
+    };
+
+    /// Everything that is recorded about the graph. Values are keyed by the address of their subscribers and contexts
+    /// by their generational box.
+    ///
+    /// The registry lock must never be taken while a subscriber set is locked, because taking a snapshot locks the
+    /// subscriber sets.
+    #[derive(Default)]
+    struct Registry {
+        next_id: u64,
+        values: HashMap<usize, ValueEntry>,
+        contexts: HashMap<GenerationalBoxId, ContextEntry>,
+        /// The number of values when dropped values should be removed next
+        prune_at: usize,
+    }
+
+    struct ValueEntry {
+        id: u64,
+        kind: ReactiveValueKind,
+        origin: Option<&'static Location<'static>>,
+        writes: u64,
+        subscribers: Weak<Mutex<HashSet<ReactiveContext>>>,
+    }
+
+    struct ContextEntry {
+        id: u64,
+        kind: ReactiveContextKind,
+        scope: ScopeId,
+        name: Option<&'static str>,
+        origin: Option<&'static Location<'static>>,
+        runs: u64,
+        dirtied: u64,
+        last_dirtied_by: Option<u64>,
+        output: Option<usize>,
+    }
+
+    fn with_registry<O>(f: impl FnOnce(&mut Registry) -> O) -> O {
+        static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+        let mut registry = REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        f(&mut registry)
+    }
+
+    fn key(subscribers: &Subscribers) -> usize {
+        Arc::as_ptr(subscribers) as *const () as usize
+    }
+
+    impl Registry {
+        fn next_id(&mut self) -> u64 {
+            self.next_id += 1;
+            self.next_id
+        }
+
+        /// Find the value for a set of subscribers, inserting it if the set hasn't been seen before
+        fn value(&mut self, subscribers: &Subscribers) -> &mut ValueEntry {
+            // Dropped values are removed in bulk so recording stays cheap when the graph is never inspected
+            if self.values.len() >= self.prune_at {
+                self.values
+                    .retain(|_, value| value.subscribers.strong_count() > 0);
+                self.prune_at = (self.values.len() * 2).max(64);
+            }
+
+            let key = key(subscribers);
+            // The address of a dropped set can be reused by a new value, which should get a new entry
+            if self
+                .values
+                .get(&key)
+                .is_some_and(|value| value.subscribers.strong_count() == 0)
+            {
+                self.values.remove(&key);
+            }
+            if !self.values.contains_key(&key) {
+                let id = self.next_id();
+                self.values.insert(
+                    key,
+                    ValueEntry {
+                        id,
+                        kind: ReactiveValueKind::Other,
+                        origin: None,
+                        writes: 0,
+                        subscribers: Arc::downgrade(subscribers),
+                    },
+                );
+            }
+            self.values.get_mut(&key).unwrap()
+        }
+    }
+
+    pub(super) fn record_value(
+        subscribers: &Subscribers,
+        kind: ReactiveValueKind,
+        origin: Option<&'static Location<'static>>,
+    ) {
+        with_registry(|registry| {
+            let value = registry.value(subscribers);
+            if value.kind == ReactiveValueKind::Other {
+                value.kind = kind;
+            }
+            if value.origin.is_none() {
+                value.origin = origin;
+            }
+        })
+    }
+
+    pub(super) fn record_write(subscribers: &Subscribers) {
+        with_registry(|registry| registry.value(subscribers).writes += 1)
+    }
+
+    pub(super) fn record_dirty(subscribers: &Subscribers, context: &ReactiveContext) {
+        with_registry(|registry| {
+            let value = registry.value(subscribers).id;
+            if let Some(context) = registry.contexts.get_mut(&context.id()) {
+                context.dirtied += 1;
+                context.last_dirtied_by = Some(value);
+            }
+        })
+    }
+
+    pub(super) fn record_context(
+        context: &ReactiveContext,
+        kind: ReactiveContextKind,
+        output: Option<&Subscribers>,
+    ) {
+        with_registry(|registry| {
+            let output = output.map(|output| {
+                registry.value(output);
+                key(output)
+            });
+            if let Some(context) = registry.contexts.get_mut(&context.id()) {
+                context.kind = kind;
+                context.output = output.or(context.output);
+            }
+        })
+    }
+
+    /// Record a context when it is created
+    pub(crate) fn record_new_context(
+        context: &ReactiveContext,
+        kind: ReactiveContextKind,
+        name: Option<&'static str>,
+        origin: Option<&'static Location<'static>>,
+    ) {
+        with_registry(|registry| {
+            let id = registry.next_id();
+            registry.contexts.insert(
+                context.id(),
+                ContextEntry {
+                    id,
+                    kind,
+                    scope: context.origin_scope(),
+                    name,
+                    origin,
+                    runs: 0,
+                    dirtied: 0,
+                    last_dirtied_by: None,
+                    output: None,
+                },
+            );
+        })
+    }
+
+    pub(crate) fn record_run(context: &ReactiveContext) {
+        with_registry(|registry| {
+            if let Some(context) = registry.contexts.get_mut(&context.id()) {
+                context.runs += 1;
+            }
+        })
+    }
+
+    /// Remove a context when it is dropped
+    pub(crate) fn forget_context(context: &ReactiveContext) {
+        with_registry(|registry| registry.contexts.remove(&context.id()));
+    }
+
+    pub(super) fn snapshot() -> ReactiveGraph {
+        let values: Vec<_> = with_registry(|registry| {
+            registry
+                .values
+                .values()
+                .map(|value| (value.id, value.subscribers.clone()))
+                .collect()
+        });
+
+        // Read the subscribers without holding the registry lock, since contexts may be recorded while a subscriber
+        // set is locked
+        let subscribers: HashMap<u64, Vec<GenerationalBoxId>> = values
+            .into_iter()
+            .filter_map(|(id, subscribers)| {
+                let subscribers = subscribers.upgrade()?;
+                let subscribers = subscribers.lock().unwrap_or_else(|err| err.into_inner());
+                Some((id, subscribers.iter().map(|context| context.id()).collect()))
+            })
+            .collect();
+
+        with_registry(|registry| {
+            let context_id =
+                |context: &GenerationalBoxId| registry.contexts.get(context)?.id.into();
+
+            let mut values: Vec<_> = registry
+                .values
+                .values()
+                .filter_map(|value| {
+                    let mut readers: Vec<u64> = subscribers
+                        .get(&value.id)?
+                        .iter()
+                        .filter_map(context_id)
+                        .collect();
+                    readers.sort_unstable();
+                    Some(ReactiveValueNode {
+                        id: value.id,
+                        kind: value.kind,
+                        origin: value.origin.map(ToString::to_string),
+                        writes: value.writes,
+                        subscribers: readers,
+                    })
+                })
+                .collect();
+            values.sort_unstable_by_key(|value| value.id);
+
+            let mut contexts: Vec<_> = registry
+                .contexts
+                .values()
+                .map(|context| ReactiveContextNode {
+                    id: context.id,
+                    kind: context.kind,
+                    scope: context.scope,
+                    name: context.name.map(ToString::to_string),
+                    origin: context.origin.map(ToString::to_string),
+                    runs: context.runs,
+                    dirtied: context.dirtied,
+                    last_dirtied_by: context.last_dirtied_by,
+                    output: context
+                        .output
+                        .and_then(|output| registry.values.get(&output))
+                        .filter(|output| subscribers.contains_key(&output.id))
+                        .map(|output| output.id),
+                })
+                .collect();
+            contexts.sort_unstable_by_key(|context| context.id);
+
+            ReactiveGraph { values, contexts }
+        })
+    }
+}
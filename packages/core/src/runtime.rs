@@ -27,6 +27,9 @@ thread_local! {
 
 /// A global runtime that is shared across all scopes that provides the async runtime and context API
 pub struct Runtime {
+    /// A unique id for the runtime, used to label the owners of its scopes in leak reports
+    pub(crate) id: usize,
+
     pub(crate) scope_states: RefCell<Vec<Option<Scope>>>,
 
     // We use this to track the current scope
@@ -72,7 +75,10 @@ impl Runtime {
         // the root element is always given element ID 0 since it's the container for the entire tree
         elements.insert(None);
 
+        static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
         Rc::new(Self {
+            id: NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
             sender,
             rendering: Cell::new(false),
             scope_states: Default::default(),
@@ -171,6 +171,13 @@ impl Scope {
             Some(rt) => rt,
             None => {
                 let owner = S::owner();
+                if generational_box::LeakTracker::is_enabled() {
+                    let runtime = Runtime::with(|rt| rt.id).unwrap_or_default();
+                    owner.set_label(format_args!(
+                        "ScopeId({}, {:?}) in VirtualDom({runtime})",
+                        self.id.0, self.name
+                    ));
+                }
                 self.provide_context(owner)
             }
         }
@@ -738,6 +738,17 @@ impl VirtualDom {
         self.runtime.clone()
     }
 
+    /// Get a report of the generational boxes owned by the scopes of this dom that are alive. Boxes are only tracked
+    /// while the [`LeakTracker`](generational_box::LeakTracker) is enabled.
+    pub fn leak_report(&self) -> generational_box::LeakReport {
+        let suffix = format!(" in VirtualDom({})", self.runtime.id);
+        let mut report = generational_box::LeakTracker::report();
+        report.retain(|group| {
+            matches!(&group.owner, generational_box::BoxOwner::Owned(Some(label)) if label.ends_with(&suffix))
+        });
+        report
+    }
+
     /// Handle an event with the Virtual Dom. This method is deprecated in favor of [VirtualDom::runtime().handle_event] and will be removed in a future release.
     #[deprecated = "Use [VirtualDom::runtime().handle_event] instead"]
     pub fn handle_event(&self, name: &str, event: Rc<dyn Any>, element: ElementId, bubbling: bool) {
@@ -754,6 +765,14 @@ impl Drop for VirtualDom {
         for scope in scopes.into_iter().rev() {
             drop(scope);
         }
+
+        // Any boxes of the scopes that are still alive are kept alive by something that outlives the dom
+        if generational_box::LeakTracker::is_enabled() {
+            let report = self.leak_report();
+            if !report.is_empty() {
+                tracing::info!("The VirtualDom was dropped. {report}");
+            }
+        }
     }
 }
 
@@ -73,6 +73,30 @@ fn suspended_child() -> Element {
     rsx!("child")
 }
 
+#[test]
+fn hydration_ids_report_pending_suspense_boundaries() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            dom.rebuild_in_place();
+
+            let mut pending = Vec::new();
+            dioxus_core::HydrationIds::collect(&dom, dom.base_scope(), |id| pending.push(id))
+                .unwrap();
+            assert_eq!(pending.len(), 1);
+
+            // Once the boundary resolves, there is nothing left to hydrate later
+            dom.wait_for_suspense().await;
+            dom.render_immediate(&mut dioxus_core::NoOpMutations);
+            let mut pending = Vec::new();
+            dioxus_core::HydrationIds::collect(&dom, dom.base_scope(), |id| pending.push(id))
+                .unwrap();
+            assert!(pending.is_empty());
+        });
+}
+
 /// When switching from a suspense fallback to the real child, the state of that component must be kept
 #[test]
 fn suspense_keeps_state() {
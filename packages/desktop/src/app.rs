@@ -1,4 +1,5 @@
 use crate::{
+    app_context::AppContexts,
     config::{Config, WindowCloseBehaviour},
     event_handlers::WindowEventHandlers,
     file_upload::{DesktopFileUploadForm, FileDialogRequest, NativeFileEngine},
@@ -6,6 +7,7 @@ use crate::{
     query::QueryResult,
     shortcut::ShortcutRegistry,
     webview::WebviewInstance,
+    window_registry::WindowRegistry,
 };
 use dioxus_core::{ElementId, VirtualDom};
 use dioxus_html::PlatformEventData;
@@ -49,6 +51,8 @@ pub(crate) struct SharedContext {
     pub(crate) event_handlers: WindowEventHandlers,
     pub(crate) pending_webviews: RefCell<Vec<WebviewInstance>>,
     pub(crate) shortcut_manager: ShortcutRegistry,
+    pub(crate) windows: WindowRegistry,
+    pub(crate) app_contexts: AppContexts,
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
 }
@@ -73,6 +77,8 @@ impl App {
                 event_handlers: WindowEventHandlers::default(),
                 pending_webviews: Default::default(),
                 shortcut_manager: ShortcutRegistry::new(),
+                windows: WindowRegistry::default(),
+                app_contexts: AppContexts::default(),
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
             }),
@@ -0,0 +1,90 @@
+use generational_box::{AnyStorage, Owner, SyncStorage, UnsyncStorage};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+};
+
+/// Context that is shared between every window of the app.
+///
+/// Each window runs its own VirtualDom, so context provided in one window is invisible to the others. Values provided
+/// here can be read from any window. Signals and other copy state created while providing a value belong to the app
+/// instead of the window that provided it, so they stay alive when that window is closed.
+pub(crate) struct AppContexts {
+    values: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+    owner: Owner<UnsyncStorage>,
+    sync_owner: Owner<SyncStorage>,
+}
+
+impl Default for AppContexts {
+    fn default() -> Self {
+        Self {
+            values: Default::default(),
+            owner: UnsyncStorage::owner(),
+            sync_owner: SyncStorage::owner(),
+        }
+    }
+}
+
+impl AppContexts {
+    pub(crate) fn get<T: Clone + 'static>(&self) -> Option<T> {
+        self.values
+            .borrow()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Create a value with the app as the owner of any copy state it creates, and share it with every window
+    pub(crate) fn provide<T: Clone + 'static>(&self, init: impl FnOnce() -> T) -> T {
+        let value = dioxus_core::prelude::with_owner(self.owner.clone(), || {
+            dioxus_core::prelude::with_owner(self.sync_owner.clone(), init)
+        });
+        self.values
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(value.clone()));
+        value
+    }
+
+    /// Get the shared value of a type, or create and share it if no window provided it yet
+    pub(crate) fn get_or_provide<T: Clone + 'static>(&self, init: impl FnOnce() -> T) -> T {
+        self.get().unwrap_or_else(|| self.provide(init))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn providing_a_value_replaces_the_old_value() {
+        let contexts = AppContexts::default();
+        assert_eq!(contexts.get::<u32>(), None);
+        assert_eq!(contexts.provide(|| 1u32), 1);
+        assert_eq!(contexts.provide(|| 2u32), 2);
+        assert_eq!(contexts.get::<u32>(), Some(2));
+        assert_eq!(contexts.get_or_provide(|| 3u32), 2);
+    }
+
+    #[test]
+    fn shared_signals_outlive_the_window_that_created_them() {
+        fn app(contexts: Rc<AppContexts>) -> Element {
+            let mut count = use_hook(|| contexts.get_or_provide(|| Signal::new(0)));
+            use_hook(move || count += 1);
+            rsx! { "{count}" }
+        }
+
+        let contexts = Rc::new(AppContexts::default());
+        let mut main = VirtualDom::new_with_props(app, contexts.clone());
+        main.rebuild_in_place();
+        let mut settings = VirtualDom::new_with_props(app, contexts.clone());
+        settings.rebuild_in_place();
+
+        // The window that created the signal is closed, but the other window can still use it
+        drop(main);
+        let count = contexts.get::<Signal<i32>>().unwrap();
+        settings.in_runtime(|| assert_eq!(count(), 2));
+    }
+}
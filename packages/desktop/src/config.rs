@@ -49,6 +49,7 @@ impl From<MenuBuilderState> for Option<DioxusMenu> {
 pub struct Config {
     pub(crate) event_loop: Option<EventLoop<UserWindowEvent>>,
     pub(crate) window: WindowBuilder,
+    pub(crate) window_label: Option<String>,
     pub(crate) as_child_window: bool,
     pub(crate) menu: MenuBuilderState,
     pub(crate) protocols: Vec<WryProtocol>,
@@ -94,6 +95,7 @@ impl Config {
 
         Self {
             window,
+            window_label: None,
             as_child_window: false,
             event_loop: None,
             menu: MenuBuilderState::Unset,
@@ -163,6 +165,19 @@ impl Config {
         self
     }
 
+    /// Set a label to find the window with later.
+    ///
+    /// Other windows can look the window up by its label with [`DesktopService::get_window`] and focus or close it
+    /// with [`DesktopService::focus_window`] and [`DesktopService::close_window_by_label`].
+    ///
+    /// [`DesktopService::get_window`]: crate::DesktopService::get_window
+    /// [`DesktopService::focus_window`]: crate::DesktopService::focus_window
+    /// [`DesktopService::close_window_by_label`]: crate::DesktopService::close_window_by_label
+    pub fn with_window_label(mut self, label: impl Into<String>) -> Self {
+        self.window_label = Some(label.into());
+        self
+    }
+
     /// Set the window as child
     pub fn with_as_child_window(mut self) -> Self {
         self.as_child_window = true;
@@ -59,6 +59,9 @@ pub struct DesktopService {
     /// The tao window itself
     pub window: Window,
 
+    /// The label set with [`Config::with_window_label`]
+    pub(crate) label: Option<String>,
+
     pub(crate) shared: Rc<SharedContext>,
 
     /// The receiver for queries about the current window
@@ -83,6 +86,7 @@ impl DesktopService {
     pub(crate) fn new(
         webview: WebView,
         window: Window,
+        label: Option<String>,
         shared: Rc<SharedContext>,
         asset_handlers: AssetHandlerRegistry,
         file_hover: NativeFileHover,
@@ -90,6 +94,7 @@ impl DesktopService {
         Self {
             window,
             webview,
+            label,
             shared,
             asset_handlers,
             file_hover,
@@ -125,6 +130,63 @@ impl DesktopService {
         Rc::downgrade(&cx)
     }
 
+    /// Get the label of this window, if it was created with one
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Get a handle to every open window of the app, including this one, in the order they were created
+    ///
+    /// Don't hold on to the handles in the windows themselves, or the windows can't be closed. Use
+    /// [`Rc::downgrade`] to get a [`WeakDesktopContext`] if you need to keep them around.
+    pub fn windows(&self) -> Vec<DesktopContext> {
+        self.shared.windows.windows()
+    }
+
+    /// Get a handle to the open window with a label set with [`Config::with_window_label`]
+    ///
+    /// If several windows have the same label, this returns the one that was created first.
+    pub fn get_window(&self, label: &str) -> Option<DesktopContext> {
+        self.shared.windows.find(label)
+    }
+
+    /// Show and focus the window with a label. Returns false if there is no open window with that label.
+    pub fn focus_window(&self, label: &str) -> bool {
+        let Some(window) = self.get_window(label) else {
+            return false;
+        };
+        window.window.set_visible(true);
+        window.window.set_minimized(false);
+        window.window.set_focus();
+        true
+    }
+
+    /// Close the window with a label. Returns false if there is no open window with that label.
+    pub fn close_window_by_label(&self, label: &str) -> bool {
+        let Some(window) = self.get_window(label) else {
+            return false;
+        };
+        self.close_window(window.id());
+        true
+    }
+
+    /// Get a value that was shared with every window of the app with [`DesktopService::provide_app_context`]
+    pub fn app_context<T: Clone + 'static>(&self) -> Option<T> {
+        self.shared.app_contexts.get()
+    }
+
+    /// Share a value with every window of the app, replacing the previous value of the same type.
+    ///
+    /// Every window runs its own VirtualDom, so normal context doesn't cross windows. Signals and other copy state
+    /// created in `init` belong to the app instead of the current window, so a window can keep using them after
+    /// the window that created them is closed. Writing to a shared signal rerenders the components that read it in
+    /// every window.
+    ///
+    /// See [`use_app_context`](crate::use_app_context) for a hook that creates the value the first time it is used.
+    pub fn provide_app_context<T: Clone + 'static>(&self, init: impl FnOnce() -> T) -> T {
+        self.shared.app_contexts.provide(init)
+    }
+
     /// trigger the drag-window event
     ///
     /// Moves the window with the left mouse button until the button is released.
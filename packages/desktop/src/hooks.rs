@@ -18,6 +18,43 @@ pub fn use_window() -> DesktopContext {
     use_hook(consume_context::<DesktopContext>)
 }
 
+/// Get a value that is shared between every window of the app, creating it with `init` if no window provided it yet.
+///
+/// This is the way to share state between windows: a settings window can write to a signal that the main window
+/// reads. Signals created in `init` belong to the app, so they outlive the window that created them.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_desktop::{use_app_context, window, Config};
+///
+/// #[derive(Clone, Copy)]
+/// struct DarkMode(Signal<bool>);
+///
+/// fn app() -> Element {
+///     let dark = use_app_context(|| DarkMode(Signal::new(false))).0;
+///     rsx! {
+///         "Dark mode: {dark}"
+///         button {
+///             onclick: move |_| {
+///                 let cfg = Config::new().with_window_label("settings");
+///                 window().new_window(VirtualDom::new(settings), cfg);
+///             },
+///             "Settings"
+///         }
+///     }
+/// }
+///
+/// fn settings() -> Element {
+///     let mut dark = use_app_context(|| DarkMode(Signal::new(false))).0;
+///     rsx! {
+///         button { onclick: move |_| dark.toggle(), "Toggle dark mode" }
+///     }
+/// }
+/// ```
+pub fn use_app_context<T: Clone + 'static>(init: impl FnOnce() -> T) -> T {
+    use_hook(|| window().shared.app_contexts.get_or_provide(init))
+}
+
 /// Register an event handler that runs when a wry event is processed.
 pub fn use_wry_event_handler(
     mut handler: impl FnMut(&Event<UserWindowEvent>, &EventLoopWindowTarget<UserWindowEvent>) + 'static,
@@ -6,6 +6,7 @@
 
 mod android_sync_lock;
 mod app;
+mod app_context;
 mod assets;
 mod config;
 mod desktop_context;
@@ -23,6 +24,7 @@ mod query;
 mod shortcut;
 mod waker;
 mod webview;
+mod window_registry;
 
 // mobile shortcut is only supported on mobile platforms
 #[cfg(any(target_os = "ios", target_os = "android"))]
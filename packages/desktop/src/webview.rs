@@ -410,10 +410,12 @@ impl WebviewInstance {
         let desktop_context = Rc::from(DesktopService::new(
             webview,
             window,
+            cfg.window_label.take(),
             shared.clone(),
             asset_handlers,
             file_hover,
         ));
+        shared.windows.register(&desktop_context);
 
         // Provide the desktop context to the virtual dom and edit handler
         edits.set_desktop_context(Rc::downgrade(&desktop_context));
@@ -423,6 +425,13 @@ impl WebviewInstance {
             ScopeId::ROOT.provide_context(desktop_context.clone());
             ScopeId::ROOT.provide_context(provider);
             ScopeId::ROOT.provide_context(history_provider);
+            // Persistent signals in every window save into the same file in the data directory
+            #[cfg(feature = "persistent")]
+            if let Some(data_dir) = &cfg.data_dir {
+                let storage: Rc<dyn dioxus_signals::PersistentStorage> =
+                    Rc::new(dioxus_signals::FileStorage::in_directory(data_dir));
+                ScopeId::ROOT.provide_context(storage);
+            }
         });
 
         WebviewInstance {
@@ -0,0 +1,103 @@
+use crate::DesktopService;
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+/// A window that can be looked up by its label
+pub(crate) trait LabeledWindow {
+    fn label(&self) -> Option<&str>;
+}
+
+impl LabeledWindow for DesktopService {
+    fn label(&self) -> Option<&str> {
+        DesktopService::label(self)
+    }
+}
+
+/// Every window the app created, in the order they were created.
+///
+/// The registry only holds weak handles, so it never keeps a window alive. Windows that were closed are skipped and
+/// cleaned up the next time the registry is read.
+pub(crate) struct WindowRegistry<W = DesktopService> {
+    windows: RefCell<Vec<Weak<W>>>,
+}
+
+impl<W> Default for WindowRegistry<W> {
+    fn default() -> Self {
+        Self {
+            windows: Default::default(),
+        }
+    }
+}
+
+impl<W: LabeledWindow> WindowRegistry<W> {
+    pub(crate) fn register(&self, window: &Rc<W>) {
+        self.windows.borrow_mut().push(Rc::downgrade(window));
+    }
+
+    /// Get a handle to every window that is still open
+    pub(crate) fn windows(&self) -> Vec<Rc<W>> {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|window| window.strong_count() > 0);
+        windows
+            .iter()
+            .filter_map(|window| window.upgrade())
+            .collect()
+    }
+
+    /// Get the first open window with a label
+    pub(crate) fn find(&self, label: &str) -> Option<Rc<W>> {
+        self.windows()
+            .into_iter()
+            .find(|window| window.label() == Some(label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Window(Option<&'static str>);
+
+    impl LabeledWindow for Window {
+        fn label(&self) -> Option<&str> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn windows_are_found_by_label() {
+        let registry = WindowRegistry::default();
+        let main = Rc::new(Window(None));
+        let settings = Rc::new(Window(Some("settings")));
+        registry.register(&main);
+        registry.register(&settings);
+
+        assert!(Rc::ptr_eq(&registry.find("settings").unwrap(), &settings));
+        assert!(registry.find("main").is_none());
+        assert_eq!(registry.windows().len(), 2);
+    }
+
+    #[test]
+    fn closed_windows_are_removed() {
+        let registry = WindowRegistry::default();
+        let main = Rc::new(Window(None));
+        let settings = Rc::new(Window(Some("settings")));
+        registry.register(&main);
+        registry.register(&settings);
+
+        drop(settings);
+        assert!(registry.find("settings").is_none());
+        assert_eq!(registry.windows().len(), 1);
+        assert_eq!(registry.windows.borrow().len(), 1);
+
+        // A new window can reuse the label of a closed window
+        let new_settings = Rc::new(Window(Some("settings")));
+        registry.register(&new_settings);
+        assert!(Rc::ptr_eq(
+            &registry.find("settings").unwrap(),
+            &new_settings
+        ));
+    }
+}
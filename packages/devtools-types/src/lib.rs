@@ -1,4 +1,7 @@
 use dioxus_core::internal::HotReloadTemplateWithLocation;
+pub use dioxus_core::{
+    ReactiveContextKind, ReactiveContextNode, ReactiveGraph, ReactiveValueKind, ReactiveValueNode,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -31,6 +34,9 @@ pub enum ClientMsg {
         level: String,
         messages: Vec<String>,
     },
+
+    /// A snapshot of the app's reactive graph. This is only sent by apps built with the `reactive-graph` feature.
+    ReactiveGraph(ReactiveGraph),
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -42,11 +42,15 @@ pub mod prelude {
     #[cfg(feature = "signals")]
     pub use dioxus_signals::*;
 
+    // The Store derive macro refers to the signals crate by name
+    #[cfg(feature = "signals")]
+    pub use dioxus_signals;
+
     pub use dioxus_core::prelude::*;
 
     #[cfg(feature = "macro")]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, rsx, Props, Store};
 
     #[cfg(feature = "macro")]
     pub use dioxus_config_macro::*;
@@ -10,8 +10,11 @@
 //! - `hooks`: (default) re-exports `dioxus-hooks`
 //! - `hot-reload`: (default) enables hot rsx reloading in all renderers that support it
 //! - `router`: exports the [router](https://dioxuslabs.com/learn/0.6/router) and enables any router features for the current platform
+//! - `form`: exports `dioxus-form` for form state and validation with `#[derive(Form)]` and `use_form`
 //! - `third-party-renderer`: Just disables warnings about no active platform when no renderers are enabled
 //! - `logger`: Enable the default tracing subscriber for Dioxus apps
+//! - `persistent`: enables signals that are saved in local storage on the web or the data directory on desktop with `use_persistent`
+//! - `reactive-graph`: records the dependency graph between signals, memos, effects and components so it can be inspected with `ReactiveGraph::snapshot` or in the `dx serve` devtools
 //!
 //! Platform features (the current platform determines what platform the [`launch()`] function runs):
 //!
@@ -66,6 +69,10 @@ pub use dioxus_html as html;
 #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
 pub use dioxus_core_macro as core_macro;
 
+#[cfg(feature = "macro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
+pub use dioxus_core_macro::test;
+
 #[cfg(feature = "logger")]
 #[cfg_attr(docsrs, doc(cfg(feature = "logger")))]
 pub use dioxus_logger as logger;
@@ -99,12 +106,17 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "signals")))]
     pub use dioxus_signals::*;
 
+    // The Store derive macro refers to the signals crate by name
+    #[cfg(feature = "signals")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "signals")))]
+    pub use dioxus_signals;
+
     pub use dioxus_core::prelude::*;
 
     #[cfg(feature = "macro")]
     #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
     #[allow(deprecated)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, rsx, Props, Store};
 
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
@@ -139,6 +151,14 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
     pub use dioxus_router::prelude::*;
 
+    #[cfg(feature = "form")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+    pub use dioxus_form;
+
+    #[cfg(feature = "form")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+    pub use dioxus_form::prelude::*;
+
     #[cfg(feature = "asset")]
     #[cfg_attr(docsrs, doc(cfg(feature = "asset")))]
     pub use manganis::{self, *};
@@ -156,6 +176,10 @@ pub use dioxus_web as web;
 #[cfg_attr(docsrs, doc(cfg(feature = "router")))]
 pub use dioxus_router as router;
 
+#[cfg(feature = "form")]
+#[cfg_attr(docsrs, doc(cfg(feature = "form")))]
+pub use dioxus_form as form;
+
 #[cfg(feature = "fullstack")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
 pub use dioxus_fullstack as fullstack;
@@ -22,13 +22,36 @@ pub use cargo_metadata;
 #[non_exhaustive]
 #[derive(Serialize, Deserialize, Clone)]
 pub enum StructuredOutput {
-    BuildFinished { path: PathBuf },
-    BuildUpdate { stage: BuildStage },
-    CargoOutput { message: CompilerMessage },
-    BundleOutput { bundles: Vec<PathBuf> },
-    HtmlTranslate { html: String },
+    BuildFinished {
+        path: PathBuf,
+    },
+    BuildUpdate {
+        stage: BuildStage,
+    },
+    CargoOutput {
+        message: CompilerMessage,
+    },
+    BundleOutput {
+        bundles: Vec<PathBuf>,
+    },
+    HtmlTranslate {
+        html: String,
+    },
+    TestResult {
+        name: String,
+        outcome: TestOutcome,
+        /// The output the test captured. This is only set for failed tests
+        output: Option<String>,
+    },
+    TestsFinished {
+        passed: usize,
+        failed: usize,
+        ignored: usize,
+    },
     Success,
-    Error { message: String },
+    Error {
+        message: String,
+    },
 }
 
 impl std::fmt::Debug for StructuredOutput {
@@ -72,3 +95,12 @@ pub enum BuildStage {
     Restarting,
     CompressingAssets,
 }
+
+/// The outcome of a single test run by `dx test`
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
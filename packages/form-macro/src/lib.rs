@@ -0,0 +1,221 @@
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, LitStr, Type};
+
+/// Derives the `FormFields` trait for a struct with named fields. Each field is mapped to the input with the same name.
+///
+/// Fields can be customized with the `#[form]` attribute:
+/// - `#[form(rename = "name")]`: Use a different name for the input
+/// - `#[form(validate = validator)]`: Check the value of the field with a function or closure that takes a reference to
+///   the value and returns `Result<(), impl Display>`. This attribute can be used more than once.
+/// - `#[form(skip)]`: Don't read the field from the form. The field is set to its default value.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_form::validate::{email, min_length};
+///
+/// #[derive(Form, Clone, Default)]
+/// struct Signup {
+///     #[form(validate = min_length(3))]
+///     name: String,
+///     #[form(rename = "email-address", validate = email)]
+///     email: String,
+///     #[form(validate = |age: &u32| if *age < 13 { Err("You must be 13 or older") } else { Ok(()) })]
+///     age: u32,
+///     newsletter: bool,
+/// }
+/// ```
+#[proc_macro_derive(Form, attributes(form))]
+pub fn derive_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match form_fields(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct FormField {
+    ident: syn::Ident,
+    ty: Type,
+    name: String,
+    validators: Vec<Expr>,
+    skip: bool,
+}
+
+impl FormField {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let ident = field.ident.clone().expect("named fields have identifiers");
+        let mut parsed = Self {
+            name: ident.to_string(),
+            ident,
+            ty: field.ty.clone(),
+            validators: Vec::new(),
+            skip: false,
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("form"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("validate") {
+                    parsed.validators.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename`, `validate` or `skip`"))
+                }
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    /// Run the validators of the field on `value`, returning early with the first error
+    fn run_validators(&self, value: TokenStream2) -> TokenStream2 {
+        let validators = &self.validators;
+        quote! {
+            #(
+                dioxus_form::__private::run_validator(&#value, #validators)?;
+            )*
+        }
+    }
+}
+
+fn form_fields(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &data.fields,
+                    "Form can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Form can only be derived for structs",
+            ))
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(FormField::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let inputs: Vec<_> = fields.iter().filter(|field| !field.skip).collect();
+    let input_names = inputs.iter().map(|field| &field.name);
+
+    let parse_fields = fields.iter().map(|field| {
+        let FormField { ident, ty, name, .. } = field;
+        if field.skip {
+            return quote! { let #ident = ::core::option::Option::Some(::core::default::Default::default()); };
+        }
+        let validators = field.run_validators(quote!(value));
+        quote! {
+            let #ident = match (|| -> ::core::result::Result<#ty, ::std::string::String> {
+                let value = dioxus_form::__private::parse_field::<#ty>(values, #name)?;
+                #validators
+                ::core::result::Result::Ok(value)
+            })() {
+                ::core::result::Result::Ok(value) => ::core::option::Option::Some(value),
+                ::core::result::Result::Err(error) => {
+                    errors.insert(#name, error);
+                    ::core::option::Option::None
+                }
+            };
+        }
+    });
+    let field_idents = fields.iter().map(|field| &field.ident);
+
+    let to_values = inputs.iter().map(|field| {
+        let FormField { ident, name, .. } = field;
+        quote! {
+            values.insert(
+                ::std::string::String::from(#name),
+                dioxus_form::FromFormValue::to_form_value(&self.#ident),
+            );
+        }
+    });
+
+    let validate_inputs = inputs.iter().map(|field| {
+        let FormField { ty, name, .. } = field;
+        let validators = field.run_validators(quote!(value));
+        quote! {
+            #name => {
+                let value = <#ty as dioxus_form::FromFormValue>::from_form_value(value)?;
+                #validators
+                ::core::result::Result::Ok(())
+            }
+        }
+    });
+
+    let validate_values = inputs.iter().map(|field| {
+        let FormField { ident, name, .. } = field;
+        let validators = field.run_validators(quote!(self.#ident));
+        quote! {
+            if let ::core::result::Result::Err(error) = (|| -> ::core::result::Result<(), ::std::string::String> {
+                #validators
+                ::core::result::Result::Ok(())
+            })() {
+                errors.insert(#name, error);
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics dioxus_form::FormFields for #name #ty_generics #where_clause {
+            const FIELDS: &'static [&'static str] = &[#(#input_names),*];
+
+            fn from_values(values: &dioxus_form::FormValues) -> ::core::result::Result<Self, dioxus_form::FieldErrors> {
+                let mut errors = dioxus_form::FieldErrors::new();
+                #(#parse_fields)*
+                if !errors.is_empty() {
+                    return ::core::result::Result::Err(errors);
+                }
+                ::core::result::Result::Ok(Self {
+                    #(#field_idents: #field_idents.unwrap(),)*
+                })
+            }
+
+            fn to_values(&self) -> dioxus_form::FormValues {
+                let mut values = dioxus_form::FormValues::new();
+                #(#to_values)*
+                values
+            }
+
+            fn validate_field(name: &str, value: &[::std::string::String]) -> ::core::result::Result<(), ::std::string::String> {
+                match name {
+                    #(#validate_inputs)*
+                    _ => ::core::result::Result::Ok(()),
+                }
+            }
+
+            fn validate(&self) -> ::core::result::Result<(), dioxus_form::FieldErrors> {
+                let mut errors = dioxus_form::FieldErrors::new();
+                #(#validate_values)*
+                match errors.is_empty() {
+                    true => ::core::result::Result::Ok(()),
+                    false => ::core::result::Result::Err(errors),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,110 @@
+use dioxus_lib::prelude::dioxus_core::CapturedError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Errors for the fields of a form, keyed by the name of the field.
+///
+/// `FieldErrors` can be returned from a server function as a `ServerFnError<FieldErrors>`. [`Form::submit_to`](crate::Form::submit_to)
+/// shows the errors next to the fields they belong to. The errors are displayed as JSON so they can be sent from the
+/// server to the client.
+///
+/// ```rust
+/// # use dioxus_form::FieldErrors;
+/// let errors = FieldErrors::new().with("email", "This email is already taken");
+/// assert_eq!(errors.get("email"), Some("This email is already taken"));
+/// assert_eq!(errors.to_string().parse::<FieldErrors>().unwrap(), errors);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldErrors(BTreeMap<String, String>);
+
+impl FieldErrors {
+    /// Create an empty set of errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error for a field, replacing the error the field already had
+    pub fn insert(&mut self, field: impl Into<String>, error: impl Into<String>) {
+        self.0.insert(field.into(), error.into());
+    }
+
+    /// Add an error for a field and return the errors
+    pub fn with(mut self, field: impl Into<String>, error: impl Into<String>) -> Self {
+        self.insert(field, error);
+        self
+    }
+
+    /// Get the error of a field
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    /// Check if there are no errors
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the fields and their errors
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field, error)| (field.as_str(), error.as_str()))
+    }
+}
+
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(&self.0).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for FieldErrors {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Self)
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
+/// An error returned when a form is submitted. The error is either mapped back to the fields of the form or kept as
+/// an error for the whole form.
+pub trait IntoFieldErrors {
+    /// Get the errors of the fields, or the error for the whole form if the error doesn't belong to a field
+    fn into_field_errors(self) -> Result<FieldErrors, CapturedError>;
+}
+
+impl IntoFieldErrors for FieldErrors {
+    fn into_field_errors(self) -> Result<FieldErrors, CapturedError> {
+        Ok(self)
+    }
+}
+
+impl IntoFieldErrors for CapturedError {
+    fn into_field_errors(self) -> Result<FieldErrors, CapturedError> {
+        Err(self)
+    }
+}
+
+#[cfg(feature = "fullstack")]
+impl IntoFieldErrors for server_fn::ServerFnError<FieldErrors> {
+    fn into_field_errors(self) -> Result<FieldErrors, CapturedError> {
+        match self {
+            server_fn::ServerFnError::WrappedServerError(errors) => Ok(errors),
+            other => Err(CapturedError::from_display(other)),
+        }
+    }
+}
+
+#[cfg(feature = "fullstack")]
+impl IntoFieldErrors for server_fn::ServerFnError {
+    fn into_field_errors(self) -> Result<FieldErrors, CapturedError> {
+        Err(CapturedError::from_display(self))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{FieldErrors, FormValues};
+use dioxus_lib::prelude::FormData;
+
+/// A struct that can be read from the values of a form. This trait is implemented with `#[derive(Form)]`.
+pub trait FormFields: Sized + 'static {
+    /// The names of the inputs this struct is read from
+    const FIELDS: &'static [&'static str];
+
+    /// Parse and validate the struct from the values of a form
+    fn from_values(values: &FormValues) -> Result<Self, FieldErrors>;
+
+    /// Convert the struct into the values of a form
+    fn to_values(&self) -> FormValues;
+
+    /// Parse and validate the value of a single input. Inputs that aren't part of the struct are always valid.
+    fn validate_field(name: &str, value: &[String]) -> Result<(), String>;
+
+    /// Run the validators of every field on the struct. This is useful to check data on the server that was parsed
+    /// without [`FormFields::from_values`], like the arguments of a server function.
+    fn validate(&self) -> Result<(), FieldErrors>;
+
+    /// Parse and validate the struct from the data of a `onsubmit` or `oninput` event
+    fn from_form_data(data: &FormData) -> Result<Self, FieldErrors> {
+        Self::from_values(&form_values(data))
+    }
+}
+
+/// Collect the values of a form event
+pub(crate) fn form_values(data: &FormData) -> FormValues {
+    data.values()
+        .into_iter()
+        .map(|(name, value)| (name, value.0))
+        .collect()
+}
@@ -0,0 +1,451 @@
+use crate::{fields::form_values, FieldErrors, FormFields, IntoFieldErrors};
+use dioxus_lib::prelude::{dioxus_core::CapturedError, *};
+use std::{fmt::Display, future::Future, marker::PhantomData, pin::Pin, rc::Rc};
+
+type AsyncValidator = Rc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Result<(), String>>>>>;
+
+/// Create a [`Form`] that tracks the value, validation and submission state of each field of `T`.
+///
+/// Every field starts with the value from `initial`. Fields are validated with the validators from `#[derive(Form)]`
+/// as they change. Each field is stored in its own signals, so typing into one input only reruns the components that
+/// read that field.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_form::validate::{email, min_length};
+///
+/// #[derive(Form, Clone, Default)]
+/// struct Signup {
+///     #[form(validate = min_length(3))]
+///     name: String,
+///     #[form(validate = email)]
+///     email: String,
+/// }
+///
+/// // This could also be a server function that returns `ServerFnError<FieldErrors>`
+/// async fn signup(form: Signup) -> Result<(), FieldErrors> {
+///     if form.email.ends_with("@example.com") {
+///         return Err(FieldErrors::new().with("email", "This email is already taken"));
+///     }
+///     Ok(())
+/// }
+///
+/// fn App() -> Element {
+///     let form = use_form(Signup::default);
+///
+///     rsx! {
+///         form {
+///             onsubmit: move |event| async move {
+///                 form.submit_to(&event, signup).await;
+///             },
+///             for name in Signup::FIELDS {
+///                 input { ..form.field(name).attributes() }
+///                 if let Some(error) = form.field(name).error() {
+///                     p { "{error}" }
+///                 }
+///             }
+///             input { r#type: "submit", disabled: !form.is_valid() || form.is_submitting() }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_form<T: FormFields>(initial: impl FnOnce() -> T) -> Form<T> {
+    use_hook(|| {
+        let values = initial().to_values();
+        let fields = T::FIELDS
+            .iter()
+            .map(|&name| {
+                let value = values.get(name).cloned().unwrap_or_default();
+                FieldState {
+                    name,
+                    value: Signal::new(value.clone()),
+                    initial: CopyValue::new(value),
+                    touched: Signal::new(false),
+                    error: Signal::new(None),
+                    submit_error: Signal::new(None),
+                    validating: Signal::new(false),
+                    async_validator: CopyValue::new(None),
+                    task: CopyValue::new(None),
+                }
+            })
+            .collect();
+
+        Form {
+            fields: CopyValue::new(fields),
+            submitting: Signal::new(false),
+            submit_error: Signal::new(None),
+            scope: current_scope_id().expect("to be in a dioxus runtime"),
+            phantom: PhantomData,
+        }
+    })
+}
+
+/// The state of a single field of a form
+#[derive(Clone, Copy, PartialEq)]
+struct FieldState {
+    name: &'static str,
+    value: Signal<Vec<String>>,
+    initial: CopyValue<Vec<String>>,
+    touched: Signal<bool>,
+    error: Signal<Option<String>>,
+    /// The error from the last submission. This is cleared when the value changes or the form is submitted again.
+    submit_error: Signal<Option<String>>,
+    validating: Signal<bool>,
+    async_validator: CopyValue<Option<AsyncValidator>>,
+    /// The async validator that is running for the current value
+    task: CopyValue<Option<Task>>,
+}
+
+/// A handle to the state of a form created with [`use_form`]
+pub struct Form<T: 'static> {
+    fields: CopyValue<Vec<FieldState>>,
+    submitting: Signal<bool>,
+    submit_error: Signal<Option<CapturedError>>,
+    scope: ScopeId,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Form<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Form<T> {}
+
+impl<T> PartialEq for Form<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
+}
+
+impl<T: FormFields> Form<T> {
+    /// Get a field of the form by the name of its input
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't one of [`FormFields::FIELDS`]
+    pub fn field(&self, name: &str) -> Field {
+        let state = self
+            .fields
+            .read()
+            .iter()
+            .find(|field| field.name == name)
+            .copied()
+            .unwrap_or_else(|| panic!("`{name}` is not a field of this form"));
+        Field {
+            state,
+            validate: T::validate_field,
+            scope: self.scope,
+        }
+    }
+
+    /// Get every field of the form
+    pub fn fields(&self) -> Vec<Field> {
+        T::FIELDS.iter().map(|name| self.field(name)).collect()
+    }
+
+    /// Parse the current values of the fields into `T`
+    pub fn values(&self) -> Result<T, FieldErrors> {
+        let values = self
+            .fields
+            .read()
+            .iter()
+            .map(|field| (field.name.to_string(), field.value.cloned()))
+            .collect();
+        T::from_values(&values)
+    }
+
+    /// Check if every field is valid and no async validators are running
+    pub fn is_valid(&self) -> bool {
+        self.fields().iter().all(Field::is_valid)
+    }
+
+    /// Check if any field has changed from its initial value
+    pub fn is_dirty(&self) -> bool {
+        self.fields().iter().any(Field::is_dirty)
+    }
+
+    /// Check if the form is being submitted with [`Form::submit_to`]
+    pub fn is_submitting(&self) -> bool {
+        self.submitting.cloned()
+    }
+
+    /// Get the error from the last submission that didn't belong to any field
+    pub fn submit_error(&self) -> Option<CapturedError> {
+        self.submit_error.cloned()
+    }
+
+    /// Show errors next to the fields they belong to. Errors for fields that aren't part of the form are ignored.
+    pub fn set_errors(&self, errors: &FieldErrors) {
+        for (name, error) in errors.iter() {
+            if let Some(mut state) = self.find(name) {
+                state.touched.set(true);
+                state.submit_error.set(Some(error.to_string()));
+            }
+        }
+    }
+
+    /// Reset every field to its initial value and clear all errors
+    pub fn reset(&self) {
+        for field in self.fields() {
+            field.reset();
+        }
+        let mut submit_error = self.submit_error;
+        submit_error.set(None);
+    }
+
+    /// Update the fields from the data of a `onsubmit` event and parse it into `T`. Every field is marked as touched
+    /// so its errors are shown.
+    pub fn parse(&self, data: &FormData) -> Result<T, FieldErrors> {
+        let values = form_values(data);
+        for field in self.fields() {
+            let mut submit_error = field.state.submit_error;
+            submit_error.set(None);
+            let value = values.get(field.name()).cloned().unwrap_or_default();
+            if *field.state.value.peek() != value {
+                field.set_values(value);
+            }
+            field.touch();
+        }
+
+        let parsed = T::from_values(&values)?;
+        // Async validators only run as fields change, so their errors are only stored on the fields
+        let mut errors = FieldErrors::new();
+        for field in self.fields() {
+            if let Some(error) = field.error() {
+                errors.insert(field.name(), error);
+            } else if field.is_validating() {
+                errors.insert(field.name(), "This field is being validated");
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(parsed),
+            false => Err(errors),
+        }
+    }
+
+    /// Parse the data of a `onsubmit` event and pass it to `submit` if it is valid.
+    ///
+    /// If `submit` fails, [`FieldErrors`] are shown next to the fields they belong to and any other error is stored
+    /// in [`Form::submit_error`]. Returns the output of `submit` if the form was valid and `submit` succeeded.
+    pub async fn submit_to<R, E, F>(
+        &self,
+        data: &FormData,
+        submit: impl FnOnce(T) -> F,
+    ) -> Option<R>
+    where
+        E: IntoFieldErrors,
+        F: Future<Output = Result<R, E>>,
+    {
+        let (mut submitting, mut submit_error) = (self.submitting, self.submit_error);
+        submit_error.set(None);
+        let value = self.parse(data).ok()?;
+
+        submitting.set(true);
+        let result = submit(value).await;
+        submitting.set(false);
+
+        match result.map_err(IntoFieldErrors::into_field_errors) {
+            Ok(output) => Some(output),
+            Err(Ok(errors)) => {
+                self.set_errors(&errors);
+                None
+            }
+            Err(Err(error)) => {
+                submit_error.set(Some(error));
+                None
+            }
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<FieldState> {
+        self.fields
+            .read()
+            .iter()
+            .find(|field| field.name == name)
+            .copied()
+    }
+}
+
+/// A handle to a single field of a [`Form`]
+#[derive(Clone, Copy)]
+pub struct Field {
+    state: FieldState,
+    validate: fn(&str, &[String]) -> Result<(), String>,
+    scope: ScopeId,
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl Field {
+    /// Get the name of the input of the field
+    pub fn name(&self) -> &'static str {
+        self.state.name
+    }
+
+    /// Get the value of the field. If the input has more than one value, this is the first value.
+    pub fn value(&self) -> String {
+        self.state.value.read().first().cloned().unwrap_or_default()
+    }
+
+    /// Get all values of the field
+    pub fn values(&self) -> Vec<String> {
+        self.state.value.cloned()
+    }
+
+    /// Set the value of the field and validate it
+    pub fn set(&self, value: impl Into<String>) {
+        self.set_values(vec![value.into()]);
+    }
+
+    /// Set all values of the field and validate them
+    pub fn set_values(&self, values: Vec<String>) {
+        let FieldState {
+            mut value,
+            mut error,
+            mut submit_error,
+            mut validating,
+            async_validator,
+            mut task,
+            ..
+        } = self.state;
+
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+        let result = (self.validate)(self.name(), &values);
+        let first = values.first().cloned().unwrap_or_default();
+        value.set(values);
+        error.set(result.clone().err());
+        submit_error.set(None);
+
+        let validator = async_validator.read().clone();
+        match (result, validator) {
+            (Ok(()), Some(validator)) => {
+                validating.set(true);
+                let running = Runtime::current()
+                    .expect("to be in a dioxus runtime")
+                    .spawn(self.scope, async move {
+                        let result = validator(first).await;
+                        error.set(result.err());
+                        validating.set(false);
+                        task.set(None);
+                    });
+                task.set(Some(running));
+            }
+            _ => validating.set(false),
+        }
+    }
+
+    /// Check the value of the field with an async validator after the validators from `#[derive(Form)]` pass. This
+    /// replaces the last async validator of the field.
+    ///
+    /// The validator runs every time the value changes. If the value changes while the validator is running, the old
+    /// validator is canceled.
+    pub fn set_async_validator<F, E>(&self, validator: impl Fn(String) -> F + 'static)
+    where
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Display,
+    {
+        let mut async_validator = self.state.async_validator;
+        async_validator.set(Some(Rc::new(move |value| {
+            let future = validator(value);
+            Box::pin(async move { future.await.map_err(|error| error.to_string()) })
+        })));
+    }
+
+    /// Mark the field as touched and show the errors of its current value. This is called when the input loses focus.
+    pub fn touch(&self) {
+        let FieldState {
+            mut touched,
+            mut error,
+            value,
+            ..
+        } = self.state;
+        touched.set(true);
+        let result = (self.validate)(self.name(), &value.peek());
+        if let Err(message) = result {
+            error.set(Some(message));
+        }
+    }
+
+    /// Check if the input of the field has lost focus or the form was submitted
+    pub fn is_touched(&self) -> bool {
+        self.state.touched.cloned()
+    }
+
+    /// Check if the value has changed from its initial value
+    pub fn is_dirty(&self) -> bool {
+        *self.state.value.read() != *self.state.initial.read()
+    }
+
+    /// Get the error of the field. Fields are only validated once they change or are touched. Errors from
+    /// [`Form::submit_to`] are shown until the value changes or the form is submitted again.
+    pub fn error(&self) -> Option<String> {
+        let error = self.state.error.cloned();
+        error.or_else(|| self.state.submit_error.cloned())
+    }
+
+    /// Check if the current value is valid and the async validator isn't running
+    pub fn is_valid(&self) -> bool {
+        self.error().is_none()
+            && !self.is_validating()
+            && (self.validate)(self.name(), &self.state.value.read()).is_ok()
+    }
+
+    /// Check if the async validator is running
+    pub fn is_validating(&self) -> bool {
+        self.state.validating.cloned()
+    }
+
+    /// Reset the field to its initial value and clear its error
+    pub fn reset(&self) {
+        let FieldState {
+            mut value,
+            initial,
+            mut touched,
+            mut error,
+            mut submit_error,
+            mut validating,
+            mut task,
+            ..
+        } = self.state;
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+        value.set(initial.cloned());
+        touched.set(false);
+        error.set(None);
+        submit_error.set(None);
+        validating.set(false);
+    }
+
+    /// Get the attributes to bind the field to a text-like input. This sets the `name` and `value` of the input,
+    /// updates the field as the user types and touches it when the input loses focus.
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # #[derive(Form, Default)]
+    /// # struct Login { username: String }
+    /// # fn App() -> Element {
+    /// let form = use_form(Login::default);
+    /// rsx! {
+    ///     input { class: "username", ..form.field("username").attributes() }
+    /// }
+    /// # }
+    /// ```
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let field = *self;
+        vec![
+            Attribute::new("name", self.name(), None, false),
+            Attribute::new("value", self.value(), None, true),
+            dioxus_elements::events::oninput(move |event: FormEvent| field.set(event.value())),
+            dioxus_elements::events::onfocusout(move |_| field.touch()),
+        ]
+    }
+}
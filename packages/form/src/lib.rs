@@ -0,0 +1,41 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![deny(missing_docs)]
+
+mod errors;
+mod fields;
+mod form;
+pub mod validate;
+mod value;
+
+pub use errors::*;
+pub use fields::FormFields;
+pub use form::*;
+pub use value::*;
+
+pub use dioxus_form_macro::Form;
+
+/// A collection of useful items most applications using forms might need.
+pub mod prelude {
+    pub use crate::{
+        use_form, Field, FieldErrors, Form, FormFields, FromFormValue, IntoFieldErrors,
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::{FormValues, FromFormValue};
+    use std::fmt::Display;
+
+    pub fn parse_field<T: FromFormValue>(values: &FormValues, name: &str) -> Result<T, String> {
+        T::from_form_value(values.get(name).map(Vec::as_slice).unwrap_or_default())
+    }
+
+    pub fn run_validator<T: ?Sized, E: Display>(
+        value: &T,
+        validator: impl FnOnce(&T) -> Result<(), E>,
+    ) -> Result<(), String> {
+        validator(value).map_err(|error| error.to_string())
+    }
+}
@@ -0,0 +1,58 @@
+//! Validators that can be used with `#[form(validate = ...)]`.
+//!
+//! A validator is any function or closure that takes a reference to the value of a field and returns
+//! `Result<(), impl Display>`.
+
+use std::fmt::Display;
+
+/// Check that a text field isn't empty or only whitespace
+pub fn required<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+    match value.as_ref().trim().is_empty() {
+        true => Err("This field is required".to_string()),
+        false => Ok(()),
+    }
+}
+
+/// Check that a text field has at least `min` characters
+pub fn min_length<T: AsRef<str> + ?Sized>(min: usize) -> impl Fn(&T) -> Result<(), String> {
+    move |value| match value.as_ref().chars().count() < min {
+        true => Err(format!("Must be at least {min} characters")),
+        false => Ok(()),
+    }
+}
+
+/// Check that a text field has at most `max` characters
+pub fn max_length<T: AsRef<str> + ?Sized>(max: usize) -> impl Fn(&T) -> Result<(), String> {
+    move |value| match value.as_ref().chars().count() > max {
+        true => Err(format!("Must be at most {max} characters")),
+        false => Ok(()),
+    }
+}
+
+/// Check that a value is between `min` and `max`, including both ends
+pub fn range<T: PartialOrd + Display>(min: T, max: T) -> impl Fn(&T) -> Result<(), String> {
+    move |value| match *value < min || *value > max {
+        true => Err(format!("Must be between {min} and {max}")),
+        false => Ok(()),
+    }
+}
+
+/// Check that a text field looks like an email address. This only checks the shape of the address. To know if the
+/// address exists, you need to send an email to it.
+pub fn email<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+    let value = value.as_ref();
+    let valid = match value.split_once('@') {
+        Some((user, domain)) => {
+            !user.is_empty()
+                && !domain.starts_with('.')
+                && domain.contains('.')
+                && !domain.ends_with('.')
+                && !value.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    match valid {
+        true => Ok(()),
+        false => Err("Must be an email address".to_string()),
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+/// The values of the inputs in a form, keyed by the name of the input. Inputs like multi-selects can have more than one
+/// value.
+pub type FormValues = HashMap<String, Vec<String>>;
+
+/// A type that can be read from and written to the values of a form input.
+///
+/// This is implemented for strings, numbers, `bool`, `char` and `Option`s and `Vec`s of them. Implement it for your
+/// own types to use them in a struct that derives `Form`.
+pub trait FromFormValue: Sized {
+    /// Parse the value from the values of an input. An input that isn't in the form has no values.
+    fn from_form_value(values: &[String]) -> Result<Self, String>;
+
+    /// Convert the value into the values of an input
+    fn to_form_value(&self) -> Vec<String>;
+}
+
+fn single(values: &[String]) -> &str {
+    values.first().map(String::as_str).unwrap_or_default()
+}
+
+impl FromFormValue for String {
+    fn from_form_value(values: &[String]) -> Result<Self, String> {
+        Ok(single(values).to_string())
+    }
+
+    fn to_form_value(&self) -> Vec<String> {
+        vec![self.clone()]
+    }
+}
+
+/// Checkboxes are only part of the form when they are checked, so a missing value is `false`
+impl FromFormValue for bool {
+    fn from_form_value(values: &[String]) -> Result<Self, String> {
+        match single(values) {
+            "" | "false" | "off" => Ok(false),
+            "true" | "on" => Ok(true),
+            other => Err(format!("`{other}` is not a valid boolean")),
+        }
+    }
+
+    fn to_form_value(&self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($ty:ty => $expected:literal),* $(,)?) => {
+        $(
+            impl FromFormValue for $ty {
+                fn from_form_value(values: &[String]) -> Result<Self, String> {
+                    let value = single(values).trim();
+                    if value.is_empty() {
+                        return Err("This field is required".to_string());
+                    }
+                    value.parse().map_err(|_| format!("Expected {}", $expected))
+                }
+
+                fn to_form_value(&self) -> Vec<String> {
+                    vec![self.to_string()]
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str! {
+    i8 => "a whole number",
+    i16 => "a whole number",
+    i32 => "a whole number",
+    i64 => "a whole number",
+    i128 => "a whole number",
+    isize => "a whole number",
+    u8 => "a positive whole number",
+    u16 => "a positive whole number",
+    u32 => "a positive whole number",
+    u64 => "a positive whole number",
+    u128 => "a positive whole number",
+    usize => "a positive whole number",
+    f32 => "a number",
+    f64 => "a number",
+    char => "a single character",
+}
+
+/// An empty input is `None`
+impl<T: FromFormValue> FromFormValue for Option<T> {
+    fn from_form_value(values: &[String]) -> Result<Self, String> {
+        match single(values).trim().is_empty() {
+            true => Ok(None),
+            false => T::from_form_value(values).map(Some),
+        }
+    }
+
+    fn to_form_value(&self) -> Vec<String> {
+        self.as_ref().map(T::to_form_value).unwrap_or_default()
+    }
+}
+
+/// Each value of the input is parsed separately
+impl<T: FromFormValue> FromFormValue for Vec<T> {
+    fn from_form_value(values: &[String]) -> Result<Self, String> {
+        values
+            .iter()
+            .map(|value| T::from_form_value(std::slice::from_ref(value)))
+            .collect()
+    }
+
+    fn to_form_value(&self) -> Vec<String> {
+        self.iter().flat_map(T::to_form_value).collect()
+    }
+}
@@ -0,0 +1,265 @@
+#![allow(non_snake_case)]
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_core::{CapturedError, NoOpMutations};
+use dioxus_form::validate::{email, min_length, range};
+use dioxus_html::{FormValue, SerializedFormData};
+
+#[derive(Form, Debug, Clone, Default, PartialEq)]
+struct Signup {
+    #[form(validate = min_length(3))]
+    username: String,
+    #[form(rename = "email-address", validate = email)]
+    email: String,
+    #[form(validate = range(13, 120))]
+    age: u32,
+    nickname: Option<String>,
+    tags: Vec<String>,
+    newsletter: bool,
+    #[form(skip)]
+    id: u64,
+}
+
+fn form_data(values: &[(&str, &[&str])]) -> FormData {
+    let values = values
+        .iter()
+        .map(|(name, values)| {
+            let values = values.iter().map(|value| value.to_string()).collect();
+            (name.to_string(), FormValue(values))
+        })
+        .collect::<HashMap<_, _>>();
+    FormData::new(SerializedFormData::new(String::new(), values))
+}
+
+fn valid_signup() -> FormData {
+    form_data(&[
+        ("username", &["dioxus"]),
+        ("email-address", &["dioxus@example.com"]),
+        ("age", &["21"]),
+        ("nickname", &[""]),
+        ("tags", &["rust", "ui"]),
+        ("newsletter", &["on"]),
+    ])
+}
+
+/// Run the dom until `done` returns true
+async fn run_until(dom: &mut VirtualDom, mut done: impl FnMut(&mut VirtualDom) -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !done(dom) {
+            // Tasks that don't mark a component as dirty don't end `wait_for_work`, so check again after a while
+            tokio::select! {
+                _ = dom.wait_for_work() => {}
+                _ = tokio::time::sleep(Duration::from_millis(5)) => {}
+            }
+            dom.render_immediate(&mut NoOpMutations);
+        }
+    })
+    .await
+    .expect("timed out");
+}
+
+/// Render a component that creates a form and return the dom and the form
+fn render_form() -> (VirtualDom, Form<Signup>) {
+    let form = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |form: Rc<RefCell<Option<Form<Signup>>>>| {
+            let signup = use_form(Signup::default);
+            use_hook(|| *form.borrow_mut() = Some(signup));
+            rsx! {}
+        },
+        form.clone(),
+    );
+    dom.rebuild_in_place();
+    let form = form.borrow().expect("the form to be created");
+    (dom, form)
+}
+
+#[test]
+fn derive_parses_fields() {
+    assert_eq!(
+        Signup::FIELDS,
+        [
+            "username",
+            "email-address",
+            "age",
+            "nickname",
+            "tags",
+            "newsletter"
+        ]
+    );
+
+    let signup = Signup::from_form_data(&valid_signup()).unwrap();
+    assert_eq!(
+        signup,
+        Signup {
+            username: "dioxus".to_string(),
+            email: "dioxus@example.com".to_string(),
+            age: 21,
+            nickname: None,
+            tags: vec!["rust".to_string(), "ui".to_string()],
+            newsletter: true,
+            id: 0,
+        }
+    );
+    assert_eq!(Signup::from_values(&signup.to_values()), Ok(signup));
+}
+
+#[test]
+fn derive_collects_errors_for_every_field() {
+    let errors = Signup::from_form_data(&form_data(&[
+        ("username", &["di"]),
+        ("email-address", &["not an email"]),
+        ("age", &["twelve"]),
+    ]))
+    .unwrap_err();
+
+    assert_eq!(
+        errors.get("username"),
+        Some("Must be at least 3 characters")
+    );
+    assert_eq!(
+        errors.get("email-address"),
+        Some("Must be an email address")
+    );
+    assert_eq!(errors.get("age"), Some("Expected a positive whole number"));
+    assert_eq!(errors.get("newsletter"), None);
+
+    assert_eq!(
+        Signup::validate_field("age", &["12".to_string()]),
+        Err("Must be between 13 and 120".to_string())
+    );
+    let too_young = Signup {
+        username: "dioxus".to_string(),
+        email: "dioxus@example.com".to_string(),
+        age: 12,
+        ..Default::default()
+    };
+    assert_eq!(
+        too_young.validate(),
+        Err(FieldErrors::new().with("age", "Must be between 13 and 120"))
+    );
+}
+
+#[test]
+fn fields_track_their_state() {
+    let (dom, form) = render_form();
+    dom.in_runtime(|| {
+        let username = form.field("username");
+        assert!(!form.is_dirty());
+        assert!(!form.is_valid());
+
+        username.touch();
+        assert!(username.is_touched());
+        assert_eq!(
+            username.error().as_deref(),
+            Some("Must be at least 3 characters")
+        );
+
+        username.set("dioxus");
+        assert!(username.is_dirty());
+        assert_eq!(username.error(), None);
+        assert!(username.is_valid());
+
+        form.reset();
+        assert!(!form.is_dirty());
+        assert!(!username.is_touched());
+        assert_eq!(username.value(), "");
+    });
+}
+
+#[tokio::test]
+async fn async_validators_run_after_sync_validators() {
+    let (mut dom, form) = render_form();
+    let username = dom.in_runtime(|| form.field("username"));
+    dom.in_runtime(|| {
+        username.set_async_validator(|name: String| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            match name == "admin" {
+                true => Err("This username is taken"),
+                false => Ok(()),
+            }
+        });
+
+        // The async validator doesn't run until the sync validators pass
+        username.set("ad");
+        assert!(!username.is_validating());
+
+        username.set("admin");
+        assert!(username.is_validating());
+        assert!(!username.is_valid());
+    });
+
+    run_until(&mut dom, |dom| dom.in_runtime(|| !username.is_validating())).await;
+    dom.in_runtime(|| {
+        assert_eq!(username.error().as_deref(), Some("This username is taken"));
+
+        // Changing the value cancels the running validator
+        username.set("admin");
+        username.set("dioxus");
+    });
+    run_until(&mut dom, |dom| dom.in_runtime(|| !username.is_validating())).await;
+    dom.in_runtime(|| assert!(username.is_valid()));
+}
+
+#[tokio::test]
+async fn submitting_maps_errors_to_fields() {
+    let (dom, form) = render_form();
+    let valid = valid_signup();
+    let invalid = form_data(&[("username", &["di"])]);
+    let submitted = dom
+        .in_runtime(|| form.submit_to(&invalid, |_| async { Ok::<_, FieldErrors>(()) }))
+        .await;
+    assert_eq!(submitted, None);
+    dom.in_runtime(|| {
+        assert!(form.field("email-address").is_touched());
+        assert_eq!(
+            form.field("username").error().as_deref(),
+            Some("Must be at least 3 characters")
+        );
+    });
+
+    let submitted = dom
+        .in_runtime(|| {
+            form.submit_to(&valid, |signup: Signup| async move {
+                Err::<(), _>(
+                    FieldErrors::new().with("username", format!("{} is taken", signup.username)),
+                )
+            })
+        })
+        .await;
+    assert_eq!(submitted, None);
+    dom.in_runtime(|| {
+        assert_eq!(
+            form.field("username").error().as_deref(),
+            Some("dioxus is taken")
+        );
+        assert!(form.submit_error().is_none());
+    });
+
+    let submitted = dom
+        .in_runtime(|| {
+            form.submit_to(&valid, |_| async {
+                Err::<(), _>(CapturedError::from_display("the server is down"))
+            })
+        })
+        .await;
+    assert_eq!(submitted, None);
+    dom.in_runtime(|| {
+        let error = form.submit_error().unwrap().to_string();
+        assert!(error.contains("the server is down"));
+    });
+
+    let submitted = dom
+        .in_runtime(|| {
+            form.submit_to(&valid, |signup: Signup| async move {
+                Ok::<_, FieldErrors>(signup.age)
+            })
+        })
+        .await;
+    assert_eq!(submitted, Some(21));
+    dom.in_runtime(|| assert!(form.submit_error().is_none()));
+}
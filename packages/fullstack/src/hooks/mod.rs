@@ -1,2 +1,4 @@
 pub mod server_cached;
 pub mod server_future;
+pub mod server_query;
+pub mod server_stream;
@@ -0,0 +1,74 @@
+use super::server_future::use_server_future;
+use dioxus_lib::prelude::{dioxus_core::CapturedError, *};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+/// Fetch data that is cached under a key like [`use_query`], but load the first data on the server.
+///
+/// On the server, this waits for the first fetch before continuing to render and serializes the data into the page. When
+/// the page is hydrated on the client, the data is put into the [`QueryClient`] cache so the client doesn't fetch it
+/// again. After that, the query works like [`use_query`]: it is deduplicated, refetched when invalidated and shared with
+/// every other component that uses the same key.
+///
+/// Like [`use_server_future`], this suspends until the first data is loaded. Only the first key is loaded on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+///
+/// #[server]
+/// async fn fetch_todos() -> Result<Vec<String>, ServerFnError> {
+///     Ok(vec!["Buy milk".to_string()])
+/// }
+///
+/// fn Todos() -> Element {
+///     let todos = use_server_query(
+///         || "todos",
+///         || async { fetch_todos().await.map_err(dioxus_core::CapturedError::from_display) },
+///     )?;
+///
+///     rsx! {
+///         for todo in todos.data().cloned().unwrap_or_default() {
+///             p { "{todo}" }
+///         }
+///     }
+/// }
+/// ```
+#[must_use = "Consider using `use_query` to fetch data that doesn't need to be rendered on the server"]
+#[track_caller]
+pub fn use_server_query<T, E, K, F>(
+    key: impl Fn() -> K + 'static,
+    fetcher: impl Fn() -> F + 'static,
+) -> Result<Query<T>, RenderError>
+where
+    T: Clone + Serialize + DeserializeOwned + 'static,
+    E: Into<CapturedError>,
+    K: Into<QueryKey>,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let client = use_query_client();
+    let first_key = use_hook(|| key().into());
+
+    // The first fetch is created outside of the server future so the server future doesn't subscribe to any signals the
+    // fetcher reads. Later fetches are run by the query.
+    let first_fetch = use_hook(|| {
+        let cached = client.data::<T>(first_key.clone());
+        Rc::new(RefCell::new(cached.is_none().then(&fetcher)))
+    });
+    let first_data = use_server_future(move || {
+        let fetch = first_fetch.borrow_mut().take();
+        async move {
+            let fetch = fetch?;
+            // Errors are sent as text since `CapturedError` is only serializable with some features of dioxus-core
+            Some(fetch.await.map_err(|err| err.into().to_string()))
+        }
+    })?;
+
+    // If the first fetch failed, the query fetches the data again so it can retry
+    use_hook(|| {
+        if let Some(Ok(data)) = first_data.peek().clone().flatten() {
+            client.set_initial_data(first_key, data);
+        }
+    });
+
+    Ok(use_query(key, fetcher))
+}
@@ -0,0 +1,247 @@
+use dioxus_lib::prelude::*;
+use futures_util::StreamExt;
+use server_fn::ServerFnError;
+use std::future::Future;
+
+use crate::stream::TypedStream;
+
+/// Calls a server function that returns a [`TypedStream`] and collects the values into a list as they arrive.
+///
+/// The closure is reactive: if it reads any signals, the stream is restarted with an empty list when they change. The
+/// stream is dropped when the component unmounts, which closes the response.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus::fullstack::prelude::*;
+///
+/// #[server(output = JsonLines)]
+/// async fn search(query: String) -> Result<TypedStream<String>, ServerFnError> {
+///     let results = ["dioxus", "dioxus-fullstack"].map(|name| format!("{name} matches {query}"));
+///     Ok(futures_util::stream::iter(results).into())
+/// }
+///
+/// fn App() -> Element {
+///     let mut query = use_signal(|| "dioxus".to_string());
+///     // The stream restarts whenever the query changes
+///     let results = use_server_stream(move || search(query()));
+///
+///     rsx! {
+///         input { value: "{query}", oninput: move |event| query.set(event.value()) }
+///         ul {
+///             for result in results.items().iter() {
+///                 li { "{result}" }
+///             }
+///         }
+///         if let ServerStreamState::Failed(err) = results.state()() {
+///             "Search failed: {err}"
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_server_stream<T, F>(mut stream: impl FnMut() -> F + 'static) -> ServerStream<T>
+where
+    T: 'static,
+    F: Future<Output = Result<TypedStream<T>, ServerFnError>> + 'static,
+{
+    let mut items = use_signal(Vec::new);
+    let mut state = use_signal(|| ServerStreamState::Connecting);
+
+    let resource = use_resource(move || {
+        let response = stream();
+        async move {
+            items.write().clear();
+            state.set(ServerStreamState::Connecting);
+
+            let stream = match response.await {
+                Ok(stream) => stream.into_inner(),
+                Err(err) => {
+                    state.set(ServerStreamState::Failed(err));
+                    return;
+                }
+            };
+            futures_util::pin_mut!(stream);
+
+            state.set(ServerStreamState::Streaming);
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(item) => items.write().push(item),
+                    Err(err) => {
+                        state.set(ServerStreamState::Failed(err));
+                        return;
+                    }
+                }
+            }
+            state.set(ServerStreamState::Finished);
+        }
+    });
+
+    ServerStream {
+        items,
+        state,
+        resource,
+    }
+}
+
+/// The state of a [`ServerStream`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerStreamState {
+    /// The server function was called, but the response hasn't started yet
+    Connecting,
+    /// Values are arriving from the server
+    Streaming,
+    /// The stream ended normally
+    Finished,
+    /// The server function or the stream returned an error. Values that arrived before the error are kept.
+    Failed(ServerFnError),
+    /// The stream was stopped with [`ServerStream::cancel`]
+    Cancelled,
+}
+
+/// A handle to a stream of values from a server function, created with [`use_server_stream`].
+pub struct ServerStream<T: 'static> {
+    items: Signal<Vec<T>>,
+    state: Signal<ServerStreamState>,
+    resource: Resource<()>,
+}
+
+impl<T> ServerStream<T> {
+    /// The values that have arrived so far. Reading the list subscribes to new values.
+    pub fn items(&self) -> ReadOnlySignal<Vec<T>> {
+        self.items.into()
+    }
+
+    /// The state of the stream
+    pub fn state(&self) -> ReadOnlySignal<ServerStreamState> {
+        self.state.into()
+    }
+
+    /// Check if the stream ended, either normally, with an error, or because it was cancelled
+    pub fn is_finished(&self) -> bool {
+        !matches!(
+            *self.state.read(),
+            ServerStreamState::Connecting | ServerStreamState::Streaming
+        )
+    }
+
+    /// Clear the values and call the server function again
+    pub fn restart(&mut self) {
+        self.resource.restart();
+    }
+
+    /// Stop reading the stream. Values that already arrived are kept.
+    pub fn cancel(&mut self) {
+        self.resource.cancel();
+        let state = self.state.peek().clone();
+        if matches!(
+            state,
+            ServerStreamState::Connecting | ServerStreamState::Streaming
+        ) {
+            self.state.set(ServerStreamState::Cancelled);
+        }
+    }
+}
+
+impl<T> PartialEq for ServerStream<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items && self.state == other.state && self.resource == other.resource
+    }
+}
+
+impl<T> Clone for ServerStream<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ServerStream<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    type Values = UnboundedReceiver<Result<u32, ServerFnError>>;
+    type Renders = Rc<RefCell<Vec<(Vec<u32>, ServerStreamState)>>>;
+
+    #[derive(Clone)]
+    struct AppProps {
+        values: Rc<RefCell<Option<Values>>>,
+        renders: Renders,
+    }
+
+    fn app(props: AppProps) -> Element {
+        let values = props.values.clone();
+        let stream = use_server_stream(move || {
+            let values = values.borrow_mut().take().unwrap();
+            async move { Ok(TypedStream::new(values)) }
+        });
+        props
+            .renders
+            .borrow_mut()
+            .push((stream.items().read().clone(), stream.state().read().clone()));
+        rsx! {}
+    }
+
+    /// Render the app until the stream has nothing left to do
+    async fn settle(dom: &mut VirtualDom) {
+        while tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work())
+            .await
+            .is_ok()
+        {
+            dom.render_immediate_to_vec();
+        }
+    }
+
+    /// Start the app and return the sender for the values of its stream and the list of what each render saw
+    fn start() -> (
+        VirtualDom,
+        UnboundedSender<Result<u32, ServerFnError>>,
+        Renders,
+    ) {
+        let (tx, rx) = unbounded();
+        let renders = Rc::new(RefCell::new(Vec::new()));
+        let mut dom = VirtualDom::new_with_props(
+            app,
+            AppProps {
+                values: Rc::new(RefCell::new(Some(rx))),
+                renders: renders.clone(),
+            },
+        );
+        dom.rebuild_in_place();
+        (dom, tx, renders)
+    }
+
+    #[tokio::test]
+    async fn values_are_collected_as_they_arrive() {
+        let (mut dom, tx, renders) = start();
+        let last = || renders.borrow().last().cloned().unwrap();
+        settle(&mut dom).await;
+        assert_eq!(last(), (vec![], ServerStreamState::Streaming));
+
+        tx.unbounded_send(Ok(1)).unwrap();
+        settle(&mut dom).await;
+        assert_eq!(last(), (vec![1], ServerStreamState::Streaming));
+
+        tx.unbounded_send(Ok(2)).unwrap();
+        drop(tx);
+        settle(&mut dom).await;
+        assert_eq!(last(), (vec![1, 2], ServerStreamState::Finished));
+    }
+
+    #[tokio::test]
+    async fn values_are_kept_when_the_stream_fails() {
+        let (mut dom, tx, renders) = start();
+        let err = ServerFnError::ServerError("the database is down".to_string());
+        tx.unbounded_send(Ok(1)).unwrap();
+        tx.unbounded_send(Err(err.clone())).unwrap();
+        settle(&mut dom).await;
+        assert_eq!(
+            renders.borrow().last().cloned().unwrap(),
+            (vec![1], ServerStreamState::Failed(err))
+        );
+    }
+}
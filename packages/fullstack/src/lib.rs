@@ -14,6 +14,8 @@ pub mod server;
 
 mod hooks;
 
+mod stream;
+
 pub mod document;
 #[cfg(feature = "server")]
 mod render;
@@ -31,7 +33,14 @@ mod server_context;
 /// A prelude of commonly used items in dioxus-fullstack.
 pub mod prelude {
     use crate::hooks;
-    pub use hooks::{server_cached::use_server_cached, server_future::use_server_future};
+    pub use hooks::{
+        server_cached::use_server_cached,
+        server_future::use_server_future,
+        server_query::use_server_query,
+        server_stream::{use_server_stream, ServerStream, ServerStreamState},
+    };
+
+    pub use crate::stream::{JsonLines, ServerSentEvents, TypedStream};
 
     #[cfg(feature = "axum")]
     #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
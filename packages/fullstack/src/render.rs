@@ -14,7 +14,10 @@ use std::fmt::Write;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::RwLock;
-use std::{collections::HashMap, future::Future};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+};
 use tokio::task::JoinHandle;
 
 use crate::{prelude::*, StreamingMode};
@@ -61,7 +64,7 @@ pub enum SSRError {
 
 struct SsrRendererPool {
     renderers: RwLock<Vec<Renderer>>,
-    incremental_cache: Option<RwLock<dioxus_isrg::IncrementalRenderer>>,
+    incremental_cache: Option<dioxus_isrg::IncrementalRenderer>,
 }
 
 impl SsrRendererPool {
@@ -72,37 +75,36 @@ impl SsrRendererPool {
         let renderers = RwLock::new((0..initial_size).map(|_| pre_renderer()).collect());
         Self {
             renderers,
-            incremental_cache: incremental.map(|cache| RwLock::new(cache.build())),
+            incremental_cache: incremental.map(|cache| cache.build()),
         }
     }
 
     /// Look for a cached route in the incremental cache and send it into the render channel if it exists
-    fn check_cached_route(
+    ///
+    /// Returns the freshness of the cached route and whether this request should re-render the stale route in the background
+    async fn check_cached_route(
         &self,
         route: &str,
         render_into: &mut Sender<Result<String, dioxus_isrg::IncrementalRendererError>>,
-    ) -> Option<RenderFreshness> {
+    ) -> Option<(RenderFreshness, bool)> {
         if let Some(incremental) = &self.incremental_cache {
-            if let Ok(mut incremental) = incremental.write() {
-                match incremental.get(route) {
-                    Ok(Some(cached_render)) => {
-                        let CachedRender {
-                            freshness,
-                            response,
-                            ..
-                        } = cached_render;
-                        _ = render_into.start_send(String::from_utf8(response.to_vec()).map_err(
-                            |err| dioxus_isrg::IncrementalRendererError::Other(Box::new(err)),
-                        ));
-                        return Some(freshness);
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to get route \"{route}\" from incremental cache: {e}"
-                        );
-                    }
-                    _ => {}
+            match incremental.get(route).await {
+                Ok(Some(cached_render)) => {
+                    let CachedRender {
+                        freshness,
+                        response,
+                        revalidate,
+                        ..
+                    } = cached_render;
+                    _ = render_into.start_send(String::from_utf8(response.into_owned()).map_err(
+                        |err| dioxus_isrg::IncrementalRendererError::Other(Box::new(err)),
+                    ));
+                    return Some((freshness, revalidate));
+                }
+                Err(e) => {
+                    tracing::error!("Failed to get route \"{route}\" from incremental cache: {e}");
                 }
+                _ => {}
             }
         }
         None
@@ -154,19 +156,85 @@ impl SsrRendererPool {
             Result<String, dioxus_isrg::IncrementalRendererError>,
         >(1000);
 
-        let (initial_result_tx, initial_result_rx) = futures_channel::oneshot::channel();
-
         // before we even spawn anything, we can check synchronously if we have the route cached
-        if let Some(freshness) = self.check_cached_route(&route, &mut into) {
-            return Ok((
-                freshness,
-                ReceiverWithDrop {
-                    receiver: rx,
-                    cancel_task: None,
-                },
-            ));
+        match self.check_cached_route(&route, &mut into).await {
+            Some((freshness, false)) => {
+                return Ok((
+                    freshness,
+                    ReceiverWithDrop {
+                        receiver: rx,
+                        cancel_task: None,
+                    },
+                ));
+            }
+            // If the cached route is stale and this request is responsible for revalidating it, render the route again
+            // in the background. The client already has the stale page, so the new render only goes into the cache.
+            // The render gets its own server context so it can't change the response that is sent with the stale page
+            Some((freshness, true)) => {
+                let (background_into, _) = futures_channel::mpsc::channel(0);
+                let (background_result_tx, _) = futures_channel::oneshot::channel();
+                let revalidation_guard = RevalidationGuard {
+                    pool: self.clone(),
+                    route: route.clone(),
+                    cached: false,
+                };
+                self.clone().spawn_render(
+                    cfg,
+                    route,
+                    virtual_dom_factory,
+                    &server_context.detached(),
+                    background_into,
+                    background_result_tx,
+                    Some(revalidation_guard),
+                );
+                return Ok((
+                    freshness,
+                    ReceiverWithDrop {
+                        receiver: rx,
+                        cancel_task: None,
+                    },
+                ));
+            }
+            None => {}
         }
 
+        let (initial_result_tx, initial_result_rx) = futures_channel::oneshot::channel();
+        let join_handle = self.spawn_render(
+            cfg,
+            route,
+            virtual_dom_factory,
+            server_context,
+            into,
+            initial_result_tx,
+            None,
+        );
+
+        // Wait for the initial result which determines the status code
+        initial_result_rx.await.map_err(|err| {
+            SSRError::Incremental(IncrementalRendererError::Other(Box::new(err)))
+        })??;
+
+        Ok((
+            RenderFreshness::now(None),
+            ReceiverWithDrop {
+                receiver: rx,
+                cancel_task: Some(join_handle),
+            },
+        ))
+    }
+
+    /// Render a virtual dom into a channel in a background task
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_render(
+        self: Arc<Self>,
+        cfg: &ServeConfig,
+        route: String,
+        virtual_dom_factory: impl FnOnce() -> VirtualDom + Send + Sync + 'static,
+        server_context: &DioxusServerContext,
+        mut into: Sender<Result<String, dioxus_isrg::IncrementalRendererError>>,
+        initial_result_tx: futures_channel::oneshot::Sender<Result<(), SSRError>>,
+        mut revalidation_guard: Option<RevalidationGuard>,
+    ) -> JoinHandle<()> {
         let wrapper = FullstackHTMLTemplate { cfg: cfg.clone() };
 
         let server_context = server_context.clone();
@@ -180,7 +248,7 @@ impl SsrRendererPool {
         let myself = self.clone();
         let streaming_mode = cfg.streaming_mode;
 
-        let join_handle = spawn_platform(move || async move {
+        spawn_platform(move || async move {
             let mut virtual_dom = virtual_dom_factory();
             let document = std::rc::Rc::new(crate::document::server::ServerDocument::default());
             virtual_dom.provide_root_context(document.clone());
@@ -255,14 +323,6 @@ impl SsrRendererPool {
             let scope_to_mount_mapping = Arc::new(RwLock::new(HashMap::new()));
 
             renderer.pre_render = true;
-            {
-                let scope_to_mount_mapping = scope_to_mount_mapping.clone();
-                let stream = stream.clone();
-                renderer.set_render_components(streaming_render_component_callback(
-                    stream,
-                    scope_to_mount_mapping,
-                ));
-            }
 
             macro_rules! throw_error {
                 ($e:expr) => {
@@ -271,8 +331,25 @@ impl SsrRendererPool {
                 };
             }
 
-            // Render the initial frame with loading placeholders
-            let mut initial_frame = renderer.render(&virtual_dom);
+            let mut initial_frame = if streaming_mode == StreamingMode::InOrder {
+                // Stream the whole page in document order. Every suspense boundary is resolved once this finishes
+                if let Err(err) =
+                    render_in_order(&mut renderer, &mut virtual_dom, &stream, &server_context).await
+                {
+                    throw_error!(err);
+                }
+                String::new()
+            } else {
+                let scope_to_mount_mapping = scope_to_mount_mapping.clone();
+                let stream = stream.clone();
+                renderer.set_render_components(streaming_render_component_callback(
+                    stream,
+                    scope_to_mount_mapping,
+                ));
+
+                // Render the initial frame with loading placeholders
+                renderer.render(&virtual_dom)
+            };
 
             // Along with the initial frame, we render the html after the main element, but before the body tag closes. This should include the script that starts loading the wasm bundle.
             if let Err(err) = wrapper.render_after_main(&mut initial_frame, &virtual_dom) {
@@ -360,8 +437,11 @@ impl SsrRendererPool {
                 }
                 cached_render.push_str(&post_streaming);
 
-                if let Ok(mut incremental) = incremental.write() {
-                    let _ = incremental.cache(route, cached_render);
+                if incremental.cache(route, cached_render).await.is_ok() {
+                    // Caching the route ends the revalidation, so the guard doesn't need to cancel it
+                    if let Some(guard) = &mut revalidation_guard {
+                        guard.cached = true;
+                    }
                 }
             }
 
@@ -369,20 +449,169 @@ impl SsrRendererPool {
 
             renderer.reset_render_components();
             myself.renderers.write().unwrap().push(renderer);
-        });
+            drop(revalidation_guard);
+        })
+    }
+}
 
-        // Wait for the initial result which determines the status code
-        initial_result_rx.await.map_err(|err| {
-            SSRError::Incremental(IncrementalRendererError::Other(Box::new(err)))
-        })??;
+/// Cancels a revalidation claimed by a background render if the render fails before caching the route, so
+/// the next request for the stale route will try again
+struct RevalidationGuard {
+    pool: Arc<SsrRendererPool>,
+    route: String,
+    /// Set once the new render is cached
+    cached: bool,
+}
 
-        Ok((
-            RenderFreshness::now(None),
-            ReceiverWithDrop {
-                receiver: rx,
-                cancel_task: Some(join_handle),
+impl Drop for RevalidationGuard {
+    fn drop(&mut self) {
+        if self.cached {
+            return;
+        }
+        if let Some(incremental) = &self.pool.incremental_cache {
+            incremental.cancel_revalidation(&self.route);
+        }
+    }
+}
+
+/// Stream the page in document order. Each render stops at the first suspense boundary that is still pending, and
+/// the html up to that boundary is sent to the client. Once the boundary resolves, the page is rendered again and
+/// streaming continues up to the next pending boundary. Components that were fully rendered before the boundary are
+/// reused from the last render, so only the html from the resolved boundary onward is rendered again.
+async fn render_in_order(
+    renderer: &mut Renderer,
+    virtual_dom: &mut VirtualDom,
+    stream: &StreamingRenderer<IncrementalRendererError>,
+    server_context: &DioxusServerContext,
+) -> Result<(), IncrementalRendererError> {
+    let pending_boundary = Arc::new(RwLock::new(None));
+    renderer.set_render_components(in_order_render_component_callback(
+        pending_boundary.clone(),
+        Default::default(),
+    ));
+    let mut capturing_errors = HashSet::new();
+
+    loop {
+        let mut html = String::new();
+        let result = renderer.render_to(&mut html, virtual_dom);
+        let pending = pending_boundary.write().unwrap().take();
+        if let (Err(err), None) = (result, pending) {
+            return Err(IncrementalRendererError::RenderError(err));
+        }
+        if let Err(err) = stream.render_in_order(&html) {
+            tracing::error!(
+                "Html before a pending suspense boundary changed after it was streamed to the client"
+            );
+            return Err(IncrementalRendererError::RenderError(err));
+        }
+        let Some(pending) = pending else {
+            return Ok(());
+        };
+
+        // Everything before the boundary was already sent, so errors inside the boundary can't bubble up and
+        // rerender that html. Capture them at the boundary and send them to the client instead
+        if capturing_errors.insert(pending) {
+            virtual_dom.in_runtime(|| start_capturing_errors(pending));
+        }
+
+        ProvideServerContext::new(virtual_dom.wait_for_suspense_work(), server_context.clone())
+            .await;
+        ProvideServerContext::new(
+            virtual_dom.render_suspense_immediate(),
+            server_context.clone(),
+        )
+        .await;
+    }
+}
+
+/// Html of the components that were fully rendered in an earlier in order render
+#[derive(Default)]
+struct InOrderCache {
+    scopes: HashMap<ScopeId, RenderedScope>,
+    /// The scopes rendered inside each component that is currently rendering, with their generation
+    rendering: Vec<Vec<(ScopeId, usize)>>,
+}
+
+struct RenderedScope {
+    html: String,
+    /// The number of hydration ids used by the html
+    hydration_ids: usize,
+    /// The scope and every scope inside of it, with the generation they had when they were rendered
+    scopes: Vec<(ScopeId, usize)>,
+}
+
+/// Create the in order render component callback. If the render reaches a suspense boundary that is still pending, it
+/// stores the boundary and stops the render with an error so the html before the boundary can be sent to the client.
+///
+/// Scopes before the pending boundary are not inside a suspended boundary, so they don't rerun while suspense resolves.
+/// Their html is cached and written again without rendering them. If one of them did rerun, it is rendered again.
+fn in_order_render_component_callback(
+    pending_boundary: Arc<RwLock<Option<ScopeId>>>,
+    cache: Arc<RwLock<InOrderCache>>,
+) -> impl Fn(&mut Renderer, &mut dyn Write, &VirtualDom, ScopeId) -> std::fmt::Result
+       + Send
+       + Sync
+       + 'static {
+    move |renderer, to, vdom, scope| {
+        let is_pending_suspense_boundary =
+            SuspenseContext::downcast_suspense_boundary_from_scope(&vdom.runtime(), scope)
+                .filter(|s| s.has_suspended_tasks())
+                .is_some();
+        if is_pending_suspense_boundary {
+            *pending_boundary.write().unwrap() = Some(scope);
+            return Err(std::fmt::Error);
+        }
+
+        let generation = |scope: ScopeId| {
+            vdom.get_scope(scope)
+                .and_then(|_| vdom.in_runtime(|| scope.generation()))
+        };
+        {
+            let mut cache = cache.write().unwrap();
+            let cache = &mut *cache;
+            let cached = cache.scopes.get(&scope).filter(|cached| {
+                cached
+                    .scopes
+                    .iter()
+                    .all(|(scope, rendered)| generation(*scope) == Some(*rendered))
+            });
+            if let Some(cached) = cached {
+                to.write_str(&cached.html)?;
+                renderer.set_hydration_id(renderer.hydration_id() + cached.hydration_ids);
+                if let Some(parent) = cache.rendering.last_mut() {
+                    parent.extend_from_slice(&cached.scopes);
+                }
+                return Ok(());
+            }
+            cache.rendering.push(Vec::new());
+        }
+
+        let first_hydration_id = renderer.hydration_id();
+        let mut html = String::new();
+        let result = renderer.render_scope(&mut html, vdom, scope);
+        let mut cache = cache.write().unwrap();
+        let mut scopes = cache.rendering.pop().unwrap_or_default();
+        // Write the html before a pending boundary even if the render stopped there
+        to.write_str(&html)?;
+        result?;
+
+        // The scopes inside are part of this html now, so they don't need their own entries
+        for (inner, _) in &scopes {
+            cache.scopes.remove(inner);
+        }
+        scopes.extend(generation(scope).map(|generation| (scope, generation)));
+        if let Some(parent) = cache.rendering.last_mut() {
+            parent.extend_from_slice(&scopes);
+        }
+        cache.scopes.insert(
+            scope,
+            RenderedScope {
+                html,
+                hydration_ids: renderer.hydration_id() - first_hydration_id,
+                scopes,
             },
-        ))
+        );
+        Ok(())
     }
 }
 
@@ -642,3 +871,170 @@ fn pre_renderer() -> Renderer {
     renderer.pre_render = true;
     renderer
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[component]
+    fn Slow(delay: u64, text: &'static str) -> Element {
+        let text = use_resource(move || async move {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            text
+        })
+        .suspend()?;
+        rsx! { p { "{text}" } }
+    }
+
+    fn app() -> Element {
+        rsx! {
+            p { "before" }
+            SuspenseBoundary { fallback: |_| rsx! { "loading" },
+                Slow { delay: 20, text: "first" }
+            }
+            p { "middle" }
+            SuspenseBoundary { fallback: |_| rsx! { "loading" },
+                Slow { delay: 5, text: "second" }
+            }
+            p { "after" }
+        }
+    }
+
+    fn request() -> DioxusServerContext {
+        DioxusServerContext::new(http::Request::new(()).into_parts().0)
+    }
+
+    #[tokio::test]
+    async fn boundaries_are_streamed_in_document_order() {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        let (into, chunks) = futures_channel::mpsc::channel(100);
+        let stream = StreamingRenderer::new("", into);
+        let mut renderer = pre_renderer();
+        render_in_order(&mut renderer, &mut dom, &stream, &request())
+            .await
+            .unwrap();
+        drop(stream);
+
+        let chunks: Vec<String> = chunks.map(Result::unwrap).collect().await;
+        let chunks: Vec<&str> = chunks.iter().map(String::as_str).collect();
+        // The html before the first boundary is sent while it is loading
+        let first = chunks.iter().position(|c| c.contains("first")).unwrap();
+        assert!(chunks[..first].concat().contains("before"));
+        // The second boundary resolves first, but it is only sent with or after the first boundary
+        let second = chunks.iter().position(|c| c.contains("second")).unwrap();
+        assert!(first <= second);
+        assert!(!chunks.concat().contains("loading"));
+
+        // The streamed page is the same as rendering the resolved page at once
+        assert_eq!(chunks.concat(), pre_renderer().render(&dom));
+    }
+
+    #[tokio::test]
+    async fn html_before_the_pending_boundary_is_not_rendered_again() {
+        let mut dom = VirtualDom::new(|| {
+            rsx! {
+                Static {}
+                SuspenseBoundary { fallback: |_| rsx! { "loading" },
+                    Slow { delay: 5, text: "resolved" }
+                }
+            }
+        });
+        #[component]
+        fn Static() -> Element {
+            rsx! { p { "static" } }
+        }
+        dom.rebuild_in_place();
+
+        let pending = Arc::new(RwLock::new(None));
+        let cache = Arc::new(RwLock::new(InOrderCache::default()));
+        let mut renderer = pre_renderer();
+        renderer.set_render_components(in_order_render_component_callback(
+            pending.clone(),
+            cache.clone(),
+        ));
+        let mut html = String::new();
+        assert!(renderer.render_to(&mut html, &dom).is_err());
+        assert!(pending.write().unwrap().take().is_some());
+
+        // Change the cached html of the component before the boundary to see if it is written again
+        for scope in cache.write().unwrap().scopes.values_mut() {
+            scope.html = scope.html.replace("static", "cached");
+        }
+        dom.wait_for_suspense().await;
+        let mut html = String::new();
+        renderer.render_to(&mut html, &dom).unwrap();
+        assert!(html.contains(">cached</p>"));
+        assert!(html.contains("resolved"));
+    }
+
+    #[tokio::test]
+    async fn stale_routes_are_revalidated_with_their_own_server_context() {
+        static RENDERS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        fn app() -> Element {
+            let render = use_hook(|| {
+                let render = RENDERS.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                server_context()
+                    .response_parts_mut()
+                    .headers
+                    .insert("x-render", render.into());
+                render
+            });
+            rsx! { p { "render {render}" } }
+        }
+
+        let cfg = ServeConfig::builder()
+            .index_html(
+                r#"<html><head></head><body><div id="main"></div></body></html>"#.to_string(),
+            )
+            .incremental(
+                dioxus_isrg::IncrementalRendererConfig::new()
+                    .static_dir(
+                        std::env::temp_dir().join("dioxus-fullstack-stale-while-revalidate"),
+                    )
+                    .invalidate_after(Duration::ZERO)
+                    .stale_while_revalidate(Duration::from_secs(60)),
+            )
+            .build()
+            .unwrap();
+        let state = SSRState::new(&cfg);
+        let render = |server_context: DioxusServerContext| {
+            let (state, cfg) = (state.clone(), cfg.clone());
+            async move {
+                let (_, body) = state
+                    .render(
+                        "/".to_string(),
+                        &cfg,
+                        || VirtualDom::new(app),
+                        &server_context,
+                    )
+                    .await
+                    .unwrap_or_else(|_| panic!("failed to render the route"));
+                let body: Vec<String> = body.map(Result::unwrap).collect().await;
+                body.concat()
+            }
+        };
+
+        assert!(render(request()).await.contains("render 1"));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // The stale render is sent right away and the route is rendered again in the background
+        let stale_request = request();
+        assert!(render(stale_request.clone()).await.contains("render 1"));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !render(request()).await.contains("render 2") {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("the stale route was never revalidated");
+
+        // The background render didn't change the response of the request that started it
+        assert!(stale_request
+            .response_parts()
+            .headers
+            .get("x-render")
+            .is_none());
+    }
+}
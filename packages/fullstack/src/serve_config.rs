@@ -336,6 +336,25 @@ impl ServeConfigBuilder {
         self
     }
 
+    /// Enable in order streaming. This will stream the page to the client in document order, pausing at each suspense
+    /// boundary until it resolves.
+    ///
+    /// It is equivalent to calling `streaming_mode(StreamingMode::InOrder)`
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app() -> Element { todo!() }
+    /// dioxus::LaunchBuilder::new()
+    ///     .with_context(server_only! {
+    ///         dioxus::fullstack::ServeConfig::builder().enable_in_order_streaming()
+    ///     })
+    ///     .launch(app);
+    /// ```
+    pub fn enable_in_order_streaming(mut self) -> Self {
+        self.streaming_mode = StreamingMode::InOrder;
+        self
+    }
+
     /// Build the ServeConfig. This may fail if the index.html file is not found.
     pub fn build(self) -> Result<ServeConfig, UnableToLoadIndex> {
         // The CLI always bundles static assets into the exe/public directory
@@ -475,6 +494,12 @@ pub enum StreamingMode {
     /// Out of order streaming is enabled; server futures are resolved out of order and streamed to the client
     /// as they resolve
     OutOfOrder,
+    /// In order streaming is enabled; the page is streamed to the client up to the first pending suspense boundary.
+    /// Once that boundary resolves, streaming continues up to the next pending boundary.
+    ///
+    /// Unlike [`StreamingMode::OutOfOrder`], the streamed html doesn't depend on javascript, so clients with javascript
+    /// disabled and crawlers see the full page. A slow suspense boundary holds back everything after it in the document.
+    InOrder,
 }
 
 /// Used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
@@ -28,9 +28,10 @@ pub struct DioxusServerContext {
     pub(crate) parts: Arc<RwLock<http::request::Parts>>,
 }
 
+#[derive(Clone)]
 enum ContextType {
-    Factory(Box<dyn Fn() -> Box<dyn Any> + Send + Sync>),
-    Value(Box<dyn Any + Send + Sync>),
+    Factory(Arc<dyn Fn() -> Box<dyn Any> + Send + Sync>),
+    Value(Arc<dyn Any + Send + Sync>),
 }
 
 impl ContextType {
@@ -84,6 +85,18 @@ mod server_fn_impl {
             }
         }
 
+        /// Create a server context for the same request with its own response and a copy of the shared context. Changes
+        /// to the new context don't affect the response of the request this context belongs to.
+        pub(crate) fn detached(&self) -> Self {
+            Self {
+                parts: Arc::new(RwLock::new(self.parts.read().clone())),
+                shared_context: Arc::new(RwLock::new(self.shared_context.read().clone())),
+                response_parts: std::sync::Arc::new(RwLock::new(
+                    http::response::Response::new(()).into_parts().0,
+                )),
+            }
+        }
+
         /// Clone a value from the shared server context. If you are using [`DioxusRouterExt`](crate::prelude::DioxusRouterExt), any values you insert into
         /// the launch context will also be available in the server context.
         ///
@@ -128,7 +141,7 @@ mod server_fn_impl {
         pub fn insert_any(&self, value: Box<dyn Any + Send + Sync + 'static>) {
             self.shared_context
                 .write()
-                .insert((*value).type_id(), ContextType::Value(value));
+                .insert((*value).type_id(), ContextType::Value(value.into()));
         }
 
         /// Insert a factory that creates a non-sync value for the shared server context
@@ -139,7 +152,7 @@ mod server_fn_impl {
         {
             self.shared_context.write().insert(
                 TypeId::of::<T>(),
-                ContextType::Factory(Box::new(move || Box::new(value()))),
+                ContextType::Factory(Arc::new(move || Box::new(value()))),
             );
         }
 
@@ -147,7 +160,7 @@ mod server_fn_impl {
         pub fn insert_boxed_factory(&self, value: Box<dyn Fn() -> Box<dyn Any> + Send + Sync>) {
             self.shared_context
                 .write()
-                .insert((*value()).type_id(), ContextType::Factory(value));
+                .insert((*value()).type_id(), ContextType::Factory(value.into()));
         }
 
         /// Get the response parts from the server context
@@ -0,0 +1,444 @@
+//! Typed streams of values returned from server functions.
+
+use std::{fmt::Display, pin::Pin, str::FromStr};
+
+use bytes::Bytes;
+use futures_util::{future, stream, Stream, StreamExt};
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+use server_fn::{
+    codec::{Encoding, FromRes, IntoRes},
+    error::{NoCustomError, ServerFnErrorSerde},
+    response::{ClientRes, Res},
+    ServerFnError,
+};
+
+/// An output encoding that sends each value of a [`TypedStream`] as a line of JSON ([newline delimited JSON](https://github.com/ndjson/ndjson-spec)).
+///
+/// If the stream yields an error on the server, the response ends early and the client receives an error.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus::fullstack::prelude::{JsonLines, TypedStream};
+///
+/// #[server(output = JsonLines)]
+/// async fn count_to(to: u32) -> Result<TypedStream<u32>, ServerFnError> {
+///     Ok(futures_util::stream::iter(0..to).into())
+/// }
+/// ```
+pub struct JsonLines;
+
+impl Encoding for JsonLines {
+    const CONTENT_TYPE: &'static str = "application/x-ndjson";
+    const METHOD: Method = Method::POST;
+}
+
+/// An output encoding that sends each value of a [`TypedStream`] as a [server-sent event](https://html.spec.whatwg.org/multipage/server-sent-events.html)
+/// with the value serialized as JSON in the `data` field.
+///
+/// Errors from the stream on the server are sent as an `error` event, so the client receives the same
+/// [`ServerFnError`] the server returned.
+///
+/// Server functions are called with a `POST` request, so the response is read by the server function client instead of
+/// a browser `EventSource`.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus::fullstack::prelude::{ServerSentEvents, TypedStream};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// pub struct Progress {
+///     done: u32,
+///     total: u32,
+/// }
+///
+/// #[server(output = ServerSentEvents)]
+/// async fn import_progress() -> Result<TypedStream<Progress>, ServerFnError> {
+///     let progress = futures_util::stream::iter((0..=10).map(|done| Progress { done, total: 10 }));
+///     Ok(progress.into())
+/// }
+/// ```
+pub struct ServerSentEvents;
+
+impl Encoding for ServerSentEvents {
+    const CONTENT_TYPE: &'static str = "text/event-stream";
+    const METHOD: Method = Method::POST;
+}
+
+/// A stream of typed values returned from a server function.
+///
+/// Return a `TypedStream` from a server function with the [`JsonLines`] or [`ServerSentEvents`] output encoding. Any
+/// stream of serializable values can be converted into a `TypedStream` with [`Into`]. On the client, read the values
+/// incrementally with [`TypedStream::into_inner`] or
+/// [`use_server_stream`](crate::prelude::use_server_stream).
+pub struct TypedStream<T, CustErr = NoCustomError>(
+    Pin<Box<dyn Stream<Item = Result<T, ServerFnError<CustErr>>> + Send>>,
+);
+
+impl<T> TypedStream<T> {
+    /// Create a typed stream from a stream of values that may fail
+    pub fn new(value: impl Stream<Item = Result<T, ServerFnError>> + Send + 'static) -> Self {
+        Self(Box::pin(value))
+    }
+}
+
+impl<T, CustErr> TypedStream<T, CustErr> {
+    /// Consume the wrapper, returning the stream of values
+    pub fn into_inner(self) -> impl Stream<Item = Result<T, ServerFnError<CustErr>>> + Send {
+        self.0
+    }
+}
+
+impl<T, CustErr> std::fmt::Debug for TypedStream<T, CustErr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedStream").finish()
+    }
+}
+
+impl<S, T> From<S> for TypedStream<T>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: 'static,
+{
+    fn from(value: S) -> Self {
+        Self(Box::pin(value.map(Ok)))
+    }
+}
+
+impl<T, CustErr, Response> IntoRes<JsonLines, Response, CustErr> for TypedStream<T, CustErr>
+where
+    Response: Res<CustErr>,
+    T: Serialize + 'static,
+    CustErr: 'static,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        let lines = self.into_inner().map(|value| {
+            let mut line = serde_json::to_vec(&value?)
+                .map_err(|err| ServerFnError::Serialization(err.to_string()))?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        });
+        Response::try_from_stream(JsonLines::CONTENT_TYPE, lines)
+    }
+}
+
+impl<T, Response> FromRes<JsonLines, Response, NoCustomError> for TypedStream<T>
+where
+    Response: ClientRes<NoCustomError> + Send,
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError> {
+        let lines = frames(res.try_into_stream()?, b"\n");
+        Ok(Self::new(lines.filter_map(|line| {
+            future::ready(match line {
+                Ok(line) if line.trim().is_empty() => None,
+                Ok(line) => Some(deserialize(&line)),
+                Err(err) => Some(Err(err)),
+            })
+        })))
+    }
+}
+
+impl<T, CustErr, Response> IntoRes<ServerSentEvents, Response, CustErr> for TypedStream<T, CustErr>
+where
+    Response: Res<CustErr>,
+    T: Serialize + 'static,
+    CustErr: FromStr + Display + 'static,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        let events = self.into_inner().map(|value| {
+            let event = match value.and_then(|value| {
+                serde_json::to_string(&value)
+                    .map_err(|err| ServerFnError::Serialization(err.to_string()))
+            }) {
+                Ok(data) => format!("data: {data}\n\n"),
+                Err(err) => {
+                    let data = err.ser().unwrap_or_default();
+                    let mut event = "event: error\n".to_string();
+                    for line in data.lines() {
+                        event += &format!("data: {line}\n");
+                    }
+                    event + "\n"
+                }
+            };
+            Ok(Bytes::from(event))
+        });
+        Response::try_from_stream(ServerSentEvents::CONTENT_TYPE, events)
+    }
+}
+
+impl<T, Response> FromRes<ServerSentEvents, Response, NoCustomError> for TypedStream<T>
+where
+    Response: ClientRes<NoCustomError> + Send,
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError> {
+        let events = frames(res.try_into_stream()?, b"\n\n");
+        Ok(Self::new(events.filter_map(|event| {
+            future::ready(match event {
+                Ok(event) => parse_event(&event),
+                Err(err) => Some(Err(err)),
+            })
+        })))
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(data: &str) -> Result<T, ServerFnError> {
+    serde_json::from_str(data).map_err(|err| ServerFnError::Deserialization(err.to_string()))
+}
+
+/// Parse a server-sent event. Events without data, like comments used to keep the connection alive, are skipped.
+fn parse_event<T: DeserializeOwned>(event: &str) -> Option<Result<T, ServerFnError>> {
+    let mut name = None;
+    let mut data: Option<String> = None;
+    for line in event.lines() {
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => name = Some(value),
+            "data" => match &mut data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => data = Some(value.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    let data = data?;
+    Some(match name {
+        Some("error") => Err(ServerFnError::de(&data)),
+        _ => deserialize(&data),
+    })
+}
+
+/// Split a body into the frames between a delimiter. Any data after the last delimiter is yielded as the last frame.
+fn frames(
+    body: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+    delimiter: &'static [u8],
+) -> impl Stream<Item = Result<String, ServerFnError>> + Send {
+    struct Frames<S> {
+        body: S,
+        buffer: Vec<u8>,
+        finished: bool,
+    }
+
+    let frames = Frames {
+        body: Box::pin(body),
+        buffer: Vec::new(),
+        finished: false,
+    };
+
+    stream::unfold(frames, move |mut frames| async move {
+        loop {
+            let end = frames
+                .buffer
+                .windows(delimiter.len())
+                .position(|window| window == delimiter);
+            let frame = match end {
+                Some(end) => {
+                    let frame = frames.buffer[..end].to_vec();
+                    frames.buffer.drain(..end + delimiter.len());
+                    frame
+                }
+                None if frames.finished && !frames.buffer.is_empty() => {
+                    std::mem::take(&mut frames.buffer)
+                }
+                None if frames.finished => return None,
+                None => {
+                    match frames.body.next().await {
+                        Some(Ok(bytes)) => frames.buffer.extend_from_slice(&bytes),
+                        Some(Err(err)) => {
+                            frames.finished = true;
+                            frames.buffer.clear();
+                            return Some((Err(err), frames));
+                        }
+                        None => frames.finished = true,
+                    }
+                    continue;
+                }
+            };
+            let frame = String::from_utf8(frame)
+                .map_err(|err| ServerFnError::Deserialization(err.to_string()));
+            return Some((frame, frames));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+    use std::{
+        sync::Mutex,
+        task::{Context, Poll},
+    };
+
+    fn body(chunks: &[&'static str]) -> impl Stream<Item = Result<Bytes, ServerFnError>> + Send {
+        stream::iter(chunks.to_vec()).map(|chunk| Ok(Bytes::from(chunk)))
+    }
+
+    type Body = Pin<Box<dyn Stream<Item = Result<Bytes, ServerFnError>> + Send>>;
+
+    /// A response that hands the body the server wrote to the client in chunks of three bytes, so values are split
+    /// across chunks
+    struct ChunkedResponse(Mutex<Body>);
+
+    impl Res<NoCustomError> for ChunkedResponse {
+        fn try_from_string(_: &str, data: String) -> Result<Self, ServerFnError> {
+            Self::try_from_bytes("", Bytes::from(data))
+        }
+
+        fn try_from_bytes(_: &str, data: Bytes) -> Result<Self, ServerFnError> {
+            Self::try_from_stream("", stream::once(future::ready(Ok(data))))
+        }
+
+        fn try_from_stream(
+            _: &str,
+            data: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+        ) -> Result<Self, ServerFnError> {
+            let chunks = data.flat_map(|chunk| {
+                let chunks: Vec<_> = match chunk {
+                    Ok(bytes) => (0..bytes.len())
+                        .step_by(3)
+                        .map(|start| Ok(bytes.slice(start..(start + 3).min(bytes.len()))))
+                        .collect(),
+                    Err(err) => vec![Err(err)],
+                };
+                stream::iter(chunks)
+            });
+            Ok(Self(Mutex::new(Box::pin(chunks))))
+        }
+
+        fn error_response(_: &str, err: &ServerFnError) -> Self {
+            Self::try_from_stream("", stream::once(future::ready(Err(err.clone())))).unwrap()
+        }
+
+        fn redirect(&mut self, _: &str) {}
+    }
+
+    impl ClientRes<NoCustomError> for ChunkedResponse {
+        async fn try_into_string(self) -> Result<String, ServerFnError> {
+            unimplemented!()
+        }
+
+        async fn try_into_bytes(self) -> Result<Bytes, ServerFnError> {
+            unimplemented!()
+        }
+
+        fn try_into_stream(
+            self,
+        ) -> Result<
+            impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+            ServerFnError,
+        > {
+            let body = self.0;
+            Ok(stream::poll_fn(
+                move |cx: &mut Context| -> Poll<Option<_>> {
+                    body.lock().unwrap().poll_next_unpin(cx)
+                },
+            ))
+        }
+
+        fn status(&self) -> u16 {
+            200
+        }
+
+        fn status_text(&self) -> String {
+            "OK".to_string()
+        }
+
+        fn location(&self) -> String {
+            String::new()
+        }
+
+        fn has_redirect(&self) -> bool {
+            false
+        }
+    }
+
+    /// Encode a stream on the server and decode it on the client
+    async fn roundtrip<Enc>(
+        values: Vec<Result<String, ServerFnError>>,
+    ) -> Vec<Result<String, ServerFnError>>
+    where
+        TypedStream<String>: IntoRes<Enc, ChunkedResponse, NoCustomError>
+            + FromRes<Enc, ChunkedResponse, NoCustomError>,
+    {
+        let response =
+            <TypedStream<String> as IntoRes<Enc, ChunkedResponse, NoCustomError>>::into_res(
+                TypedStream::new(stream::iter(values)),
+            )
+            .await
+            .unwrap();
+        let stream =
+            <TypedStream<String> as FromRes<Enc, ChunkedResponse, NoCustomError>>::from_res(
+                response,
+            )
+            .await
+            .unwrap();
+        stream.into_inner().collect().await
+    }
+
+    fn values() -> Vec<Result<String, ServerFnError>> {
+        [
+            "first",
+            "a value that is split across many chunks",
+            "",
+            "multi\nline",
+        ]
+        .map(|value| Ok(value.to_string()))
+        .into()
+    }
+
+    #[test]
+    fn json_lines_roundtrip() {
+        let decoded = roundtrip::<JsonLines>(values()).now_or_never().unwrap();
+        assert_eq!(decoded, values());
+    }
+
+    #[test]
+    fn server_sent_events_roundtrip() {
+        let decoded = roundtrip::<ServerSentEvents>(values())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(decoded, values());
+
+        // Errors from the server end up on the client
+        let err = ServerFnError::ServerError("the\ndatabase is down".to_string());
+        let values = vec![Ok("before".to_string()), Err(err)];
+        let decoded = roundtrip::<ServerSentEvents>(values.clone())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn frames_are_split_across_chunks() {
+        let frames = frames(body(&["1\n2", "2\n", "\n33"]), b"\n");
+        let frames: Vec<_> = frames.collect::<Vec<_>>().now_or_never().unwrap();
+        let frames: Vec<_> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames, ["1", "22", "", "33"]);
+    }
+
+    #[test]
+    fn events_are_parsed() {
+        assert_eq!(parse_event::<u32>("data: 1"), Some(Ok(1)));
+        assert_eq!(
+            parse_event::<Vec<u32>>("event: message\ndata: [1,\ndata: 2]"),
+            Some(Ok(vec![1, 2]))
+        );
+        assert_eq!(parse_event::<u32>(": keep alive"), None);
+
+        let err = ServerFnError::<NoCustomError>::ServerError("the\ndatabase is down".into());
+        let event = format!(
+            "event: error\ndata: {}",
+            err.ser().unwrap().replace('\n', "\ndata: ")
+        );
+        assert_eq!(parse_event::<u32>(&event), Some(Err(err)));
+    }
+}
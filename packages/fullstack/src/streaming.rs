@@ -2,7 +2,10 @@
 //! 1. Stream the HTML in order - this will work even if javascript is disabled, but if there is something slow at the top of your page, and fast at the bottom, nothing will render until the slow part is done
 //! 2. Render placeholders and stream the HTML out of order - this will only work if javascript is enabled. This lets you render any parts of your page that resolve quickly, and then render the rest of the page as it becomes available
 //!
-//! Dioxus currently uses a the second out of order streaming approach which requires javascript. The rendering structure is as follows:
+//! Dioxus supports both approaches with [`StreamingMode`](crate::StreamingMode). In order streaming re-renders the page every time a suspense
+//! boundary resolves, and sends down any html between the end of the last chunk and the next pending suspense boundary.
+//!
+//! Out of order streaming requires javascript. The rendering structure is as follows:
 //! ```html
 //! // Initial content is sent down with placeholders
 //! <div>
@@ -63,6 +66,8 @@ impl Display for MountPath {
 pub(crate) struct StreamingRenderer<E = std::convert::Infallible> {
     channel: RwLock<Sender<Result<String, E>>>,
     current_path: RwLock<MountPath>,
+    /// The html of the page that has already been sent while streaming in order
+    sent_in_order: RwLock<String>,
 }
 
 impl<E> StreamingRenderer<E> {
@@ -77,6 +82,7 @@ impl<E> StreamingRenderer<E> {
         Self {
             channel: render_into.into(),
             current_path: Default::default(),
+            sent_in_order: Default::default(),
         }
     }
 
@@ -89,6 +95,20 @@ impl<E> StreamingRenderer<E> {
             .start_send(Ok(html.to_string()));
     }
 
+    /// Render the page up to the first pending suspense boundary while streaming in order. Only the html after
+    /// the content that was sent by the last call is sent to the client.
+    ///
+    /// Returns an error if the html that was already sent changed, because the client can't receive a consistent page anymore
+    pub(crate) fn render_in_order(&self, html: &str) -> std::fmt::Result {
+        let mut sent = self.sent_in_order.write().unwrap();
+        let new_html = html.strip_prefix(sent.as_str()).ok_or(std::fmt::Error)?;
+        if !new_html.is_empty() {
+            self.render(new_html);
+            sent.push_str(new_html);
+        }
+        Ok(())
+    }
+
     /// Render a new chunk of html that may change
     pub(crate) fn render_placeholder<W: Write + ?Sized>(
         &self,
@@ -161,3 +181,35 @@ impl Display for Mount {
         write!(f, "{}", self.id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_new_html_is_sent_in_order() {
+        let (into, mut chunks) = futures_channel::mpsc::channel(10);
+        let stream = StreamingRenderer::<std::convert::Infallible>::new("<head>", into);
+        let mut next_chunk = move || chunks.try_next().ok().flatten().map(Result::unwrap);
+        assert_eq!(next_chunk().as_deref(), Some("<head>"));
+
+        stream.render_in_order("<p>before</p>").unwrap();
+        assert_eq!(next_chunk().as_deref(), Some("<p>before</p>"));
+        stream
+            .render_in_order("<p>before</p><p>boundary</p>")
+            .unwrap();
+        assert_eq!(next_chunk().as_deref(), Some("<p>boundary</p>"));
+
+        // Nothing is sent if the html didn't grow
+        stream
+            .render_in_order("<p>before</p><p>boundary</p>")
+            .unwrap();
+        assert_eq!(next_chunk(), None);
+
+        // The html that was already sent can't change
+        assert!(stream
+            .render_in_order("<p>changed</p><p>boundary</p>")
+            .is_err());
+        assert_eq!(next_chunk(), None);
+    }
+}
@@ -13,12 +13,14 @@ use std::{
 pub use error::*;
 pub use references::*;
 pub use sync::SyncStorage;
+pub use tracker::*;
 pub use unsync::UnsyncStorage;
 
 mod entry;
 mod error;
 mod references;
 mod sync;
+mod tracker;
 mod unsync;
 
 /// The type erased id of a generational box.
@@ -56,9 +58,16 @@ impl<T, S: Storage<T>> GenerationalBox<T, S> {
     /// a box that needs to be manually dropped with no owners.
     #[track_caller]
     pub fn leak(value: T, location: &'static std::panic::Location<'static>) -> Self {
-        let location = S::new(value, location);
+        let raw = S::new(value, location);
+        #[cfg(debug_assertions)]
+        tracker::track(
+            raw.id(),
+            location,
+            std::any::type_name::<T>(),
+            BoxOwner::Leaked,
+        );
         Self {
-            raw: location,
+            raw,
             _marker: PhantomData,
         }
     }
@@ -67,9 +76,16 @@ impl<T, S: Storage<T>> GenerationalBox<T, S> {
     /// a box that needs to be manually dropped with no owners.
     #[track_caller]
     pub fn leak_rc(value: T, location: &'static std::panic::Location<'static>) -> Self {
-        let location = S::new_rc(value, location);
+        let raw = S::new_rc(value, location);
+        #[cfg(debug_assertions)]
+        tracker::track(
+            raw.id(),
+            location,
+            std::any::type_name::<T>(),
+            BoxOwner::Leaked,
+        );
         Self {
-            raw: location,
+            raw,
             _marker: PhantomData,
         }
     }
@@ -138,8 +154,16 @@ impl<T, S: Storage<T>> GenerationalBox<T, S> {
     /// Get a reference to the value
     #[track_caller]
     pub fn leak_reference(&self) -> BorrowResult<GenerationalBox<T, S>> {
+        let raw = S::new_reference(self.raw)?;
+        #[cfg(debug_assertions)]
+        tracker::track(
+            raw.id(),
+            std::panic::Location::caller(),
+            std::any::type_name::<T>(),
+            BoxOwner::Leaked,
+        );
         Ok(Self {
-            raw: S::new_reference(self.raw)?,
+            raw,
             _marker: std::marker::PhantomData,
         })
     }
@@ -251,6 +275,8 @@ pub trait AnyStorage: Default + 'static {
     fn owner() -> Owner<Self> {
         Owner(Arc::new(Mutex::new(OwnerInner {
             owned: Default::default(),
+            #[cfg(debug_assertions)]
+            label: None,
         })))
     }
 }
@@ -330,6 +356,8 @@ impl<S> GenerationalPointer<S> {
     where
         S: AnyStorage,
     {
+        #[cfg(debug_assertions)]
+        tracker::untrack(self.id());
         S::recycle(self);
     }
 
@@ -346,6 +374,29 @@ impl<S> GenerationalPointer<S> {
 
 struct OwnerInner<S: AnyStorage + 'static> {
     owned: Vec<GenerationalPointer<S>>,
+    /// A name for the owner in leak reports
+    #[cfg(debug_assertions)]
+    label: Option<Arc<str>>,
+}
+
+impl<S: AnyStorage> OwnerInner<S> {
+    /// Take ownership of a new box
+    fn own<T>(
+        &mut self,
+        pointer: GenerationalPointer<S>,
+        caller: &'static std::panic::Location<'static>,
+    ) {
+        #[cfg(debug_assertions)]
+        tracker::track(
+            pointer.id(),
+            caller,
+            std::any::type_name::<T>(),
+            BoxOwner::Owned(self.label.clone()),
+        );
+        #[cfg(not(debug_assertions))]
+        let _ = caller;
+        self.owned.push(pointer);
+    }
 }
 
 impl<S: AnyStorage> Drop for OwnerInner<S> {
@@ -372,6 +423,15 @@ impl<S: AnyStorage> Clone for Owner<S> {
 }
 
 impl<S: AnyStorage> Owner<S> {
+    /// Set a name for the owner that is shown in [`LeakReport`]s. This only affects boxes inserted after the label
+    /// is set.
+    pub fn set_label(&self, #[allow(unused)] label: impl std::fmt::Display) {
+        #[cfg(debug_assertions)]
+        {
+            self.0.lock().label = Some(label.to_string().into());
+        }
+    }
+
     /// Insert a value into the store. The value will be dropped when the owner is dropped.
     #[track_caller]
     pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T, S>
@@ -400,7 +460,7 @@ impl<S: AnyStorage> Owner<S> {
         S: Storage<T>,
     {
         let location = S::new_rc(value, caller);
-        self.0.lock().owned.push(location);
+        self.0.lock().own::<T>(location, caller);
         GenerationalBox {
             raw: location,
             _marker: std::marker::PhantomData,
@@ -417,7 +477,7 @@ impl<S: AnyStorage> Owner<S> {
         S: Storage<T>,
     {
         let location = S::new(value, caller);
-        self.0.lock().owned.push(location);
+        self.0.lock().own::<T>(location, caller);
         GenerationalBox {
             raw: location,
             _marker: PhantomData,
@@ -435,8 +495,11 @@ impl<S: AnyStorage> Owner<S> {
     where
         S: Storage<T>,
     {
-        let location = other.leak_reference()?;
-        self.0.lock().owned.push(location.raw);
-        Ok(location)
+        let raw = S::new_reference(other.raw)?;
+        self.0.lock().own::<T>(raw, std::panic::Location::caller());
+        Ok(GenerationalBox {
+            raw,
+            _marker: PhantomData,
+        })
     }
 }
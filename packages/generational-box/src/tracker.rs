@@ -0,0 +1,290 @@
+//! Tracking for the generational boxes that are alive. This is only compiled in debug builds.
+
+use std::{
+    fmt::{Display, Formatter},
+    panic::Location,
+    sync::Arc,
+};
+
+/// Who is responsible for dropping a generational box
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BoxOwner {
+    /// The box is dropped with an [`Owner`](crate::Owner). The label is set with [`Owner::set_label`](crate::Owner::set_label).
+    Owned(Option<Arc<str>>),
+    /// The box was created with [`GenerationalBox::leak`](crate::GenerationalBox::leak) and is only dropped with
+    /// [`GenerationalBox::manually_drop`](crate::GenerationalBox::manually_drop).
+    Leaked,
+}
+
+impl Display for BoxOwner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Owned(Some(label)) => write!(f, "owned by {label}"),
+            Self::Owned(None) => write!(f, "owned by an unlabeled owner"),
+            Self::Leaked => write!(f, "leaked"),
+        }
+    }
+}
+
+/// The generational boxes that were created at the same location with the same owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoxGroup {
+    /// Where the boxes were created
+    pub created_at: &'static Location<'static>,
+    /// The type of the value in the boxes
+    pub type_name: &'static str,
+    /// Who is responsible for dropping the boxes
+    pub owner: BoxOwner,
+    /// The number of boxes that are alive
+    pub count: usize,
+    /// If the boxes were leaked, whether the number of leaked boxes from this location is over the
+    /// [leak threshold](LeakTracker::set_leak_threshold)
+    pub over_threshold: bool,
+}
+
+/// A snapshot of the generational boxes that are alive, created with [`LeakTracker::report`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeakReport {
+    groups: Vec<BoxGroup>,
+}
+
+impl LeakReport {
+    /// Get the groups of boxes, from the largest group to the smallest
+    pub fn groups(&self) -> &[BoxGroup] {
+        &self.groups
+    }
+
+    /// Get the number of boxes that are alive
+    pub fn total(&self) -> usize {
+        self.groups.iter().map(|group| group.count).sum()
+    }
+
+    /// Get the number of boxes that were leaked and are alive
+    pub fn leaked(&self) -> usize {
+        self.groups
+            .iter()
+            .filter(|group| group.owner == BoxOwner::Leaked)
+            .map(|group| group.count)
+            .sum()
+    }
+
+    /// Check if there are no boxes alive
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Only keep the groups of boxes that match a predicate
+    pub fn retain(&mut self, f: impl FnMut(&BoxGroup) -> bool) {
+        self.groups.retain(f);
+    }
+}
+
+impl Display for LeakReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} generational boxes are alive ({} leaked)",
+            self.total(),
+            self.leaked()
+        )?;
+        for group in &self.groups {
+            write!(
+                f,
+                "\n{:>8} x {} created at {} {}",
+                group.count, group.type_name, group.created_at, group.owner
+            )?;
+            if group.over_threshold {
+                write!(f, " (over the leak threshold)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the generational boxes that are alive to help find memory leaks.
+///
+/// Tracking is off by default. Once it is enabled with [`LeakTracker::enable`], every box created afterwards is
+/// recorded with its creation location and owner until it is dropped. Tracking is only available in debug builds. In
+/// release builds, enabling the tracker does nothing and reports are always empty.
+///
+/// ```rust
+/// use generational_box::{BoxOwner, GenerationalBox, LeakTracker, Owner, UnsyncStorage};
+///
+/// LeakTracker::enable();
+/// let owner = Owner::<UnsyncStorage>::default();
+/// owner.set_label("settings");
+/// let owned = owner.insert(1);
+/// let leaked = GenerationalBox::<_, UnsyncStorage>::leak(2, std::panic::Location::caller());
+///
+/// # #[cfg(debug_assertions)]
+/// # {
+/// let report = LeakTracker::report();
+/// assert!(report.groups().iter().any(|group| group.owner == BoxOwner::Leaked));
+/// println!("{report}");
+/// # }
+///
+/// drop(owner);
+/// leaked.manually_drop();
+/// ```
+pub struct LeakTracker;
+
+impl LeakTracker {
+    /// Start tracking the boxes that are created
+    pub fn enable() {
+        #[cfg(debug_assertions)]
+        imp::ENABLED.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Stop tracking boxes and forget the boxes that were tracked
+    pub fn disable() {
+        #[cfg(debug_assertions)]
+        {
+            imp::ENABLED.store(false, std::sync::atomic::Ordering::Relaxed);
+            *imp::registry() = Default::default();
+        }
+    }
+
+    /// Check if boxes are being tracked
+    pub fn is_enabled() -> bool {
+        #[cfg(debug_assertions)]
+        return imp::ENABLED.load(std::sync::atomic::Ordering::Relaxed);
+        #[cfg(not(debug_assertions))]
+        return false;
+    }
+
+    /// Set the number of leaked boxes from a single location that can be alive before the location is flagged. A
+    /// warning is logged the first time a location goes over the threshold. Defaults to 100.
+    pub fn set_leak_threshold(#[allow(unused)] threshold: usize) {
+        #[cfg(debug_assertions)]
+        imp::THRESHOLD.store(threshold, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Get a report of the boxes that are alive, grouped by where they were created and who owns them
+    pub fn report() -> LeakReport {
+        #[cfg(debug_assertions)]
+        return imp::report();
+        #[cfg(not(debug_assertions))]
+        return LeakReport::default();
+    }
+}
+
+#[cfg(debug_assertions)]
+pub(crate) use imp::{track, untrack};
+
+#[cfg(debug_assertions)]
+mod imp {
+    use super::*;
+    use crate::GenerationalBoxId;
+    use parking_lot::{Mutex, MutexGuard};
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            OnceLock,
+        },
+    };
+
+    pub(super) static ENABLED: AtomicBool = AtomicBool::new(false);
+    pub(super) static THRESHOLD: AtomicUsize = AtomicUsize::new(100);
+
+    struct TrackedBox {
+        created_at: &'static Location<'static>,
+        type_name: &'static str,
+        owner: BoxOwner,
+    }
+
+    #[derive(Default)]
+    pub(super) struct Registry {
+        live: HashMap<GenerationalBoxId, TrackedBox>,
+        /// The number of leaked boxes that are alive for each location
+        leaked: HashMap<&'static Location<'static>, usize>,
+        /// Locations that have already been warned about
+        warned: HashSet<&'static Location<'static>>,
+    }
+
+    pub(super) fn registry() -> MutexGuard<'static, Registry> {
+        static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+        REGISTRY.get_or_init(Default::default).lock()
+    }
+
+    /// Record that a box was created
+    pub(crate) fn track(
+        id: GenerationalBoxId,
+        created_at: &'static Location<'static>,
+        type_name: &'static str,
+        owner: BoxOwner,
+    ) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut registry = registry();
+        let registry = &mut *registry;
+        if owner == BoxOwner::Leaked {
+            let count = registry.leaked.entry(created_at).or_default();
+            *count += 1;
+            if *count > THRESHOLD.load(Ordering::Relaxed) && registry.warned.insert(created_at) {
+                tracing::warn!(
+                    "{count} generational boxes of type {type_name} created at {created_at} were leaked and are still alive. Leaked boxes are only dropped with `manually_drop`."
+                );
+            }
+        }
+        registry.live.insert(
+            id,
+            TrackedBox {
+                created_at,
+                type_name,
+                owner,
+            },
+        );
+    }
+
+    /// Record that a box was dropped
+    pub(crate) fn untrack(id: GenerationalBoxId) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut registry = registry();
+        if let Some(tracked) = registry.live.remove(&id) {
+            forget_leak(&mut registry, &tracked);
+        }
+    }
+
+    fn forget_leak(registry: &mut Registry, tracked: &TrackedBox) {
+        if tracked.owner != BoxOwner::Leaked {
+            return;
+        }
+        if let Some(count) = registry.leaked.get_mut(tracked.created_at) {
+            *count -= 1;
+            if *count == 0 {
+                registry.leaked.remove(tracked.created_at);
+            }
+        }
+    }
+
+    pub(super) fn report() -> LeakReport {
+        let registry = registry();
+        let threshold = THRESHOLD.load(Ordering::Relaxed);
+        let mut groups: HashMap<(&'static Location<'static>, &BoxOwner), BoxGroup> = HashMap::new();
+        for tracked in registry.live.values() {
+            groups
+                .entry((tracked.created_at, &tracked.owner))
+                .or_insert_with(|| BoxGroup {
+                    created_at: tracked.created_at,
+                    type_name: tracked.type_name,
+                    owner: tracked.owner.clone(),
+                    count: 0,
+                    over_threshold: tracked.owner == BoxOwner::Leaked
+                        && registry.leaked.get(tracked.created_at).copied() > Some(threshold),
+                })
+                .count += 1;
+        }
+
+        let mut groups: Vec<_> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.created_at.to_string().cmp(&b.created_at.to_string()))
+        });
+        LeakReport { groups }
+    }
+}
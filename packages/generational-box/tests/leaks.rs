@@ -0,0 +1,69 @@
+// Boxes are only tracked in debug builds
+#![cfg(debug_assertions)]
+
+use generational_box::{BoxOwner, GenerationalBox, LeakTracker, Owner, SyncStorage, UnsyncStorage};
+use std::panic::Location;
+
+#[test]
+fn owned_boxes_are_grouped_by_location_and_owner() {
+    LeakTracker::enable();
+    let owner = Owner::<UnsyncStorage>::default();
+    owner.set_label("settings");
+    let location = Location::caller();
+    let boxes: Vec<_> = (0..3)
+        .map(|i| owner.insert_with_caller(i, location))
+        .collect();
+
+    let report = LeakTracker::report();
+    let group = report
+        .groups()
+        .iter()
+        .find(|group| group.created_at == location)
+        .expect("the boxes to be tracked");
+    assert_eq!(group.count, 3);
+    assert_eq!(group.type_name, "i32");
+    assert_eq!(group.owner, BoxOwner::Owned(Some("settings".into())));
+    assert!(!group.over_threshold);
+
+    drop(owner);
+    assert!(boxes.iter().all(|value| value.try_read().is_err()));
+    let report = LeakTracker::report();
+    assert!(report
+        .groups()
+        .iter()
+        .all(|group| group.created_at != location));
+}
+
+#[test]
+fn leaked_boxes_over_the_threshold_are_flagged() {
+    LeakTracker::enable();
+    LeakTracker::set_leak_threshold(2);
+    let location = Location::caller();
+    let leaked = || GenerationalBox::<_, SyncStorage>::leak("leaked".to_string(), location);
+    let find_group = || {
+        LeakTracker::report()
+            .groups()
+            .iter()
+            .find(|group| group.created_at == location)
+            .cloned()
+    };
+
+    let boxes = [leaked(), leaked()];
+    let group = find_group().expect("the boxes to be tracked");
+    assert_eq!(group.owner, BoxOwner::Leaked);
+    assert_eq!(group.count, 2);
+    assert!(!group.over_threshold);
+
+    let third = leaked();
+    let group = find_group().expect("the boxes to be tracked");
+    assert_eq!(group.count, 3);
+    assert!(group.over_threshold);
+    assert!(LeakTracker::report()
+        .to_string()
+        .contains("over the leak threshold"));
+
+    for leaked in boxes.iter().chain([&third]) {
+        leaked.manually_drop();
+    }
+    assert_eq!(find_group(), None);
+}
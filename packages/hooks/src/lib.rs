@@ -93,5 +93,36 @@ pub use use_hook_did_run::*;
 mod use_signal;
 pub use use_signal::*;
 
+mod use_store;
+pub use use_store::*;
+
+mod use_history;
+pub use use_history::*;
+
+#[cfg(feature = "persistent")]
+mod use_persistent;
+#[cfg(feature = "persistent")]
+pub use use_persistent::*;
+
 mod use_set_compare;
 pub use use_set_compare::*;
+
+mod use_query;
+pub use use_query::*;
+
+mod use_mutation;
+pub use use_mutation::*;
+
+mod use_debounce;
+pub use use_debounce::*;
+
+mod use_throttle;
+pub use use_throttle::*;
+
+mod use_interval;
+pub use use_interval::*;
+
+mod use_timeout;
+pub use use_timeout::*;
+
+mod timer;
@@ -0,0 +1,95 @@
+//! Timers that work on every platform, including the web where `std::time::Instant` and threads are not available
+
+use crate::use_hook_did_run;
+use dioxus_core::prelude::*;
+use dioxus_signals::*;
+use std::{future::Future, time::Duration};
+
+pub(crate) use web_time::Instant;
+
+/// Wait for a duration without blocking the thread
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+    #[cfg(not(target_arch = "wasm32"))]
+    futures_timer::Delay::new(duration).await;
+}
+
+/// The task behind a timer hook. Only one task runs at a time: starting a new task cancels the old one.
+///
+/// The task is spawned with [`Runtime::spawn`] on the scope of the hook, so it is canceled when the component is
+/// unmounted. Hooks that start their task when the component is created start it from an effect, which never runs on
+/// the server. The task is paused while the component returns early before reaching the hook, like when the component
+/// is suspended.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct TimerTask {
+    scope: ScopeId,
+    task: CopyValue<Option<Task>>,
+    /// If the component returned early the last time it rendered
+    paused: CopyValue<bool>,
+    running: Signal<bool>,
+}
+
+pub(crate) fn use_timer_task() -> TimerTask {
+    let timer = use_hook(|| TimerTask {
+        scope: current_scope_id().expect("to be in a dioxus runtime"),
+        task: CopyValue::new(None),
+        paused: CopyValue::new(false),
+        running: Signal::new(false),
+    });
+
+    use_hook_did_run(move |did_run| {
+        let mut paused = timer.paused;
+        paused.set(!did_run);
+        if let Some(task) = timer.task.cloned() {
+            match did_run {
+                true => task.resume(),
+                false => task.pause(),
+            }
+        }
+    });
+
+    timer
+}
+
+impl TimerTask {
+    /// Cancel the running task and start a new one
+    pub(crate) fn start(&self, future: impl Future<Output = ()> + 'static) {
+        self.cancel();
+
+        let (mut task_slot, mut running) = (self.task, self.running);
+        let task = Runtime::current()
+            .expect("to be in a dioxus runtime")
+            .spawn(self.scope, async move {
+                future.await;
+                task_slot.set(None);
+                running.set(false);
+            });
+        if self.paused.cloned() {
+            task.pause();
+        }
+        task_slot.set(Some(task));
+        running.set(true);
+    }
+
+    /// Cancel the running task if there is one
+    pub(crate) fn cancel(&self) {
+        let (mut task, mut running) = (self.task, self.running);
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+        if *running.peek() {
+            running.set(false);
+        }
+    }
+
+    /// Check if the task is running. This subscribes to the state of the task.
+    pub(crate) fn is_running(&self) -> bool {
+        self.running.cloned()
+    }
+
+    /// Check if the task is running without subscribing to the state of the task
+    pub(crate) fn is_running_untracked(&self) -> bool {
+        *self.running.peek()
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    timer::{sleep, use_timer_task, TimerTask},
+    use_callback,
+};
+use dioxus_core::prelude::*;
+use std::time::Duration;
+
+/// Create a [`Debounce`] that calls `callback` once `delay` has passed without another call to [`Debounce::action`].
+///
+/// This is useful for work that should only run once the user stops typing, like searching. Only the last value passed
+/// to [`Debounce::action`] is used.
+///
+/// The timer runs on the scope of the component. It is canceled when the component is unmounted, paused while the
+/// component is suspended and never runs on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn Search() -> Element {
+///     let mut query = use_signal(String::new);
+///     // Only search after the user stops typing for half a second
+///     let mut search = use_debounce(Duration::from_millis(500), move |text: String| query.set(text));
+///
+///     rsx! {
+///         input { oninput: move |event| search.action(event.value()) }
+///         "Results for {query}"
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+pub fn use_debounce<T: 'static>(delay: Duration, callback: impl FnMut(T) + 'static) -> Debounce<T> {
+    let callback = use_callback(callback);
+    let timer = use_timer_task();
+    Debounce {
+        delay,
+        callback,
+        timer,
+    }
+}
+
+/// A handle to a debounced callback created with [`use_debounce`]
+pub struct Debounce<T: 'static> {
+    delay: Duration,
+    callback: Callback<T>,
+    timer: TimerTask,
+}
+
+impl<T> Clone for Debounce<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Debounce<T> {}
+
+impl<T> PartialEq for Debounce<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.delay == other.delay && self.callback == other.callback && self.timer == other.timer
+    }
+}
+
+impl<T: 'static> Debounce<T> {
+    /// Restart the timer. The callback is called with `value` once the delay passes, unless this is called again first.
+    pub fn action(&mut self, value: T) {
+        let (delay, callback) = (self.delay, self.callback);
+        self.timer.start(async move {
+            sleep(delay).await;
+            callback.call(value);
+        });
+    }
+
+    /// Cancel the pending call
+    pub fn cancel(&mut self) {
+        self.timer.cancel();
+    }
+
+    /// Check if a call is waiting for the delay to pass
+    pub fn is_pending(&self) -> bool {
+        self.timer.is_running()
+    }
+}
@@ -1,6 +1,7 @@
 use std::{cell::Cell, rc::Rc};
 
 use dioxus_core::prelude::*;
+use dioxus_core::{ReactiveContextKind, ReactiveGraph};
 use futures_util::StreamExt;
 
 use crate::use_callback;
@@ -16,6 +17,7 @@ pub fn use_effect(mut callback: impl FnMut() + 'static) -> Effect {
     use_hook(|| {
         // Inside the effect, we track any reads so that we can rerun the effect if a value the effect reads changes
         let (rc, mut changed) = ReactiveContext::new_with_origin(location);
+        ReactiveGraph::record_context(&rc, ReactiveContextKind::Effect, None);
 
         // Deduplicate queued effects
         let effect_queued = Rc::new(Cell::new(false));
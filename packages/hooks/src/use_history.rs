@@ -0,0 +1,41 @@
+use dioxus_core::prelude::*;
+use dioxus_signals::{HistorySignal, DEFAULT_HISTORY_DEPTH};
+
+/// Creates a new [`HistorySignal`] that keeps up to [`DEFAULT_HISTORY_DEPTH`] steps of history. Every write to the
+/// signal can be undone and redone.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn App() -> Element {
+///     let mut count = use_history(|| 0);
+///
+///     rsx! {
+///         button { onclick: move |_| count += 1, "Count: {count}" }
+///         button { disabled: !count.can_undo(), onclick: move |_| { count.undo(); }, "Undo" }
+///         button { disabled: !count.can_redo(), onclick: move |_| { count.redo(); }, "Redo" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_history<T: Clone + 'static>(f: impl FnOnce() -> T) -> HistorySignal<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(|| HistorySignal::new_with_caller(f(), DEFAULT_HISTORY_DEPTH, caller))
+}
+
+/// Creates a new [`HistorySignal`] that keeps up to `max_depth` steps of history. Once the history is full, the oldest
+/// steps are forgotten.
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_history_with_max_depth<T: Clone + 'static>(
+    max_depth: usize,
+    f: impl FnOnce() -> T,
+) -> HistorySignal<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(|| HistorySignal::new_with_caller(f(), max_depth, caller))
+}
@@ -0,0 +1,81 @@
+use crate::{
+    timer::{sleep, use_timer_task, TimerTask},
+    use_callback,
+};
+use dioxus_core::prelude::*;
+use dioxus_signals::*;
+use std::time::Duration;
+
+/// Call `callback` every `period`, starting when the component is first rendered.
+///
+/// If the component is rendered with a different period, the new period is used after the next tick. The timer runs on
+/// the scope of the component. It is canceled when the component is unmounted, paused while the component is suspended
+/// and never runs on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn Clock() -> Element {
+///     let mut seconds = use_signal(|| 0);
+///     let mut interval = use_interval(Duration::from_secs(1), move || seconds += 1);
+///
+///     rsx! {
+///         "{seconds} seconds"
+///         button {
+///             onclick: move |_| if interval.is_running() { interval.cancel() } else { interval.restart() },
+///             "Start/Stop"
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+pub fn use_interval(period: Duration, mut callback: impl FnMut() + 'static) -> Interval {
+    let callback = use_callback(move |_| callback());
+    let timer = use_timer_task();
+    let mut current_period = use_hook(|| CopyValue::new(period));
+    if *current_period.peek() != period {
+        current_period.set(period);
+    }
+
+    let interval = Interval {
+        period: current_period,
+        callback,
+        timer,
+    };
+    // Effects never run on the server, so the timer only starts on the client
+    use_hook(|| queue_effect(move || interval.restart()));
+    interval
+}
+
+/// A handle to an interval created with [`use_interval`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Interval {
+    period: CopyValue<Duration>,
+    callback: Callback,
+    timer: TimerTask,
+}
+
+impl Interval {
+    /// Stop calling the callback
+    pub fn cancel(&mut self) {
+        self.timer.cancel();
+    }
+
+    /// Start the interval again. The next call happens one period from now.
+    pub fn restart(&self) {
+        let (period, callback) = (self.period, self.callback);
+        self.timer.start(async move {
+            loop {
+                sleep(period.cloned()).await;
+                callback.call(());
+            }
+        });
+    }
+
+    /// Check if the interval is running
+    pub fn is_running(&self) -> bool {
+        self.timer.is_running()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{use_query_client, QueryClient, QueryKey};
+use dioxus_core::{prelude::*, CapturedError};
+use dioxus_signals::*;
+use std::{future::Future, pin::Pin};
+
+type MutationFn<A, T> =
+    Box<dyn FnMut(A) -> Pin<Box<dyn Future<Output = Result<T, CapturedError>>>>>;
+
+/// Create a [`UseMutation`] that changes data on the server, like a request that adds a todo.
+///
+/// Mutations are not run until you call [`UseMutation::mutate`]. After a mutation changes data on the server, invalidate
+/// the queries that read the data with [`invalidate_query`](crate::invalidate_query) or update the cache with
+/// [`UseMutation::mutate_optimistic`] so the app shows the change.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+///
+/// # async fn fetch_todos() -> Result<Vec<String>, std::io::Error> { todo!() }
+/// # async fn add_todo(todo: String) -> Result<(), std::io::Error> { todo!() }
+/// fn Todos() -> Element {
+///     let todos = use_query(|| "todos", fetch_todos);
+///     let mut add = use_mutation(add_todo);
+///
+///     rsx! {
+///         for todo in todos.data().cloned().unwrap_or_default() {
+///             p { "{todo}" }
+///         }
+///         button {
+///             // Show the new todo right away. If the request fails, the todos go back to how they were before.
+///             onclick: move |_| {
+///                 let todo = "Buy milk".to_string();
+///                 add.mutate_optimistic(todo.clone(), "todos", |todos: &mut Vec<String>| todos.push(todo))
+///             },
+///             disabled: add.pending(),
+///             "Add todo"
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_mutation<A, T, E, F>(mut mutation: impl FnMut(A) -> F + 'static) -> UseMutation<A, T>
+where
+    A: 'static,
+    T: 'static,
+    E: Into<CapturedError>,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let client = use_query_client();
+    use_hook(|| {
+        let mutation: MutationFn<A, T> = Box::new(move |args| {
+            let future = mutation(args);
+            Box::pin(async move { future.await.map_err(Into::into) })
+                as Pin<Box<dyn Future<Output = Result<T, CapturedError>>>>
+        });
+        UseMutation {
+            client,
+            scope: current_scope_id().expect("to be in a dioxus runtime"),
+            mutation: CopyValue::new(mutation),
+            result: Signal::new(None),
+            running: Signal::new(0),
+        }
+    })
+}
+
+/// A handle to a mutation created with [`use_mutation`]
+pub struct UseMutation<A: 'static, T: 'static> {
+    client: QueryClient,
+    scope: ScopeId,
+    mutation: CopyValue<MutationFn<A, T>>,
+    result: Signal<Option<Result<T, CapturedError>>>,
+    /// The number of mutations that haven't finished yet
+    running: Signal<usize>,
+}
+
+impl<A, T> Clone for UseMutation<A, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, T> Copy for UseMutation<A, T> {}
+
+impl<A, T> PartialEq for UseMutation<A, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mutation == other.mutation
+    }
+}
+
+impl<A: 'static, T: 'static> UseMutation<A, T> {
+    /// Run the mutation
+    pub fn mutate(&mut self, args: A) {
+        self.run(args, |_, _| {});
+    }
+
+    /// Update the cached data of a query before running the mutation, so the app shows the result of the mutation
+    /// without waiting for the server.
+    ///
+    /// If the mutation fails, the cached data is rolled back to what it was before. Once the mutation finishes, the query
+    /// is invalidated so it shows the data that is actually on the server. If the query doesn't have any data yet,
+    /// `update` is not called.
+    pub fn mutate_optimistic<Q: Clone + 'static>(
+        &mut self,
+        args: A,
+        key: impl Into<QueryKey>,
+        update: impl FnOnce(&mut Q),
+    ) {
+        let key = key.into();
+        let previous = self.client.data::<Q>(key.clone());
+        if let Some(mut optimistic) = previous.clone() {
+            update(&mut optimistic);
+            self.client.set_data(key.clone(), optimistic);
+        }
+
+        self.run(args, move |client, succeeded| {
+            if let (false, Some(previous)) = (succeeded, previous) {
+                client.set_data(key.clone(), previous);
+            }
+            client.invalidate(key);
+        });
+    }
+
+    /// The result of the last mutation that finished, or `None` if no mutation has finished yet
+    pub fn result(&self) -> ReadOnlySignal<Option<Result<T, CapturedError>>> {
+        self.result.into()
+    }
+
+    /// Check if a mutation is running
+    pub fn pending(&self) -> bool {
+        *self.running.read() > 0
+    }
+
+    fn run(&mut self, args: A, settled: impl FnOnce(QueryClient, bool) + 'static) {
+        let future = (self.mutation.write())(args);
+        let Self {
+            client,
+            mut result,
+            mut running,
+            ..
+        } = *self;
+        running += 1;
+        self.scope.push_future(async move {
+            let output = future.await;
+            settled(client, output.is_ok());
+            result.set(Some(output));
+            running -= 1;
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use dioxus_core::prelude::*;
+use dioxus_signals::{PersistentOptions, PersistentSignal};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Creates a new [`PersistentSignal`] saved under a key. The signal loads the value saved under the key, or calls `init`
+/// if nothing was saved yet, and saves the value whenever it is written.
+///
+/// On the web values are saved in local storage, and on desktop they are saved in the app's data directory. Signals with
+/// the same key in other windows or browser tabs are updated when the value changes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn App() -> Element {
+///     // The count is still there after the app restarts
+///     let mut count = use_persistent("count", || 0);
+///
+///     rsx! {
+///         button { onclick: move |_| count += 1, "Clicked {count} times" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_persistent<T: Serialize + DeserializeOwned + 'static>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+) -> PersistentSignal<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(|| {
+        PersistentSignal::new_with_caller(PersistentOptions::new(key.to_string()), init, caller)
+    })
+}
+
+/// Creates a new [`PersistentSignal`] with options for the version, migrations and storage of the saved value.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::signals::PersistentOptions;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Settings {
+///     volume: f64,
+///     muted: bool,
+/// }
+///
+/// fn App() -> Element {
+///     let settings = use_persistent_with(
+///         PersistentOptions::new("settings")
+///             .with_version(1)
+///             // Version 0 only saved the volume
+///             .with_migration(0, |volume: f64| Settings { volume, muted: false }),
+///         || Settings { volume: 0.5, muted: false },
+///     );
+///
+///     rsx! { "Volume: {settings.read().volume}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_persistent_with<T: Serialize + DeserializeOwned + 'static>(
+    options: PersistentOptions,
+    init: impl FnOnce() -> T,
+) -> PersistentSignal<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(|| PersistentSignal::new_with_caller(options, init, caller))
+}
@@ -0,0 +1,802 @@
+use crate::{
+    timer::{sleep, Instant},
+    use_memo,
+};
+use dioxus_core::{prelude::*, CapturedError};
+use dioxus_signals::*;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+/// The key a query is cached under. A key is a list of parts, like `["todos", "42"]`.
+///
+/// Invalidating a key also invalidates every key that starts with it, so invalidating `"todos"` refetches both the list
+/// of todos and every single todo.
+///
+/// Keys can be created from strings and from tuples of values that implement [`Display`]:
+///
+/// ```rust
+/// # use dioxus_hooks::QueryKey;
+/// let todos = QueryKey::from("todos");
+/// let todo = QueryKey::from(("todos", 42));
+/// assert!(todo.starts_with(&todos));
+/// assert_eq!(todo.to_string(), "todos/42");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QueryKey(Vec<String>);
+
+impl QueryKey {
+    /// Create a key from a list of parts
+    pub fn new(parts: impl IntoIterator<Item = impl Display>) -> Self {
+        Self(parts.into_iter().map(|part| part.to_string()).collect())
+    }
+
+    /// The parts of the key
+    pub fn parts(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Check if this key is the same as `prefix` or is nested inside of it
+    pub fn starts_with(&self, prefix: &QueryKey) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+}
+
+impl Display for QueryKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join("/"))
+    }
+}
+
+impl From<&str> for QueryKey {
+    fn from(key: &str) -> Self {
+        Self(vec![key.to_string()])
+    }
+}
+
+impl From<String> for QueryKey {
+    fn from(key: String) -> Self {
+        Self(vec![key])
+    }
+}
+
+impl From<Vec<String>> for QueryKey {
+    fn from(parts: Vec<String>) -> Self {
+        Self(parts)
+    }
+}
+
+macro_rules! impl_from_tuple {
+    ($($part:ident),+) => {
+        impl<$($part: Display),+> From<($($part,)+)> for QueryKey {
+            #[allow(non_snake_case)]
+            fn from(($($part,)+): ($($part,)+)) -> Self {
+                Self(vec![$($part.to_string()),+])
+            }
+        }
+    };
+}
+
+impl_from_tuple!(A);
+impl_from_tuple!(A, B);
+impl_from_tuple!(A, B, C);
+impl_from_tuple!(A, B, C, D);
+
+/// Options that control when a query is refetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryOptions {
+    stale_time: Duration,
+    cache_time: Duration,
+    retries: u32,
+    retry_delay: Duration,
+    refetch_on_focus: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time: Duration::ZERO,
+            cache_time: Duration::from_secs(5 * 60),
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            refetch_on_focus: true,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// The longest a failed fetch waits before it is retried
+    const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+    /// Create the default options. Data is stale as soon as it is fetched, unused data is removed from the cache after 5
+    /// minutes, failed fetches are retried 3 times and stale data is refetched when the window is focused.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long fetched data is fresh. Components that start using a query with fresh data use the cached data
+    /// without refetching it.
+    pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = stale_time;
+        self
+    }
+
+    /// Set how long the data of a query is kept after the last component using it is dropped. If a component starts
+    /// using the query again before then, it shows the cached data while it is refetched. Use [`Duration::MAX`] to keep
+    /// the data for as long as the client lives.
+    pub fn with_cache_time(mut self, cache_time: Duration) -> Self {
+        self.cache_time = cache_time;
+        self
+    }
+
+    /// Set how many times a failed fetch is retried before the error is shown
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set how long to wait before the first retry. The delay doubles after every retry, up to 30 seconds.
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Set if stale data is refetched in the background when the window is focused
+    pub fn with_refetch_on_focus(mut self, refetch_on_focus: bool) -> Self {
+        self.refetch_on_focus = refetch_on_focus;
+        self
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(Self::MAX_RETRY_DELAY)
+    }
+}
+
+/// The cache shared by every query in an app.
+///
+/// A client is created in the root of the app the first time [`use_query_client`] or [`use_query`] is called. You can
+/// provide a separate client to part of your app with [`provide_context`] to give it its own cache.
+#[derive(Clone, Copy, PartialEq)]
+pub struct QueryClient {
+    inner: CopyValue<ClientInner>,
+}
+
+struct ClientInner {
+    /// The scope that owns the cached data and the fetch tasks
+    scope: ScopeId,
+    queries: HashMap<QueryKey, Rc<dyn AnyQuery>>,
+}
+
+impl Default for QueryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryClient {
+    /// Create a new client with an empty cache owned by the current scope
+    pub fn new() -> Self {
+        Self::new_in_scope(current_scope_id().expect("to be in a dioxus runtime"))
+    }
+
+    /// Create a new client with an empty cache owned by a scope
+    pub fn new_in_scope(scope: ScopeId) -> Self {
+        let client = Self {
+            inner: CopyValue::new_in_scope(
+                ClientInner {
+                    scope,
+                    queries: HashMap::new(),
+                },
+                scope,
+            ),
+        };
+        #[cfg(target_arch = "wasm32")]
+        client.refetch_when_the_window_is_focused();
+        client
+    }
+
+    /// Mark every query under a key as stale and refetch the ones that are in use
+    pub fn invalidate(&self, key: impl Into<QueryKey>) {
+        let key = key.into();
+        for query in self.queries(|query_key| query_key.starts_with(&key)) {
+            query.invalidate();
+        }
+    }
+
+    /// Mark every query as stale and refetch the ones that are in use
+    pub fn invalidate_all(&self) {
+        for query in self.queries(|_| true) {
+            query.invalidate();
+        }
+    }
+
+    /// Replace the cached data of a query. This can be used to seed the cache with data that was loaded elsewhere or to
+    /// update it with the response of a mutation.
+    ///
+    /// The new data is fresh until the stale time of the query passes.
+    pub fn set_data<T: 'static>(&self, key: impl Into<QueryKey>, data: T) {
+        let key = key.into();
+        self.query::<T>(&key).set_data(data);
+        self.remove_when_unused(key);
+    }
+
+    /// Seed the cache with data that was loaded before the query was used, like data that was rendered on the server.
+    ///
+    /// Unlike [`QueryClient::set_data`], the first component that uses the query uses this data without refetching it,
+    /// even if the data is already stale. This does nothing if the query already has data.
+    pub fn set_initial_data<T: 'static>(&self, key: impl Into<QueryKey>, data: T) {
+        let key = key.into();
+        let query = self.query::<T>(&key);
+        if query.data.peek().is_some() {
+            return;
+        }
+        query.set_data(data);
+        query.state.borrow_mut().initial = true;
+        self.remove_when_unused(key);
+    }
+
+    /// Get a copy of the cached data of a query without subscribing to it
+    pub fn data<T: Clone + 'static>(&self, key: impl Into<QueryKey>) -> Option<T> {
+        let key = key.into();
+        let query = self.inner.read().queries.get(&key).cloned()?;
+        let query = downcast::<T>(query, &key);
+        let data = query.data.peek().clone();
+        data
+    }
+
+    /// Refetch the stale queries that are in use and refetch when the window is focused.
+    ///
+    /// On the web this is called automatically when the browser window is focused.
+    pub fn window_focused(&self) {
+        for query in self.queries(|_| true) {
+            query.window_focused();
+        }
+    }
+
+    /// Get the query for a key, creating it if it isn't cached yet
+    fn query<T: 'static>(&self, key: &QueryKey) -> Rc<QueryCell<T>> {
+        let existing = self.inner.read().queries.get(key).cloned();
+        if let Some(query) = existing {
+            return downcast(query, key);
+        }
+
+        let scope = self.inner.read().scope;
+        let query = Rc::new(QueryCell {
+            scope,
+            data: Signal::new_in_scope(None, scope),
+            error: Signal::new_in_scope(None, scope),
+            fetching: Signal::new_in_scope(false, scope),
+            state: RefCell::new(QueryState {
+                updated_at: None,
+                invalidated: false,
+                initial: false,
+                options: QueryOptions::default(),
+                observers: Vec::new(),
+                task: None,
+                removal: None,
+            }),
+        });
+        self.inner
+            .write_unchecked()
+            .queries
+            .insert(key.clone(), query.clone());
+        query
+    }
+
+    fn observer_left(&self, key: &QueryKey, observer: usize) {
+        let query = self
+            .inner
+            .try_read()
+            .ok()
+            .and_then(|inner| inner.queries.get(key).cloned());
+        if let Some(query) = query {
+            query.observer_left(observer);
+            self.remove_when_unused(key.clone());
+        }
+    }
+
+    /// Remove a query from the cache once its cache time passes if no component is using it by then
+    fn remove_when_unused(&self, key: QueryKey) {
+        let query = self
+            .inner
+            .try_read()
+            .ok()
+            .and_then(|inner| inner.queries.get(&key).cloned());
+        if let Some(query) = query {
+            query.remove_when_unused(*self, key);
+        }
+    }
+
+    /// Remove a query from the cache if it is still unused and drop its data
+    fn remove(&self, key: &QueryKey) {
+        let Ok(mut inner) = self.inner.try_write_unchecked() else {
+            return;
+        };
+        match inner.queries.get(key) {
+            Some(query) if !query.in_use() => {}
+            _ => return,
+        }
+        let query = inner.queries.remove(key);
+        drop(inner);
+        if let Some(query) = query {
+            query.drop_data();
+        }
+    }
+
+    /// Collect the queries with a matching key. The queries are cloned out of the cache so they can start tasks and
+    /// write to signals without holding the cache.
+    fn queries(&self, mut filter: impl FnMut(&QueryKey) -> bool) -> Vec<Rc<dyn AnyQuery>> {
+        match self.inner.try_read() {
+            Ok(inner) => inner
+                .queries
+                .iter()
+                .filter(|(key, _)| filter(key))
+                .map(|(_, query)| query.clone())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn refetch_when_the_window_is_focused(&self) {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Ok(runtime) = Runtime::current() else {
+            return;
+        };
+        let client = *self;
+        let on_focus = Closure::<dyn FnMut()>::new(move || {
+            let _guard = RuntimeGuard::new(runtime.clone());
+            client.window_focused();
+        });
+        _ = window.add_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref());
+        // The client lives as long as the app, so the listener is never removed
+        on_focus.forget();
+    }
+}
+
+fn downcast<T: 'static>(query: Rc<dyn AnyQuery>, key: &QueryKey) -> Rc<QueryCell<T>> {
+    query.as_any().downcast().unwrap_or_else(|_| {
+        panic!(
+            "The query `{key}` was used with different types of data. Every query with the same key must return the same type."
+        )
+    })
+}
+
+type Fetcher<T> = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<T, CapturedError>>>>>;
+
+/// The cached data and fetch state of one query
+struct QueryCell<T: 'static> {
+    scope: ScopeId,
+    data: Signal<Option<T>>,
+    error: Signal<Option<CapturedError>>,
+    fetching: Signal<bool>,
+    state: RefCell<QueryState<T>>,
+}
+
+struct QueryState<T: 'static> {
+    updated_at: Option<Instant>,
+    invalidated: bool,
+    /// If the data was set with [`QueryClient::set_initial_data`] and no component has used it yet
+    initial: bool,
+    options: QueryOptions,
+    /// The components using the query and their fetchers. The most recent fetcher is used so it can read the latest
+    /// state of its component.
+    observers: Vec<(usize, Fetcher<T>)>,
+    /// The fetch that is currently running. Every component that uses the query while it runs shares the result.
+    task: Option<Task>,
+    /// The task that removes the query from the cache after the last component stops using it
+    removal: Option<Task>,
+}
+
+impl<T> QueryState<T> {
+    fn is_stale(&self) -> bool {
+        self.invalidated
+            || match self.updated_at {
+                Some(updated_at) => updated_at.elapsed() >= self.options.stale_time,
+                None => true,
+            }
+    }
+}
+
+impl<T: 'static> QueryCell<T> {
+    fn observe(self: &Rc<Self>, observer: usize, fetcher: Fetcher<T>, options: QueryOptions) {
+        let needs_fetch = {
+            let mut state = self.state.borrow_mut();
+            if let Some(removal) = state.removal.take() {
+                removal.cancel();
+            }
+            state.observers.push((observer, fetcher));
+            state.options = options;
+            let initial = std::mem::take(&mut state.initial);
+            !initial && state.is_stale()
+        };
+        if needs_fetch {
+            self.fetch(false);
+        }
+    }
+
+    /// Fetch the data. If a fetch is already running, it is kept unless `restart` is true.
+    fn fetch(self: &Rc<Self>, restart: bool) {
+        let fetcher = {
+            let mut state = self.state.borrow_mut();
+            let Some((_, fetcher)) = state.observers.last() else {
+                return;
+            };
+            let fetcher = fetcher.clone();
+            match state.task.take() {
+                Some(task) if restart => task.cancel(),
+                Some(task) => {
+                    state.task = Some(task);
+                    return;
+                }
+                None => {}
+            }
+            fetcher
+        };
+
+        let query = self.clone();
+        let task = self.scope.push_future(async move {
+            let mut fetching = query.fetching;
+            fetching.set(true);
+
+            let mut attempt = 0;
+            let result = loop {
+                match fetcher().await {
+                    Ok(data) => break Ok(data),
+                    Err(error) => {
+                        let options = query.state.borrow().options.clone();
+                        if attempt >= options.retries {
+                            break Err(error);
+                        }
+                        sleep(options.retry_delay(attempt)).await;
+                        attempt += 1;
+                    }
+                }
+            };
+
+            query.state.borrow_mut().task = None;
+            match result {
+                Ok(data) => query.set_data(data),
+                Err(error) => {
+                    let mut error_signal = query.error;
+                    error_signal.set(Some(error));
+                }
+            }
+            fetching.set(false);
+        });
+        self.state.borrow_mut().task = task;
+    }
+
+    fn set_data(&self, data: T) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.updated_at = Some(Instant::now());
+            state.invalidated = false;
+        }
+        let (mut data_signal, mut error) = (self.data, self.error);
+        data_signal.set(Some(data));
+        if error.peek().is_some() {
+            error.set(None);
+        }
+    }
+}
+
+/// The operations the client runs on queries of any type
+trait AnyQuery {
+    fn as_any(self: Rc<Self>) -> Rc<dyn Any>;
+    fn invalidate(self: Rc<Self>);
+    fn window_focused(self: Rc<Self>);
+    fn observer_left(&self, observer: usize);
+    fn in_use(&self) -> bool;
+    fn remove_when_unused(self: Rc<Self>, client: QueryClient, key: QueryKey);
+    fn drop_data(&self);
+}
+
+impl<T: 'static> AnyQuery for QueryCell<T> {
+    fn as_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+
+    fn invalidate(self: Rc<Self>) {
+        let in_use = {
+            let mut state = self.state.borrow_mut();
+            state.invalidated = true;
+            state.initial = false;
+            !state.observers.is_empty()
+        };
+        // Restart running fetches since they may have started before the data changed
+        if in_use {
+            self.fetch(true);
+        }
+    }
+
+    fn window_focused(self: Rc<Self>) {
+        let refetch = {
+            let state = self.state.borrow();
+            !state.observers.is_empty() && state.options.refetch_on_focus && state.is_stale()
+        };
+        if refetch {
+            self.fetch(false);
+        }
+    }
+
+    fn observer_left(&self, observer: usize) {
+        self.state
+            .borrow_mut()
+            .observers
+            .retain(|(id, _)| *id != observer);
+    }
+
+    fn in_use(&self) -> bool {
+        !self.state.borrow().observers.is_empty()
+    }
+
+    fn remove_when_unused(self: Rc<Self>, client: QueryClient, key: QueryKey) {
+        let mut state = self.state.borrow_mut();
+        let cache_time = state.options.cache_time;
+        if !state.observers.is_empty() || cache_time == Duration::MAX {
+            return;
+        }
+        let removal = self.scope.push_future(async move {
+            sleep(cache_time).await;
+            client.remove(&key);
+        });
+        if let Some(old) = std::mem::replace(&mut state.removal, removal) {
+            old.cancel();
+        }
+    }
+
+    fn drop_data(&self) {
+        let mut state = self.state.borrow_mut();
+        for task in [state.task.take(), state.removal.take()]
+            .into_iter()
+            .flatten()
+        {
+            task.cancel();
+        }
+        drop(state);
+        self.data.manually_drop();
+        self.error.manually_drop();
+        self.fetching.manually_drop();
+    }
+}
+
+/// Get the [`QueryClient`] for the current component, creating one in the root of the app if there isn't one yet
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+pub fn use_query_client() -> QueryClient {
+    use_hook(|| {
+        try_consume_context::<QueryClient>()
+            .unwrap_or_else(|| provide_root_context(QueryClient::new_in_scope(ScopeId::ROOT)))
+    })
+}
+
+/// Mark every query under a key as stale in the [`QueryClient`] of the current component and refetch the ones that are
+/// in use. This does nothing if no query has been used yet.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn AddTodo() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 // add_todo().await;
+///                 invalidate_query("todos");
+///             },
+///             "Add todo"
+///         }
+///     }
+/// }
+/// ```
+pub fn invalidate_query(key: impl Into<QueryKey>) {
+    if let Some(client) = try_consume_context::<QueryClient>() {
+        client.invalidate(key);
+    }
+}
+
+/// Fetch data that is cached under a key and shared with every other component that uses the same key.
+///
+/// `key` is reactive: when a signal it reads changes, the query switches to the data of the new key. `fetcher` is only
+/// called when the data needs to be fetched:
+/// - When the first component starts using the key
+/// - When a component starts using the key and the cached data is stale
+/// - When the key is invalidated with [`QueryClient::invalidate`] or [`invalidate_query`]
+/// - When the window is focused and the cached data is stale
+///
+/// If several components need the data at the same time, they share a single request. Failed requests are retried with
+/// an increasing delay before the error is shown. Use [`use_query_with`] to change how long data stays fresh and how
+/// failed requests are retried.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+///
+/// # async fn fetch_todo(id: u32) -> Result<String, std::io::Error> { todo!() }
+/// #[component]
+/// fn Todo(id: ReadOnlySignal<u32>) -> Element {
+///     // Every `Todo` component with the same id shares the same request and cached data
+///     let todo = use_query(move || ("todo", id()), move || fetch_todo(id()));
+///
+///     match (&*todo.data().read(), &*todo.error().read()) {
+///         (Some(todo), _) => rsx! { "{todo}" },
+///         (None, Some(error)) => rsx! { "Failed to load the todo: {error}" },
+///         (None, None) => rsx! { "Loading..." },
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_query<T, E, K, F>(
+    key: impl Fn() -> K + 'static,
+    fetcher: impl Fn() -> F + 'static,
+) -> Query<T>
+where
+    T: 'static,
+    E: Into<CapturedError>,
+    K: Into<QueryKey>,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    use_query_with(QueryOptions::default(), key, fetcher)
+}
+
+/// Fetch data that is cached under a key with custom [`QueryOptions`]. See [`use_query`] for more details.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// # async fn fetch_todos() -> Result<Vec<String>, std::io::Error> { todo!() }
+/// fn Todos() -> Element {
+///     // The todos are only refetched if they are more than a minute old
+///     let todos = use_query_with(
+///         QueryOptions::new().with_stale_time(Duration::from_secs(60)),
+///         || "todos",
+///         fetch_todos,
+///     );
+///
+///     rsx! {
+///         for todo in todos.data().cloned().unwrap_or_default() {
+///             p { "{todo}" }
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+pub fn use_query_with<T, E, K, F>(
+    options: QueryOptions,
+    key: impl Fn() -> K + 'static,
+    fetcher: impl Fn() -> F + 'static,
+) -> Query<T>
+where
+    T: 'static,
+    E: Into<CapturedError>,
+    K: Into<QueryKey>,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let client = use_query_client();
+    let key = use_memo(move || key().into());
+    let observer = use_hook(|| {
+        thread_local! {
+            static NEXT_OBSERVER: Cell<usize> = const { Cell::new(0) };
+        }
+        let fetcher: Fetcher<T> = Rc::new(move || {
+            let future = fetcher();
+            Box::pin(async move { future.await.map_err(Into::into) })
+                as Pin<Box<dyn Future<Output = Result<T, CapturedError>>>>
+        });
+        Rc::new(Observer {
+            id: NEXT_OBSERVER.with(|next| next.replace(next.get() + 1)),
+            key: RefCell::new(None),
+            fetcher,
+        })
+    });
+
+    // Reading the key reruns the component when it changes, so the query follows the new key
+    let current_key = key.read().clone();
+    let old_key = observer.key.replace(Some(current_key.clone()));
+    if old_key.as_ref() != Some(&current_key) {
+        if let Some(old_key) = old_key {
+            client.observer_left(&old_key, observer.id);
+        }
+        client
+            .query::<T>(&current_key)
+            .observe(observer.id, observer.fetcher.clone(), options);
+    }
+
+    use_drop({
+        let observer = observer.clone();
+        move || {
+            if let Some(key) = observer.key.take() {
+                client.observer_left(&key, observer.id);
+            }
+        }
+    });
+
+    Query {
+        client,
+        key,
+        _marker: PhantomData,
+    }
+}
+
+/// A component using a query
+struct Observer<T: 'static> {
+    id: usize,
+    key: RefCell<Option<QueryKey>>,
+    fetcher: Fetcher<T>,
+}
+
+/// A handle to cached data fetched with [`use_query`]
+pub struct Query<T: 'static> {
+    client: QueryClient,
+    key: Memo<QueryKey>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Query<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Query<T> {}
+
+impl<T> PartialEq for Query<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.client == other.client && self.key == other.key
+    }
+}
+
+impl<T: 'static> Query<T> {
+    /// The data of the query, or `None` if it hasn't been fetched yet. The data is kept while the query is refetched
+    /// and when a refetch fails.
+    pub fn data(&self) -> ReadOnlySignal<Option<T>> {
+        self.query().data.into()
+    }
+
+    /// The error of the last fetch if every retry failed
+    pub fn error(&self) -> ReadOnlySignal<Option<CapturedError>> {
+        self.query().error.into()
+    }
+
+    /// Check if the data is being fetched
+    pub fn is_fetching(&self) -> bool {
+        self.query().fetching.cloned()
+    }
+
+    /// Check if the data is stale and will be refetched the next time a component starts using the query or the window
+    /// is focused
+    pub fn is_stale(&self) -> bool {
+        self.query().state.borrow().is_stale()
+    }
+
+    /// Fetch the data again, even if it is fresh
+    pub fn refetch(&self) {
+        self.query().fetch(true);
+    }
+
+    /// The current key of the query
+    pub fn key(&self) -> QueryKey {
+        self.key.cloned()
+    }
+
+    fn query(&self) -> Rc<QueryCell<T>> {
+        self.client.query(&self.key.read())
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::{use_callback, use_signal};
 use dioxus_core::prelude::*;
+use dioxus_core::{ReactiveContextKind, ReactiveGraph};
 use dioxus_signals::*;
 use futures_util::{future, pin_mut, FutureExt, StreamExt};
 use std::ops::Deref;
@@ -25,6 +26,7 @@ where
     let mut state = use_signal(|| UseResourceState::Pending);
     let (rc, changed) = use_hook(|| {
         let (rc, changed) = ReactiveContext::new_with_origin(location);
+        ReactiveGraph::record_context(&rc, ReactiveContextKind::Resource, None);
         (rc, Rc::new(Cell::new(Some(changed))))
     });
 
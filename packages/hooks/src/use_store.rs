@@ -0,0 +1,40 @@
+use dioxus_core::prelude::*;
+use dioxus_signals::Store;
+
+/// Creates a new [`Store`]. Stores track reads and writes to each field, index and key of a value separately, so
+/// writing one part of the value only reruns the components that read that part.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Store)]
+/// struct Counters {
+///     left: i32,
+///     right: i32,
+/// }
+///
+/// fn App() -> Element {
+///     let counters = use_store(|| Counters { left: 0, right: 0 });
+///
+///     rsx! {
+///         Counter { count: counters.left() }
+///         Counter { count: counters.right() }
+///     }
+/// }
+///
+/// #[component]
+/// fn Counter(count: Store<i32>) -> Element {
+///     // Clicking one counter doesn't rerun the other counter
+///     rsx! {
+///         button { onclick: move |_| *count.write() += 1, "{count}" }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_store<T: 'static>(f: impl FnOnce() -> T) -> Store<T> {
+    let caller = std::panic::Location::caller();
+    use_hook(|| Store::new_with_caller(f(), caller))
+}
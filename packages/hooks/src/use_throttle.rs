@@ -0,0 +1,115 @@
+use crate::{
+    timer::{sleep, use_timer_task, TimerTask},
+    use_callback,
+};
+use dioxus_core::prelude::*;
+use dioxus_signals::*;
+use std::time::Duration;
+
+/// Create a [`Throttle`] that calls `callback` at most once every `interval`.
+///
+/// The first call to [`Throttle::action`] calls the callback right away. Calls during the interval after it are
+/// combined: once the interval passes, the callback is called again with the last value.
+///
+/// The timer runs on the scope of the component. It is canceled when the component is unmounted, paused while the
+/// component is suspended and never runs on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn Mouse() -> Element {
+///     let mut position = use_signal(|| 0.0);
+///     // Update the position at most 10 times a second while the mouse moves
+///     let mut update = use_throttle(Duration::from_millis(100), move |y: f64| position.set(y));
+///
+///     rsx! {
+///         div {
+///             onmousemove: move |event| update.action(event.client_coordinates().y),
+///             "The mouse is at {position}"
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+pub fn use_throttle<T: 'static>(
+    interval: Duration,
+    callback: impl FnMut(T) + 'static,
+) -> Throttle<T> {
+    let callback = use_callback(callback);
+    let timer = use_timer_task();
+    let trailing = use_hook(|| CopyValue::new(None));
+    Throttle {
+        interval,
+        callback,
+        timer,
+        trailing,
+    }
+}
+
+/// A handle to a throttled callback created with [`use_throttle`]
+pub struct Throttle<T: 'static> {
+    interval: Duration,
+    callback: Callback<T>,
+    timer: TimerTask,
+    /// The last value passed to the throttle while it was waiting for the interval to pass
+    trailing: CopyValue<Option<T>>,
+}
+
+impl<T> Clone for Throttle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Throttle<T> {}
+
+impl<T> PartialEq for Throttle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.interval == other.interval
+            && self.callback == other.callback
+            && self.timer == other.timer
+            && self.trailing == other.trailing
+    }
+}
+
+impl<T: 'static> Throttle<T> {
+    /// Call the callback with `value` now if the interval has passed since the last call, or once it passes if it
+    /// hasn't
+    pub fn action(&mut self, value: T) {
+        if self.timer.is_running_untracked() {
+            self.trailing.set(Some(value));
+            return;
+        }
+
+        let Self {
+            interval,
+            callback,
+            mut trailing,
+            ..
+        } = *self;
+        callback.call(value);
+        self.timer.start(async move {
+            loop {
+                sleep(interval).await;
+                match trailing.take() {
+                    Some(value) => callback.call(value),
+                    None => break,
+                }
+            }
+        });
+    }
+
+    /// Drop the value that is waiting for the interval to pass
+    pub fn cancel(&mut self) {
+        self.trailing.set(None);
+        self.timer.cancel();
+    }
+
+    /// Check if the throttle is waiting for the interval to pass. Calls to [`Throttle::action`] while it is waiting
+    /// are delayed.
+    pub fn is_waiting(&self) -> bool {
+        self.timer.is_running()
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    timer::{sleep, use_timer_task, TimerTask},
+    use_callback,
+};
+use dioxus_core::prelude::*;
+use std::time::Duration;
+
+/// Call `callback` once after `delay`, starting when the component is first rendered.
+///
+/// The timer runs on the scope of the component. It is canceled when the component is unmounted, paused while the
+/// component is suspended and never runs on the server.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn Toast() -> Element {
+///     let mut visible = use_signal(|| true);
+///     // Hide the toast after 3 seconds
+///     let mut timeout = use_timeout(Duration::from_secs(3), move || visible.set(false));
+///
+///     rsx! {
+///         if visible() {
+///             div {
+///                 // Keep the toast open while the mouse is over it
+///                 onmouseenter: move |_| timeout.cancel(),
+///                 onmouseleave: move |_| timeout.restart(),
+///                 "Saved!"
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+pub fn use_timeout(delay: Duration, mut callback: impl FnMut() + 'static) -> Timeout {
+    let callback = use_callback(move |_| callback());
+    let timer = use_timer_task();
+    let timeout = Timeout {
+        delay,
+        callback,
+        timer,
+    };
+    // Effects never run on the server, so the timer only starts on the client
+    use_hook(|| queue_effect(move || timeout.restart()));
+    timeout
+}
+
+/// A handle to a timeout created with [`use_timeout`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Timeout {
+    delay: Duration,
+    callback: Callback,
+    timer: TimerTask,
+}
+
+impl Timeout {
+    /// Cancel the timeout if it hasn't fired yet
+    pub fn cancel(&mut self) {
+        self.timer.cancel();
+    }
+
+    /// Start waiting for the delay again, even if the timeout already fired
+    pub fn restart(&self) {
+        let (delay, callback) = (self.delay, self.callback);
+        self.timer.start(async move {
+            sleep(delay).await;
+            callback.call(());
+        });
+    }
+
+    /// Check if the timeout is waiting for the delay to pass
+    pub fn is_pending(&self) -> bool {
+        self.timer.is_running()
+    }
+}
@@ -0,0 +1,253 @@
+#![allow(non_snake_case)]
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_core::{CapturedError, NoOpMutations};
+
+thread_local! {
+    static FETCHES: Cell<usize> = const { Cell::new(0) };
+    static FAILURES: Cell<usize> = const { Cell::new(0) };
+}
+
+async fn fetch_number() -> Result<usize, CapturedError> {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    if FAILURES.with(|failures| failures.get()) > 0 {
+        FAILURES.with(|failures| failures.set(failures.get() - 1));
+        return Err(CapturedError::from_display("failed to fetch"));
+    }
+    Ok(FETCHES.with(|fetches| fetches.replace(fetches.get() + 1)) + 1)
+}
+
+/// Run the dom until `done` returns true
+async fn run_until(dom: &mut VirtualDom, mut done: impl FnMut(&mut VirtualDom) -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !done(dom) {
+            // Tasks that don't mark a component as dirty don't end `wait_for_work`, so check again after a while
+            tokio::select! {
+                _ = dom.wait_for_work() => {}
+                _ = tokio::time::sleep(Duration::from_millis(5)) => {}
+            }
+            dom.render_immediate(&mut NoOpMutations);
+        }
+    })
+    .await
+    .expect("timed out");
+}
+
+#[tokio::test]
+async fn queries_with_the_same_key_share_a_fetch() {
+    let queries = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |queries: Rc<RefCell<Vec<Query<usize>>>>| {
+            rsx! {
+                Child { queries: queries.clone() }
+                Child { queries }
+            }
+        },
+        queries.clone(),
+    );
+
+    #[component]
+    fn Child(queries: Rc<RefCell<Vec<Query<usize>>>>) -> Element {
+        let query = use_query(|| "shared", fetch_number);
+        use_hook(|| queries.borrow_mut().push(query));
+        rsx! {}
+    }
+
+    dom.rebuild_in_place();
+    let [first, second] = queries.borrow()[..] else {
+        panic!("expected two queries");
+    };
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| first.data().cloned().is_some())
+    })
+    .await;
+
+    dom.in_runtime(|| {
+        assert_eq!(first.data().cloned(), Some(1));
+        assert_eq!(second.data().cloned(), Some(1));
+    });
+    assert_eq!(FETCHES.with(|fetches| fetches.get()), 1);
+}
+
+#[tokio::test]
+async fn invalidating_a_key_refetches_nested_keys() {
+    let query = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |query: Rc<RefCell<Option<Query<usize>>>>| {
+            let todo = use_query(|| ("todos", 1), fetch_number);
+            query.borrow_mut().replace(todo);
+            rsx! {}
+        },
+        query.clone(),
+    );
+
+    dom.rebuild_in_place();
+    let query = query.borrow().unwrap();
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| query.data().cloned() == Some(1))
+    })
+    .await;
+
+    dom.in_runtime(|| ScopeId::APP.in_runtime(|| invalidate_query("todos")));
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| query.data().cloned() == Some(2))
+    })
+    .await;
+    dom.in_runtime(|| assert!(!query.is_fetching()));
+}
+
+#[tokio::test]
+async fn failed_fetches_are_retried() {
+    FAILURES.with(|failures| failures.set(2));
+    let query = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |query: Rc<RefCell<Option<Query<usize>>>>| {
+            let number = use_query_with(
+                QueryOptions::new()
+                    .with_retries(2)
+                    .with_retry_delay(Duration::from_millis(1)),
+                || "retried",
+                fetch_number,
+            );
+            query.borrow_mut().replace(number);
+            rsx! {}
+        },
+        query.clone(),
+    );
+
+    dom.rebuild_in_place();
+    let query = query.borrow().unwrap();
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| query.data().cloned().is_some())
+    })
+    .await;
+
+    dom.in_runtime(|| {
+        assert_eq!(query.data().cloned(), Some(1));
+        assert!(query.error().read().is_none());
+    });
+    assert_eq!(FAILURES.with(|failures| failures.get()), 0);
+}
+
+#[tokio::test]
+async fn failed_optimistic_mutations_are_rolled_back() {
+    type Handles = (Query<Vec<String>>, UseMutation<String, ()>);
+    let handles = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |handles: Rc<RefCell<Option<Handles>>>| {
+            let todos = use_query_with(
+                QueryOptions::new().with_stale_time(Duration::from_secs(60)),
+                || "todos",
+                || async { Ok::<_, CapturedError>(vec!["Buy milk".to_string()]) },
+            );
+            let add = use_mutation(|_todo: String| async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Err::<(), _>(CapturedError::from_display("failed to add the todo"))
+            });
+            handles.borrow_mut().replace((todos, add));
+            rsx! {}
+        },
+        handles.clone(),
+    );
+
+    dom.rebuild_in_place();
+    let (todos, mut add) = handles.borrow().unwrap();
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| todos.data().cloned().is_some())
+    })
+    .await;
+
+    dom.in_runtime(|| {
+        ScopeId::APP.in_runtime(|| {
+            let todo = "Walk the dog".to_string();
+            add.mutate_optimistic(todo.clone(), "todos", |todos: &mut Vec<String>| {
+                todos.push(todo)
+            });
+        });
+        assert_eq!(todos.data().cloned().unwrap().len(), 2);
+        assert!(add.pending());
+    });
+
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| add.result().read().is_some())
+    })
+    .await;
+    dom.in_runtime(|| {
+        assert_eq!(todos.data().cloned(), Some(vec!["Buy milk".to_string()]));
+        assert!(!add.pending());
+    });
+}
+
+#[tokio::test]
+async fn initial_data_is_used_without_fetching() {
+    let query = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |query: Rc<RefCell<Option<Query<usize>>>>| {
+            let client = use_query_client();
+            use_hook(|| client.set_initial_data("seeded", 42usize));
+            let number = use_query(
+                || "seeded",
+                || async { Err::<usize, _>(CapturedError::from_display("should not be fetched")) },
+            );
+            query.borrow_mut().replace(number);
+            rsx! {}
+        },
+        query.clone(),
+    );
+
+    dom.rebuild_in_place();
+    let query = query.borrow().unwrap();
+    dom.in_runtime(|| {
+        assert_eq!(query.data().cloned(), Some(42));
+        assert!(!query.is_fetching());
+        assert!(query.is_stale());
+    });
+}
+
+#[tokio::test]
+async fn unused_queries_are_removed_after_the_cache_time() {
+    type Handles = (QueryClient, Signal<bool>);
+    let client = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |client: Rc<RefCell<Option<Handles>>>| {
+            let query_client = use_query_client();
+            let show = use_signal(|| true);
+            use_hook(|| client.borrow_mut().replace((query_client, show)));
+            rsx! {
+                if show() {
+                    Child {}
+                }
+            }
+        },
+        client.clone(),
+    );
+
+    fn Child() -> Element {
+        use_query_with(
+            QueryOptions::new().with_cache_time(Duration::from_millis(50)),
+            || "cached",
+            || async { Ok::<_, CapturedError>(1usize) },
+        );
+        rsx! {}
+    }
+
+    dom.rebuild_in_place();
+    let (client, mut show) = client.borrow().unwrap();
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| client.data::<usize>("cached").is_some())
+    })
+    .await;
+
+    // The data is kept for a while after the last component using it is dropped
+    dom.in_runtime(|| show.set(false));
+    dom.render_immediate(&mut NoOpMutations);
+    dom.in_runtime(|| assert_eq!(client.data::<usize>("cached"), Some(1)));
+
+    run_until(&mut dom, |dom| {
+        dom.in_runtime(|| client.data::<usize>("cached").is_none())
+    })
+    .await;
+}
@@ -0,0 +1,194 @@
+#![allow(non_snake_case)]
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+
+/// Run the dom for a while, handling any work that comes up
+async fn run_for(dom: &mut VirtualDom, duration: Duration) {
+    let _ = tokio::time::timeout(duration, async {
+        loop {
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut NoOpMutations);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn debounce_only_calls_with_the_last_value() {
+    type Handle = (Debounce<i32>, Rc<RefCell<Vec<i32>>>);
+    let handle = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |handle: Rc<RefCell<Option<Handle>>>| {
+            let calls = use_hook(|| Rc::new(RefCell::new(Vec::new())));
+            let debounce = use_debounce(Duration::from_millis(50), {
+                let calls = calls.clone();
+                move |value| calls.borrow_mut().push(value)
+            });
+            handle.borrow_mut().replace((debounce, calls));
+            rsx! {}
+        },
+        handle.clone(),
+    );
+    dom.rebuild_in_place();
+
+    let (mut debounce, calls) = handle.borrow().clone().unwrap();
+    dom.in_runtime(|| {
+        debounce.action(1);
+        debounce.action(2);
+        debounce.action(3);
+    });
+    run_for(&mut dom, Duration::from_millis(20)).await;
+    assert!(calls.borrow().is_empty());
+
+    run_for(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(*calls.borrow(), [3]);
+    dom.in_runtime(|| assert!(!debounce.is_pending()));
+}
+
+#[tokio::test]
+async fn throttle_calls_right_away_then_with_the_last_value() {
+    type Handle = (Throttle<i32>, Rc<RefCell<Vec<i32>>>);
+    let handle = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |handle: Rc<RefCell<Option<Handle>>>| {
+            let calls = use_hook(|| Rc::new(RefCell::new(Vec::new())));
+            let throttle = use_throttle(Duration::from_millis(50), {
+                let calls = calls.clone();
+                move |value| calls.borrow_mut().push(value)
+            });
+            handle.borrow_mut().replace((throttle, calls));
+            rsx! {}
+        },
+        handle.clone(),
+    );
+    dom.rebuild_in_place();
+
+    let (mut throttle, calls) = handle.borrow().clone().unwrap();
+    dom.in_runtime(|| {
+        throttle.action(1);
+        throttle.action(2);
+        throttle.action(3);
+    });
+    assert_eq!(*calls.borrow(), [1]);
+
+    run_for(&mut dom, Duration::from_millis(200)).await;
+    assert_eq!(*calls.borrow(), [1, 3]);
+    dom.in_runtime(|| assert!(!throttle.is_waiting()));
+}
+
+#[tokio::test]
+async fn interval_stops_when_the_component_is_unmounted() {
+    let ticks = Rc::new(RefCell::new(0));
+    let mut dom = VirtualDom::new_with_props(
+        |ticks: Rc<RefCell<usize>>| {
+            let mut mounted = use_signal(|| true);
+            use_hook(|| {
+                spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    mounted.set(false);
+                })
+            });
+            rsx! {
+                if mounted() {
+                    Ticker { ticks }
+                }
+            }
+        },
+        ticks.clone(),
+    );
+
+    #[component]
+    fn Ticker(ticks: Rc<RefCell<usize>>) -> Element {
+        use_interval(Duration::from_millis(10), move || *ticks.borrow_mut() += 1);
+        rsx! {}
+    }
+
+    dom.rebuild_in_place();
+    run_for(&mut dom, Duration::from_millis(150)).await;
+    let ticks_while_mounted = *ticks.borrow();
+    assert!(ticks_while_mounted >= 3, "{ticks_while_mounted} ticks");
+
+    run_for(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(*ticks.borrow(), ticks_while_mounted);
+}
+
+#[tokio::test]
+async fn timeout_fires_once() {
+    let fired = Rc::new(RefCell::new(0));
+    let mut dom = VirtualDom::new_with_props(
+        |fired: Rc<RefCell<usize>>| {
+            use_timeout(Duration::from_millis(10), move || *fired.borrow_mut() += 1);
+            rsx! {}
+        },
+        fired.clone(),
+    );
+    dom.rebuild_in_place();
+
+    run_for(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(*fired.borrow(), 1);
+}
+
+#[tokio::test]
+async fn timers_pause_when_the_component_returns_early() {
+    type Handle = (Signal<bool>, Rc<RefCell<usize>>);
+    let handle = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |handle: Rc<RefCell<Option<Handle>>>| {
+            let early_return = use_signal(|| false);
+            let fired = use_hook(|| Rc::new(RefCell::new(0)));
+            handle.borrow_mut().replace((early_return, fired.clone()));
+            if early_return() {
+                return rsx! {};
+            }
+            use_timeout(Duration::from_millis(50), move || *fired.borrow_mut() += 1);
+            rsx! {}
+        },
+        handle.clone(),
+    );
+    dom.rebuild_in_place();
+
+    let (mut early_return, fired) = handle.borrow().clone().unwrap();
+    dom.in_runtime(|| early_return.set(true));
+    run_for(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(*fired.borrow(), 0);
+
+    dom.in_runtime(|| early_return.set(false));
+    run_for(&mut dom, Duration::from_millis(100)).await;
+    assert_eq!(*fired.borrow(), 1);
+}
+
+#[tokio::test]
+async fn timers_never_run_on_the_server() {
+    fn app() -> Element {
+        let mut ticks = use_signal(|| 0);
+        let interval = use_interval(Duration::from_millis(1), move || ticks += 1);
+        let timeout = use_timeout(Duration::from_millis(1), move || ticks += 1);
+        let running = interval.is_running() || timeout.is_pending();
+        rsx! {
+            p { "ticks: {ticks}, running: {running}" }
+            SuspenseBoundary { fallback: |_| rsx! {}, Slow {} }
+        }
+    }
+
+    fn Slow() -> Element {
+        let loaded = use_resource(|| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "loaded"
+        })
+        .suspend()?;
+        rsx! { "{loaded}" }
+    }
+
+    // Render the page like a server, which waits for suspense to resolve but never handles other work
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.wait_for_suspense().await;
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<p>ticks: 0, running: false</p>loaded"
+    );
+}
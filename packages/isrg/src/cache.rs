@@ -0,0 +1,86 @@
+//! Pluggable storage for rendered routes
+
+use chrono::{DateTime, Utc};
+
+use crate::IncrementalRendererError;
+
+/// A rendered route stored in a [`CacheBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// The time the route was rendered
+    pub timestamp: DateTime<Utc>,
+    /// The tags the route was cached with. Invalidating any of these tags removes the entry.
+    pub tags: Vec<String>,
+    /// The rendered html
+    pub html: Vec<u8>,
+}
+
+/// Storage for rendered routes that sits behind the in-memory cache of an [`IncrementalRenderer`](crate::IncrementalRenderer).
+///
+/// By default, routes are written to the static directory on the file system. Implement this trait to store
+/// routes somewhere else, like a shared store that every server in a cluster reads from. Invalidating a route
+/// or tag in a shared backend removes it for every server that uses the backend.
+///
+/// The methods are async so backends that talk to a remote store don't block the server while they wait for it.
+/// Implement the trait with [`async_trait`](https://docs.rs/async-trait).
+///
+/// Expired entries are removed by the renderer, so a backend does not need to know about `invalidate_after`.
+///
+/// ```rust
+/// use dioxus_isrg::{CacheBackend, CacheEntry, IncrementalRendererError};
+/// use std::{collections::HashMap, sync::Mutex};
+///
+/// #[derive(Default)]
+/// struct HashMapCache(Mutex<HashMap<String, CacheEntry>>);
+///
+/// #[async_trait::async_trait]
+/// impl CacheBackend for HashMapCache {
+///     async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+///         Ok(self.0.lock().unwrap().get(route).cloned())
+///     }
+///
+///     async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+///         self.0.lock().unwrap().insert(route.to_string(), entry);
+///         Ok(())
+///     }
+///
+///     async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+///         self.0.lock().unwrap().remove(route);
+///         Ok(())
+///     }
+///
+///     async fn invalidate_tag(&self, tag: &str) -> Result<(), IncrementalRendererError> {
+///         self.0
+///             .lock()
+///             .unwrap()
+///             .retain(|_, entry| !entry.tags.iter().any(|t| t == tag));
+///         Ok(())
+///     }
+///
+///     async fn clear(&self) -> Result<(), IncrementalRendererError> {
+///         self.0.lock().unwrap().clear();
+///         Ok(())
+///     }
+/// }
+///
+/// let renderer = dioxus_isrg::IncrementalRenderer::builder()
+///     .cache_backend(HashMapCache::default())
+///     .build();
+/// ```
+#[async_trait::async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Get the cached render for a route if it exists.
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError>;
+
+    /// Store the render for a route, replacing any existing entry.
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError>;
+
+    /// Remove a route from the cache.
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError>;
+
+    /// Remove every route that was cached with the given tag.
+    async fn invalidate_tag(&self, tag: &str) -> Result<(), IncrementalRendererError>;
+
+    /// Remove all routes from the cache.
+    async fn clear(&self) -> Result<(), IncrementalRendererError>;
+}
@@ -4,21 +4,27 @@
 use crate::fs_cache::PathMapFn;
 
 use crate::memory_cache::InMemoryCache;
-use crate::IncrementalRenderer;
+use crate::{CacheBackend, IncrementalRenderer};
 
 use std::{
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
+pub(crate) type RouteTagsFn = Arc<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
 /// A configuration for the incremental renderer.
 #[derive(Clone)]
 pub struct IncrementalRendererConfig {
     static_dir: PathBuf,
     memory_cache_limit: usize,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
     clear_cache: bool,
     pre_render: bool,
+    cache_backend: Option<Arc<dyn CacheBackend>>,
+    route_tags: Option<RouteTagsFn>,
 
     #[cfg(not(target_arch = "wasm32"))]
     map_path: Option<PathMapFn>,
@@ -37,14 +43,17 @@ impl IncrementalRendererConfig {
             static_dir: PathBuf::from("./static"),
             memory_cache_limit: 10000,
             invalidate_after: None,
+            stale_while_revalidate: None,
             clear_cache: true,
             pre_render: false,
+            cache_backend: None,
+            route_tags: None,
             #[cfg(not(target_arch = "wasm32"))]
             map_path: None,
         }
     }
 
-    /// Clear the cache on startup (default: true)
+    /// Clear the cache before the renderer first uses it (default: true)
     pub fn clear_cache(mut self, clear_cache: bool) -> Self {
         self.clear_cache = clear_cache;
         self
@@ -52,6 +61,8 @@ impl IncrementalRendererConfig {
 
     /// Set a mapping from the route to the file path. This will override the default mapping configured with `static_dir`.
     /// The function should return the path to the folder to store the index.html file in.
+    ///
+    /// This only applies to the default file system backend.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn map_path<F: Fn(&str) -> PathBuf + Send + Sync + 'static>(mut self, map_path: F) -> Self {
         self.map_path = Some(std::sync::Arc::new(map_path));
@@ -59,6 +70,9 @@ impl IncrementalRendererConfig {
     }
 
     /// Set the static directory.
+    ///
+    /// The tags of the cached routes are kept out of the static directory, in a directory next to it with a `.tags`
+    /// extension.
     pub fn static_dir<P: AsRef<Path>>(mut self, static_dir: P) -> Self {
         self.static_dir = static_dir.as_ref().to_path_buf();
         self
@@ -76,6 +90,82 @@ impl IncrementalRendererConfig {
         self
     }
 
+    /// Store rendered routes in a custom [`CacheBackend`] instead of the file system (default: the static directory).
+    ///
+    /// If the backend is shared between servers, set [`Self::clear_cache`] to `false` so restarting one server
+    /// doesn't wipe the cache of every other server. The in-memory cache of each server is not checked against the backend,
+    /// so set [`Self::memory_cache_limit`] to `0` if invalidations from other servers need to apply immediately.
+    pub fn cache_backend(mut self, backend: impl CacheBackend + 'static) -> Self {
+        self.cache_backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Set the tags a route is cached with. Invalidating a tag with [`IncrementalRenderer::invalidate_tag`]
+    /// removes every route that was cached with that tag.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// let renderer = IncrementalRenderer::builder()
+    ///     .route_tags(|route| match route.strip_prefix("/product/") {
+    ///         Some(id) => vec![format!("product:{id}")],
+    ///         None => Vec::new(),
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn route_tags<F: Fn(&str) -> Vec<String> + Send + Sync + 'static>(
+        mut self,
+        route_tags: F,
+    ) -> Self {
+        self.route_tags = Some(Arc::new(route_tags));
+        self
+    }
+
+    /// Keep serving expired routes for a window of time after [`Self::invalidate_after`] while they are re-rendered.
+    ///
+    /// During the window, [`IncrementalRenderer::get`] returns the stale render and asks the first caller to
+    /// re-render the route with [`CachedRender::revalidate`](crate::CachedRender::revalidate). Responses include a
+    /// `Cache-Control: stale-while-revalidate` directive. Routes older than the window are treated as a cache miss.
+    ///
+    /// Has no effect unless [`Self::invalidate_after`] is set.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # use std::time::Duration;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let static_dir = std::env::temp_dir().join(format!("dioxus-isrg-swr-doc-{}", std::process::id()));
+    /// let renderer = IncrementalRenderer::builder()
+    /// #   .static_dir(static_dir.clone())
+    ///     .invalidate_after(Duration::ZERO)
+    ///     .stale_while_revalidate(Duration::from_secs(60))
+    ///     .build();
+    /// renderer.cache("/index".to_string(), "Old").await.unwrap();
+    /// std::thread::sleep(Duration::from_millis(10));
+    ///
+    /// // The first request after the route expires gets the stale render and re-renders the route
+    /// let stale = renderer.get("/index").await.unwrap().unwrap();
+    /// assert_eq!(&*stale.response, b"Old");
+    /// assert!(stale.revalidate);
+    /// let mut headers = http::HeaderMap::new();
+    /// stale.freshness.write(&mut headers);
+    /// assert_eq!(headers[http::header::CACHE_CONTROL], "max-age=0, stale-while-revalidate=60");
+    ///
+    /// // Other requests get the stale render without re-rendering the route
+    /// assert!(!renderer.get("/index").await.unwrap().unwrap().revalidate);
+    ///
+    /// // Once the new render is cached, it replaces the stale render
+    /// renderer.cache("/index".to_string(), "New").await.unwrap();
+    /// let fresh = renderer.get("/index").await.unwrap().unwrap();
+    /// assert_eq!(&*fresh.response, b"New");
+    /// # std::fs::remove_dir_all(&static_dir).unwrap();
+    /// # _ = std::fs::remove_dir_all(static_dir.with_extension("tags"));
+    /// # }
+    /// ```
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = Some(window);
+        self
+    }
+
     /// Set whether to include hydration ids in the pre-rendered html.
     pub fn pre_render(mut self, pre_render: bool) -> Self {
         self.pre_render = pre_render;
@@ -84,21 +174,26 @@ impl IncrementalRendererConfig {
 
     /// Build the incremental renderer.
     pub fn build(self) -> IncrementalRenderer {
-        let mut renderer = IncrementalRenderer {
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache: crate::fs_cache::FileSystemCache::new(
-                self.static_dir.clone(),
-                self.map_path,
-                self.invalidate_after,
-            ),
-            memory_cache: InMemoryCache::new(self.memory_cache_limit, self.invalidate_after),
+        #[cfg(not(target_arch = "wasm32"))]
+        let default_backend = Some(Arc::new(crate::fs_cache::FileSystemCache::new(
+            self.static_dir.clone(),
+            self.map_path,
+            // Keep stale files around until they can no longer be served
+            self.invalidate_after
+                .map(|after| after + self.stale_while_revalidate.unwrap_or_default()),
+        )) as Arc<dyn CacheBackend>);
+        #[cfg(target_arch = "wasm32")]
+        let default_backend = None;
+
+        IncrementalRenderer {
+            backend: self.cache_backend.or(default_backend),
+            memory_cache: InMemoryCache::new(self.memory_cache_limit).into(),
+            route_tags: self.route_tags,
             invalidate_after: self.invalidate_after,
-        };
-
-        if self.clear_cache {
-            renderer.invalidate_all();
+            stale_while_revalidate: self.stale_while_revalidate,
+            revalidating: Default::default(),
+            // The backend can only be cleared asynchronously, so it is cleared before it is first used
+            pending_clear: self.clear_cache.into(),
         }
-
-        renderer
     }
 }
@@ -0,0 +1,259 @@
+//! A key-value cache backend stored in a directory
+
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHasher;
+
+use crate::{CacheBackend, CacheEntry, IncrementalRendererError};
+
+/// A [`CacheBackend`] that stores every route as a single file in a directory.
+///
+/// Unlike the default file system cache, entries are keyed by a hash of the route and carry their
+/// timestamp and tags with them. Writes are atomic, so multiple servers can share the directory
+/// (for example, a volume mounted into every replica) and see each other's renders and invalidations.
+///
+/// ```rust
+/// use dioxus_isrg::{DirectoryCache, IncrementalRenderer};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let shared_dir = std::env::temp_dir().join("dioxus-isrg-directory-cache-doc");
+/// let server = || {
+///     IncrementalRenderer::builder()
+///         .cache_backend(DirectoryCache::new(&shared_dir))
+///         // Pages cached in memory are not checked against the shared directory
+///         .memory_cache_limit(0)
+///         // Restarting one server shouldn't wipe the cache for every server
+///         .clear_cache(false)
+///         .build()
+/// };
+/// let first = server();
+/// let second = server();
+///
+/// first.cache("/index".to_string(), "Hello world").await.unwrap();
+/// let cached = second.get("/index").await.unwrap().unwrap();
+/// assert_eq!(&*cached.response, b"Hello world");
+///
+/// second.invalidate("/index").await;
+/// assert!(first.get("/index").await.unwrap().is_none());
+/// # }
+/// ```
+pub struct DirectoryCache {
+    root: PathBuf,
+}
+
+impl DirectoryCache {
+    /// Create a new cache that stores entries in the given directory.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    fn entry_path(&self, route: &str) -> PathBuf {
+        let mut path = self.root.join("entries");
+        path.push(hash_key(route));
+        path
+    }
+
+    fn tag_path(&self, tag: &str) -> PathBuf {
+        let mut path = self.root.join("tags");
+        path.push(hash_key(tag));
+        path
+    }
+
+    /// Write a file by writing to a temporary file and renaming it so readers never see a partial write.
+    fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), IncrementalRendererError> {
+        let parent = path.parent().expect("cache paths always have a parent");
+        std::fs::create_dir_all(parent)?;
+        static WRITE_ID: AtomicUsize = AtomicUsize::new(0);
+        let temp_path = parent.join(format!(
+            ".{}.{}.{}.tmp",
+            path.file_name().unwrap().to_string_lossy(),
+            std::process::id(),
+            WRITE_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temp_path, contents)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// The routes cached with a tag
+    pub(crate) fn tagged_routes(&self, tag: &str) -> std::io::Result<Vec<String>> {
+        read_routes(&self.tag_path(tag))
+    }
+
+    /// Add or remove a route from the list of routes cached with a tag. Tags without routes are removed.
+    fn set_tagged(
+        &self,
+        tag: &str,
+        route: &str,
+        tagged: bool,
+    ) -> Result<(), IncrementalRendererError> {
+        let tag_path = self.tag_path(tag);
+        let mut routes = read_routes(&tag_path)?;
+        if routes.iter().any(|listed| listed == route) == tagged {
+            return Ok(());
+        }
+        match tagged {
+            true => routes.push(route.to_string()),
+            false => routes.retain(|listed| listed != route),
+        }
+
+        if routes.is_empty() {
+            return remove_file(&tag_path);
+        }
+        let mut contents = String::new();
+        for route in routes {
+            contents += &route;
+            contents.push('\n');
+        }
+        Self::write_atomic(&tag_path, contents.as_bytes())
+    }
+
+    /// Read the entry of a route
+    fn read(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        let contents = match std::fs::read(self.entry_path(route)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        match decode_entry(&contents) {
+            // The hash of another route may collide with this one, so check the stored route as well
+            Some((stored_route, entry)) if stored_route == route => Ok(Some(entry)),
+            Some(_) => Ok(None),
+            None => {
+                tracing::error!("Corrupted cache entry for route {route:?}");
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for DirectoryCache {
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
+        self.read(route)
+    }
+
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
+        if route.contains('\n') || entry.tags.iter().any(|tag| tag.contains('\n')) {
+            return Err(IncrementalRendererError::Other(
+                "routes and tags stored in a DirectoryCache cannot contain newlines".into(),
+            ));
+        }
+
+        let old_tags = self.read(route)?.map(|old| old.tags).unwrap_or_default();
+        Self::write_atomic(&self.entry_path(route), &encode_entry(route, &entry))?;
+
+        // Only touch the tags that changed, so rendering a route again doesn't grow its tags
+        for tag in old_tags.iter().filter(|tag| !entry.tags.contains(tag)) {
+            self.set_tagged(tag, route, false)?;
+        }
+        for tag in entry.tags.iter().filter(|tag| !old_tags.contains(tag)) {
+            self.set_tagged(tag, route, true)?;
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        let Some(entry) = self.read(route)? else {
+            return Ok(());
+        };
+        remove_file(&self.entry_path(route))?;
+        for tag in &entry.tags {
+            self.set_tagged(tag, route, false)?;
+        }
+        Ok(())
+    }
+
+    async fn invalidate_tag(&self, tag: &str) -> Result<(), IncrementalRendererError> {
+        let tag_path = self.tag_path(tag);
+        for route in read_routes(&tag_path)? {
+            self.invalidate(&route).await?;
+        }
+        remove_file(&tag_path)
+    }
+
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
+        match std::fs::remove_dir_all(&self.root) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Remove a file if it exists
+fn remove_file(path: &Path) -> Result<(), IncrementalRendererError> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Read the routes listed in a tag file
+fn read_routes(path: &Path) -> std::io::Result<Vec<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(routes) => Ok(routes.lines().map(str::to_string).collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Entries are stored as a header of `key: value` lines, a blank line and then the html.
+fn encode_entry(route: &str, entry: &CacheEntry) -> Vec<u8> {
+    let mut encoded = format!(
+        "route: {route}\ntimestamp: {}\n",
+        entry.timestamp.timestamp_millis()
+    );
+    for tag in &entry.tags {
+        encoded += &format!("tag: {tag}\n");
+    }
+    encoded.push('\n');
+
+    let mut encoded = encoded.into_bytes();
+    encoded.extend_from_slice(&entry.html);
+    encoded
+}
+
+fn decode_entry(contents: &[u8]) -> Option<(&str, CacheEntry)> {
+    let header_end = contents.windows(2).position(|window| window == b"\n\n")?;
+    let header = std::str::from_utf8(&contents[..header_end]).ok()?;
+    let html = contents[header_end + 2..].to_vec();
+
+    let mut route = None;
+    let mut timestamp = None;
+    let mut tags = Vec::new();
+    for line in header.lines() {
+        match line.split_once(": ")? {
+            ("route", value) => route = Some(value),
+            ("timestamp", value) => {
+                timestamp = DateTime::<Utc>::from_timestamp_millis(value.parse().ok()?)
+            }
+            ("tag", value) => tags.push(value.to_string()),
+            _ => return None,
+        }
+    }
+
+    Some((
+        route?,
+        CacheEntry {
+            timestamp: timestamp?,
+            tags,
+            html,
+        },
+    ))
+}
@@ -9,29 +9,13 @@ pub struct RenderFreshness {
     age: u64,
     /// The maximum age of the rendered response
     max_age: Option<u64>,
+    /// How long after `max_age` the response may still be served while it is re-rendered
+    stale_while_revalidate: Option<u64>,
     /// The time the response was rendered
     timestamp: DateTime<Utc>,
 }
 
 impl RenderFreshness {
-    /// Create new freshness information
-    pub(crate) fn new(age: u64, max_age: u64, timestamp: DateTime<Utc>) -> Self {
-        Self {
-            age,
-            max_age: Some(max_age),
-            timestamp,
-        }
-    }
-
-    /// Create new freshness information with only the age
-    pub(crate) fn new_age(age: u64, timestamp: DateTime<Utc>) -> Self {
-        Self {
-            age,
-            max_age: None,
-            timestamp,
-        }
-    }
-
     /// Create new freshness information from a timestamp
     pub(crate) fn created_at(timestamp: DateTime<Utc>, max_age: Option<Duration>) -> Self {
         Self {
@@ -40,15 +24,23 @@ impl RenderFreshness {
                 .num_seconds()
                 .unsigned_abs(),
             max_age: max_age.map(|d| d.as_secs()),
+            stale_while_revalidate: None,
             timestamp,
         }
     }
 
+    /// Set how long the response may be served after it expires while it is re-rendered
+    pub(crate) fn with_stale_while_revalidate(mut self, window: Option<Duration>) -> Self {
+        self.stale_while_revalidate = window.map(|d| d.as_secs());
+        self
+    }
+
     /// Create new freshness information at the current time
     pub fn now(max_age: Option<Duration>) -> Self {
         Self {
             age: 0,
             max_age: max_age.map(|d| d.as_secs()),
+            stale_while_revalidate: None,
             timestamp: Utc::now(),
         }
     }
@@ -63,6 +55,16 @@ impl RenderFreshness {
         self.max_age
     }
 
+    /// Get how long in seconds the response may be served after it expires while it is re-rendered
+    pub fn stale_while_revalidate(&self) -> Option<u64> {
+        self.stale_while_revalidate
+    }
+
+    /// Check if the response is older than its maximum age
+    pub fn is_stale(&self) -> bool {
+        self.max_age.is_some_and(|max_age| self.age > max_age)
+    }
+
     /// Get the time the response was rendered
     pub fn timestamp(&self) -> DateTime<Utc> {
         self.timestamp
@@ -73,9 +75,13 @@ impl RenderFreshness {
         let age = self.age();
         headers.insert(http::header::AGE, age.into());
         if let Some(max_age) = self.max_age() {
+            let cache_control = match self.stale_while_revalidate() {
+                Some(window) => format!("max-age={}, stale-while-revalidate={}", max_age, window),
+                None => format!("max-age={}", max_age),
+            };
             headers.insert(
                 http::header::CACHE_CONTROL,
-                http::HeaderValue::from_str(&format!("max-age={}", max_age)).unwrap(),
+                http::HeaderValue::from_str(&cache_control).unwrap(),
             );
         }
     }
@@ -2,13 +2,15 @@
 
 use chrono::{DateTime, Utc};
 
-use super::{IncrementalRendererError, RenderFreshness};
+use super::{CacheBackend, CacheEntry, DirectoryCache, IncrementalRendererError};
 use std::{path::PathBuf, sync::Arc, time::SystemTime};
 
 pub(crate) type PathMapFn = Arc<dyn Fn(&str) -> PathBuf + Send + Sync>;
 
 pub(crate) struct FileSystemCache {
     static_dir: PathBuf,
+    /// The tags of each route. They are kept next to the static directory instead of inside it so they are not served.
+    tags: DirectoryCache,
     map_path: PathMapFn,
     invalidate_after: Option<std::time::Duration>,
 }
@@ -20,6 +22,7 @@ impl FileSystemCache {
         invalidate_after: Option<std::time::Duration>,
     ) -> Self {
         Self {
+            tags: DirectoryCache::new(static_dir.with_extension("tags")),
             static_dir: static_dir.clone(),
             map_path: map_path.unwrap_or_else(move || {
                 Arc::new(move |route: &str| {
@@ -34,15 +37,13 @@ impl FileSystemCache {
             invalidate_after,
         }
     }
+}
 
-    pub fn put(
-        &mut self,
-        route: String,
-        timestamp: DateTime<Utc>,
-        data: Vec<u8>,
-    ) -> Result<(), IncrementalRendererError> {
+#[async_trait::async_trait]
+impl CacheBackend for FileSystemCache {
+    async fn put(&self, route: &str, entry: CacheEntry) -> Result<(), IncrementalRendererError> {
         use std::io::Write;
-        let file_path = self.route_as_path(&route, timestamp);
+        let file_path = self.route_as_path(route, entry.timestamp);
         if let Some(parent) = file_path.parent() {
             if !parent.exists() {
                 std::fs::create_dir_all(parent)?;
@@ -50,41 +51,70 @@ impl FileSystemCache {
         }
         let file = std::fs::File::create(file_path)?;
         let mut file = std::io::BufWriter::new(file);
-        file.write_all(&data)?;
-        Ok(())
+        file.write_all(&entry.html)?;
+
+        match entry.tags.is_empty() {
+            true => self.tags.invalidate(route).await,
+            // Only the tags are stored, the html lives in the static directory
+            false => {
+                self.tags
+                    .put(
+                        route,
+                        CacheEntry {
+                            html: Vec::new(),
+                            ..entry
+                        },
+                    )
+                    .await
+            }
+        }
     }
 
-    pub fn clear(&mut self) {
+    async fn clear(&self) -> Result<(), IncrementalRendererError> {
         // clear the static directory
         let _ = std::fs::remove_dir_all(&self.static_dir);
+        self.tags.clear().await
     }
 
-    pub fn invalidate(&mut self, route: &str) {
-        let file_path = self.find_file(route).unwrap().full_path;
-        if let Err(err) = std::fs::remove_file(file_path) {
-            tracing::error!("Failed to remove file: {}", err);
+    async fn invalidate(&self, route: &str) -> Result<(), IncrementalRendererError> {
+        if let Some(file) = self.find_file(route) {
+            std::fs::remove_file(file.full_path)?;
         }
+        self.tags.invalidate(route).await
     }
 
-    pub fn get(
-        &self,
-        route: &str,
-    ) -> Result<Option<(RenderFreshness, Vec<u8>)>, IncrementalRendererError> {
+    async fn invalidate_tag(&self, tag: &str) -> Result<(), IncrementalRendererError> {
+        for route in self.tags.tagged_routes(tag)? {
+            self.invalidate(&route).await?;
+        }
+        Ok(())
+    }
+
+    async fn get(&self, route: &str) -> Result<Option<CacheEntry>, IncrementalRendererError> {
         if let Some(file_path) = self.find_file(route) {
-            if let Some(freshness) = file_path.freshness(self.invalidate_after) {
-                if let Ok(file) = std::fs::File::open(file_path.full_path) {
-                    let mut file = std::io::BufReader::new(file);
-                    let mut cache_hit = Vec::new();
-                    std::io::copy(&mut file, &mut cache_hit)?;
-                    tracing::trace!("file cache hit {:?}", route);
-                    return Ok(Some((freshness, cache_hit)));
-                }
+            if let Ok(file) = std::fs::File::open(&file_path.full_path) {
+                let mut file = std::io::BufReader::new(file);
+                let mut cache_hit = Vec::new();
+                std::io::copy(&mut file, &mut cache_hit)?;
+                tracing::trace!("file cache hit {:?}", route);
+                return Ok(Some(CacheEntry {
+                    timestamp: file_path.timestamp.into(),
+                    tags: self
+                        .tags
+                        .get(route)
+                        .await?
+                        .map(|tags| tags.tags)
+                        .unwrap_or_default(),
+                    html: cache_hit,
+                }));
             }
         }
 
         Ok(None)
     }
+}
 
+impl FileSystemCache {
     fn find_file(&self, route: &str) -> Option<ValidCachedPath> {
         let mut file_path = (self.map_path)(route);
         if let Some(deadline) = self.invalidate_after {
@@ -154,12 +184,6 @@ impl ValidCachedPath {
             timestamp,
         })
     }
-
-    pub fn freshness(&self, max_age: Option<std::time::Duration>) -> Option<RenderFreshness> {
-        let age = self.timestamp.elapsed().ok()?.as_secs();
-        let max_age = max_age.map(|max_age| max_age.as_secs());
-        Some(RenderFreshness::new(age, max_age?, self.timestamp.into()))
-    }
 }
 
 fn decode_timestamp(timestamp: &str) -> Option<std::time::SystemTime> {
@@ -2,16 +2,27 @@
 
 #![allow(non_snake_case)]
 
+mod cache;
 mod config;
+#[cfg(not(target_arch = "wasm32"))]
+mod directory_cache;
 mod freshness;
 #[cfg(not(target_arch = "wasm32"))]
 mod fs_cache;
 mod memory_cache;
 
-use std::time::Duration;
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use chrono::Utc;
+pub use cache::*;
+use chrono::{DateTime, Utc};
 pub use config::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use directory_cache::*;
 pub use freshness::*;
 
 use self::memory_cache::InMemoryCache;
@@ -23,15 +34,35 @@ pub struct CachedRender<'a> {
     /// The freshness information for the rendered response
     pub freshness: RenderFreshness,
     /// The rendered response
-    pub response: &'a [u8],
+    pub response: Cow<'a, [u8]>,
+    /// Whether the caller should re-render the route and cache the result.
+    ///
+    /// This is only set for stale renders served with [`IncrementalRendererConfig::stale_while_revalidate`]. Only the first
+    /// caller that receives a stale render is asked to revalidate it until the route is cached again or the revalidation
+    /// is cancelled with [`IncrementalRenderer::cancel_revalidation`].
+    pub revalidate: bool,
 }
 
 /// An incremental renderer.
+///
+/// The renderer can be shared between requests. Its locks are never held while it waits for the [`CacheBackend`].
 pub struct IncrementalRenderer {
-    pub(crate) memory_cache: InMemoryCache,
-    #[cfg(not(target_arch = "wasm32"))]
-    pub(crate) file_system_cache: fs_cache::FileSystemCache,
+    pub(crate) memory_cache: Mutex<InMemoryCache>,
+    pub(crate) backend: Option<Arc<dyn CacheBackend>>,
+    pub(crate) route_tags: Option<RouteTagsFn>,
     invalidate_after: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+    /// Routes that a caller is currently re-rendering
+    revalidating: Mutex<HashSet<String>>,
+    /// Whether the cache still needs to be cleared before it is first used
+    pending_clear: futures_util::lock::Mutex<bool>,
+}
+
+/// How old a cached render is compared to the configured limits
+enum Expiry {
+    Fresh,
+    Stale,
+    Expired,
 }
 
 impl IncrementalRenderer {
@@ -41,109 +72,237 @@ impl IncrementalRenderer {
     }
 
     /// Remove a route from the cache.
-    pub fn invalidate(&mut self, route: &str) {
-        self.memory_cache.invalidate(route);
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.invalidate(route);
+    pub async fn invalidate(&self, route: &str) {
+        self.clear_if_pending().await;
+        self.memory_cache.lock().unwrap().invalidate(route);
+        if let Some(backend) = &self.backend {
+            if let Err(err) = backend.invalidate(route).await {
+                tracing::error!("Failed to invalidate route {route:?}: {err}");
+            }
+        }
+    }
+
+    /// Remove every route that was cached with a tag.
+    ///
+    /// ```rust
+    /// # use dioxus_isrg::IncrementalRenderer;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let renderer = IncrementalRenderer::builder()
+    /// #   .static_dir(std::env::temp_dir().join("dioxus-isrg-invalidate-tag-doc"))
+    ///     .route_tags(|route| match route.strip_prefix("/product/") {
+    ///         Some(id) => vec![format!("product:{id}")],
+    ///         None => Vec::new(),
+    ///     })
+    ///     .build();
+    /// renderer.cache("/product/42".to_string(), "Product 42").await.unwrap();
+    /// renderer
+    ///     .cache_with_tags("/featured".to_string(), "Featured: Product 42", ["product:42"])
+    ///     .await
+    ///     .unwrap();
+    /// renderer.cache("/product/43".to_string(), "Product 43").await.unwrap();
+    ///
+    /// renderer.invalidate_tag("product:42").await;
+    /// assert!(renderer.get("/product/42").await.unwrap().is_none());
+    /// assert!(renderer.get("/featured").await.unwrap().is_none());
+    /// assert!(renderer.get("/product/43").await.unwrap().is_some());
+    /// # }
+    /// ```
+    pub async fn invalidate_tag(&self, tag: &str) {
+        self.clear_if_pending().await;
+        self.memory_cache.lock().unwrap().invalidate_tag(tag);
+        if let Some(backend) = &self.backend {
+            if let Err(err) = backend.invalidate_tag(tag).await {
+                tracing::error!("Failed to invalidate tag {tag:?}: {err}");
+            }
+        }
     }
 
     /// Remove all routes from the cache.
-    pub fn invalidate_all(&mut self) {
-        self.memory_cache.clear();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache.clear();
+    pub async fn invalidate_all(&self) {
+        let mut pending_clear = self.pending_clear.lock().await;
+        *pending_clear = false;
+        self.clear().await;
+    }
+
+    /// Clear the cache if [`IncrementalRendererConfig::clear_cache`] is set and it wasn't cleared yet
+    async fn clear_if_pending(&self) {
+        let mut pending_clear = self.pending_clear.lock().await;
+        if std::mem::take(&mut *pending_clear) {
+            self.clear().await;
+        }
+    }
+
+    async fn clear(&self) {
+        self.memory_cache.lock().unwrap().clear();
+        if let Some(backend) = &self.backend {
+            if let Err(err) = backend.clear().await {
+                tracing::error!("Failed to clear the incremental cache: {err}");
+            }
+        }
     }
 
     /// Cache a rendered response.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
     /// let route = "/index".to_string();
     /// let response = b"<html><body>Hello world</body></html>";
-    /// renderer.cache(route, response).unwrap();
+    /// renderer.cache(route, response).await.unwrap();
+    /// # }
     /// ```
-    pub fn cache(
-        &mut self,
+    pub async fn cache(
+        &self,
+        route: String,
+        html: impl Into<Vec<u8>>,
+    ) -> Result<RenderFreshness, IncrementalRendererError> {
+        self.cache_with_tags(route, html, std::iter::empty::<String>())
+            .await
+    }
+
+    /// Cache a rendered response with extra tags in addition to the tags from [`IncrementalRendererConfig::route_tags`].
+    pub async fn cache_with_tags(
+        &self,
         route: String,
         html: impl Into<Vec<u8>>,
+        tags: impl IntoIterator<Item = impl Into<String>>,
     ) -> Result<RenderFreshness, IncrementalRendererError> {
         let timestamp = Utc::now();
-        let html = html.into();
-        #[cfg(not(target_arch = "wasm32"))]
-        self.file_system_cache
-            .put(route.clone(), timestamp, html.clone())?;
-        self.memory_cache.put(route, timestamp, html);
-        Ok(RenderFreshness::created_at(
+        let mut all_tags = match &self.route_tags {
+            Some(route_tags) => route_tags(&route),
+            None => Vec::new(),
+        };
+        all_tags.extend(tags.into_iter().map(Into::into));
+        let entry = CacheEntry {
             timestamp,
-            self.invalidate_after,
-        ))
+            tags: all_tags,
+            html: html.into(),
+        };
+        self.clear_if_pending().await;
+        if let Some(backend) = &self.backend {
+            backend.put(&route, entry.clone()).await?;
+        }
+        self.revalidating.lock().unwrap().remove(&route);
+        self.memory_cache.lock().unwrap().put(route, entry);
+        Ok(self.freshness(timestamp))
+    }
+
+    /// Release the revalidation of a route that was claimed by a stale [`CachedRender`] without caching a new render,
+    /// for example because rendering failed. The next request for the stale route will be asked to revalidate it.
+    pub fn cancel_revalidation(&self, route: &str) {
+        self.revalidating.lock().unwrap().remove(route);
     }
 
     /// Try to get a cached response for a route.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
     /// # let route = "/index".to_string();
     /// # let response = b"<html><body>Hello world</body></html>";
-    /// # renderer.cache(route, response).unwrap();
+    /// # renderer.cache(route, response).await.unwrap();
     /// let route = "/index";
-    /// let response = renderer.get(route).unwrap();
-    /// assert_eq!(response.unwrap().response, b"<html><body>Hello world</body></html>");
+    /// let response = renderer.get(route).await.unwrap();
+    /// assert_eq!(&*response.unwrap().response, b"<html><body>Hello world</body></html>");
+    /// # }
     /// ```
     ///
     /// If the route is not cached, `None` is returned.
     ///
     /// ```rust
     /// # use dioxus_isrg::IncrementalRenderer;
-    /// # let mut renderer = IncrementalRenderer::builder().build();
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let renderer = IncrementalRenderer::builder().build();
     /// let route = "/index";
-    /// let response = renderer.get(route).unwrap();
+    /// let response = renderer.get(route).await.unwrap();
     /// assert!(response.is_none());
+    /// # }
     /// ```
-    pub fn get<'a>(
-        &'a mut self,
+    pub async fn get(
+        &self,
         route: &str,
-    ) -> Result<Option<CachedRender<'a>>, IncrementalRendererError> {
-        let Self {
-            memory_cache,
-            #[cfg(not(target_arch = "wasm32"))]
-            file_system_cache,
-            ..
-        } = self;
-
-        #[allow(unused)]
-        enum FsGetError {
-            NotPresent,
-            Error(IncrementalRendererError),
-        }
+    ) -> Result<Option<CachedRender<'static>>, IncrementalRendererError> {
+        self.clear_if_pending().await;
 
-        // The borrow checker prevents us from simply using a match/if and returning early. Instead we need to use the more complex closure API
-        // non lexical lifetimes will make this possible (it works with polonius)
-        let or_insert = || {
-            // check the file cache
-            #[cfg(not(target_arch = "wasm32"))]
-            return match file_system_cache.get(route) {
-                Ok(Some((freshness, bytes))) => Ok((freshness.timestamp(), bytes)),
-                Ok(None) => Err(FsGetError::NotPresent),
-                Err(e) => Err(FsGetError::Error(e)),
-            };
-
-            #[allow(unreachable_code)]
-            Err(FsGetError::NotPresent)
+        // If the route isn't in memory yet, check the backend
+        let in_memory = self.memory_cache.lock().unwrap().get(route).cloned();
+        let entry = match in_memory {
+            Some(entry) => entry,
+            None => {
+                let Some(backend) = &self.backend else {
+                    return Ok(None);
+                };
+                let Some(entry) = backend.get(route).await? else {
+                    return Ok(None);
+                };
+                self.memory_cache
+                    .lock()
+                    .unwrap()
+                    .put(route.to_string(), entry.clone());
+                entry
+            }
         };
 
-        match memory_cache.try_get_or_insert(route, or_insert) {
-            Ok(Some((freshness, bytes))) => Ok(Some(CachedRender {
-                route: route.to_string(),
-                freshness,
-                response: bytes,
-            })),
-            Err(FsGetError::NotPresent) | Ok(None) => Ok(None),
-            Err(FsGetError::Error(e)) => Err(e),
+        let Some(revalidate) = self.check_expiry(route, entry.timestamp).await else {
+            return Ok(None);
+        };
+
+        Ok(Some(CachedRender {
+            route: route.to_string(),
+            freshness: self.freshness(entry.timestamp),
+            response: Cow::Owned(entry.html),
+            revalidate,
+        }))
+    }
+
+    /// Check if a cached render can still be served. Removes the route and returns `None` if it has expired.
+    /// Otherwise returns whether the caller should revalidate the route.
+    async fn check_expiry(&self, route: &str, timestamp: DateTime<Utc>) -> Option<bool> {
+        match self.expiry(timestamp) {
+            Expiry::Fresh => Some(false),
+            Expiry::Stale => {
+                tracing::trace!("serving stale cache entry for {route:?}");
+                Some(self.revalidating.lock().unwrap().insert(route.to_string()))
+            }
+            Expiry::Expired => {
+                // The cache entry is out of date, so we need to remove it.
+                tracing::trace!("cache entry for {route:?} out of date");
+                self.invalidate(route).await;
+                None
+            }
+        }
+    }
+
+    fn expiry(&self, timestamp: DateTime<Utc>) -> Expiry {
+        let Some(invalidate_after) = self.invalidate_after else {
+            return Expiry::Fresh;
+        };
+        // If we can't convert to a std duration, the duration is negative and hasn't elapsed yet.
+        let Ok(elapsed) = Utc::now().signed_duration_since(timestamp).to_std() else {
+            return Expiry::Fresh;
+        };
+        if elapsed <= invalidate_after {
+            Expiry::Fresh
+        } else if self
+            .stale_while_revalidate
+            .is_some_and(|window| elapsed <= invalidate_after + window)
+        {
+            Expiry::Stale
+        } else {
+            Expiry::Expired
         }
     }
+
+    fn freshness(&self, timestamp: DateTime<Utc>) -> RenderFreshness {
+        RenderFreshness::created_at(timestamp, self.invalidate_after)
+            .with_stale_while_revalidate(self.stale_while_revalidate)
+    }
 }
 
 /// An error that can occur while rendering a route or retrieving a cached route.
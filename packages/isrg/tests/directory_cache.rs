@@ -0,0 +1,62 @@
+use dioxus_isrg::{CacheBackend, CacheEntry, DirectoryCache};
+use std::path::Path;
+
+fn entry(tags: &[&str]) -> CacheEntry {
+    CacheEntry {
+        timestamp: chrono::Utc::now(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        html: b"Hello world".to_vec(),
+    }
+}
+
+/// The contents of every tag file
+fn tag_files(root: &Path) -> Vec<String> {
+    let mut files: Vec<_> = match root.join("tags").read_dir() {
+        Ok(dir) => dir
+            .map(|file| std::fs::read_to_string(file.unwrap().path()).unwrap())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+    files
+}
+
+#[tokio::test]
+async fn tags_follow_the_latest_render() {
+    let root =
+        std::env::temp_dir().join(format!("dioxus-isrg-directory-tags-{}", std::process::id()));
+    let cache = DirectoryCache::new(&root);
+    cache.clear().await.unwrap();
+
+    // Rendering a route again doesn't list it twice
+    cache
+        .put("/product/42", entry(&["product:42", "product:42"]))
+        .await
+        .unwrap();
+    cache
+        .put("/product/42", entry(&["product:42"]))
+        .await
+        .unwrap();
+    assert_eq!(tag_files(&root), ["/product/42\n"]);
+
+    // Routes are removed from the tags they no longer carry
+    cache
+        .put("/product/42", entry(&["featured"]))
+        .await
+        .unwrap();
+    assert_eq!(tag_files(&root), ["/product/42\n"]);
+    cache.invalidate_tag("product:42").await.unwrap();
+    assert!(cache.get("/product/42").await.unwrap().is_some());
+
+    // Invalidating a route removes it from its tags
+    cache
+        .put("/product/43", entry(&["featured"]))
+        .await
+        .unwrap();
+    cache.invalidate("/product/42").await.unwrap();
+    assert_eq!(tag_files(&root), ["/product/43\n"]);
+    cache.invalidate("/product/43").await.unwrap();
+    assert!(tag_files(&root).is_empty());
+
+    cache.clear().await.unwrap();
+}
@@ -0,0 +1,56 @@
+use dioxus_isrg::IncrementalRenderer;
+use std::path::Path;
+
+fn renderer(static_dir: &Path, clear_cache: bool) -> IncrementalRenderer {
+    IncrementalRenderer::builder()
+        .static_dir(static_dir)
+        .clear_cache(clear_cache)
+        .route_tags(|route| match route.strip_prefix("/product/") {
+            Some(id) => vec![format!("product:{id}")],
+            None => Vec::new(),
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn tags_survive_a_reload_from_disk() {
+    let static_dir = std::env::temp_dir().join(format!("dioxus-isrg-tags-{}", std::process::id()));
+
+    let first = renderer(&static_dir, true);
+    first
+        .cache("/product/42".to_string(), "Product 42")
+        .await
+        .unwrap();
+    first
+        .cache_with_tags("/featured".to_string(), "Featured", ["product:42"])
+        .await
+        .unwrap();
+    first
+        .cache("/product/43".to_string(), "Product 43")
+        .await
+        .unwrap();
+    drop(first);
+
+    // A new server reads the routes from disk into its memory cache along with their tags
+    let second = renderer(&static_dir, false);
+    assert!(second.get("/product/42").await.unwrap().is_some());
+    assert!(second.get("/featured").await.unwrap().is_some());
+    assert!(second.get("/product/43").await.unwrap().is_some());
+
+    second.invalidate_tag("product:42").await;
+    assert!(second.get("/product/42").await.unwrap().is_none());
+    assert!(second.get("/featured").await.unwrap().is_none());
+    assert!(second.get("/product/43").await.unwrap().is_some());
+
+    // Tags are not stored in the static directory where they would be served
+    let tags_dir = static_dir.with_extension("tags");
+    assert!(tags_dir.exists());
+    assert!(!static_dir.join(".tags").exists());
+
+    // Routes are removed from the tag index when they are invalidated
+    second.invalidate("/product/43").await;
+    assert_eq!(tags_dir.join("tags").read_dir().unwrap().count(), 0);
+
+    std::fs::remove_dir_all(&static_dir).unwrap();
+    std::fs::remove_dir_all(&tags_dir).unwrap();
+}
@@ -0,0 +1,24 @@
+use dioxus::prelude::*;
+use dioxus_liveview::{ActixRouter, LiveviewRouter};
+
+fn app() -> Element {
+    let mut num = use_signal(|| 0);
+
+    rsx! {
+        div {
+            "hello actix! {num}"
+            button { onclick: move |_| num += 1, "Increment" }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dioxus::logger::initialize_default();
+
+    let addr: std::net::SocketAddr = ([127, 0, 0, 1], 3030).into();
+
+    println!("Listening on http://{addr}");
+
+    ActixRouter::new().with_app("/", app).start(addr).await;
+}
@@ -0,0 +1,260 @@
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter};
+use actix_web::{
+    web::{self, Payload, ServiceConfig},
+    App, HttpRequest, HttpResponse, HttpServer,
+};
+use actix_ws::{AggregatedMessage, Session};
+use dioxus_core::prelude::VirtualDom;
+use futures_util::{Sink, Stream, StreamExt};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// The largest message the client can send. File uploads are sent over the socket, so this matches the default of
+/// axum.
+const MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+/// Convert an actix-ws session into a `LiveViewSocket`.
+///
+/// The message stream of actix is tied to the thread of the actix worker, so this spawns a task on the current actix
+/// runtime that forwards messages from the client to the socket.
+///
+/// ```rust, no_run
+/// use actix_web::{web, HttpRequest, HttpResponse};
+/// use dioxus::prelude::*;
+/// use dioxus_liveview::{actix_socket, LiveViewPool};
+///
+/// async fn ws(
+///     req: HttpRequest,
+///     body: web::Payload,
+///     pool: web::Data<LiveViewPool>,
+/// ) -> actix_web::Result<HttpResponse> {
+///     let (response, session, stream) = actix_ws::handle(&req, body)?;
+///     let socket = actix_socket(session, stream);
+///     let pool = pool.get_ref().clone();
+///     tokio::spawn(async move { _ = pool.launch(socket, app).await });
+///     Ok(response)
+/// }
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from actix!" }
+/// }
+/// ```
+pub fn actix_socket(session: Session, stream: actix_ws::MessageStream) -> impl LiveViewSocket {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut pong_session = session.clone();
+    actix_web::rt::spawn(async move {
+        let mut stream = stream
+            .max_frame_size(MAX_MESSAGE_SIZE)
+            .aggregate_continuations()
+            .max_continuation_size(MAX_MESSAGE_SIZE);
+        loop {
+            let message = tokio::select! {
+                message = stream.next() => message,
+                // Stop reading once the liveview session drops the socket
+                _ = tx.closed() => break,
+            };
+            let message = match message {
+                Some(Ok(AggregatedMessage::Text(text))) => Ok(text.as_bytes().to_vec()),
+                Some(Ok(AggregatedMessage::Binary(bytes))) => Ok(bytes.to_vec()),
+                Some(Ok(AggregatedMessage::Ping(bytes))) => {
+                    if pong_session.pong(&bytes).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                Some(Ok(AggregatedMessage::Pong(_))) => continue,
+                Some(Ok(AggregatedMessage::Close(_))) | None => break,
+                Some(Err(_)) => Err(LiveViewError::SendingFailed),
+            };
+            if tx.send(message).is_err() {
+                break;
+            }
+        }
+        _ = pong_session.close(None).await;
+    });
+
+    let sink = futures_util::sink::unfold(session, |mut session, message: Vec<u8>| async move {
+        session
+            .binary(message)
+            .await
+            .map_err(|_| LiveViewError::SendingFailed)?;
+        Ok::<_, LiveViewError>(session)
+    });
+
+    Duplex {
+        stream: UnboundedReceiverStream::new(rx),
+        sink: Box::pin(sink),
+    }
+}
+
+/// A socket made from separate halves
+struct Duplex<St, Si> {
+    stream: St,
+    sink: Si,
+}
+
+impl<St: Stream + Unpin, Si: Unpin> Stream for Duplex<St, Si> {
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+impl<St: Unpin, Si: Sink<Vec<u8>> + Unpin> Sink<Vec<u8>> for Duplex<St, Si> {
+    type Error = Si::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.sink).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        Pin::new(&mut self.sink).start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.sink).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.sink).poll_close(cx)
+    }
+}
+
+/// A LiveView router for actix-web.
+///
+/// Actix builds a new [`App`] on every worker thread, so this router collects the liveview routes and adds them to
+/// each app with [`ActixRouter::configure`].
+///
+/// ```rust, no_run
+/// use actix_web::{App, HttpServer};
+/// use dioxus::prelude::*;
+/// use dioxus_liveview::{ActixRouter, LiveviewRouter};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from actix!" }
+/// }
+///
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let liveview = ActixRouter::new().with_app("/", app);
+///     HttpServer::new(move || App::new().configure(|cfg| liveview.configure(cfg)))
+///         .bind(("127.0.0.1", 3030))?
+///         .run()
+///         .await
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct ActixRouter {
+    pool: LiveViewPool,
+    routes: Vec<ActixRoute>,
+}
+
+#[derive(Clone)]
+struct ActixRoute {
+    route: String,
+    ws_path: String,
+    app: Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+}
+
+impl ActixRouter {
+    /// Create a new router with no routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a custom pool to run the sessions of the router
+    pub fn with_pool(mut self, pool: LiveViewPool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Add the liveview routes to an actix app
+    pub fn configure(&self, cfg: &mut ServiceConfig) {
+        for route in &self.routes {
+            let ws_route = route.clone();
+            let pool = self.pool.clone();
+            cfg.route(
+                &route.ws_path,
+                web::get().to(move |req: HttpRequest, body: Payload| {
+                    let app = ws_route.app.clone();
+                    let pool = pool.clone();
+                    async move {
+                        let (response, session, stream) = actix_ws::handle(&req, body)?;
+                        let socket = actix_socket(session, stream);
+                        actix_web::rt::spawn(async move {
+                            _ = pool.launch_virtualdom(socket, move || app()).await;
+                        });
+                        Ok::<_, actix_web::Error>(response)
+                    }
+                }),
+            );
+
+            let page = |route: ActixRoute, pool: LiveViewPool| {
+                move || {
+                    let route = route.clone();
+                    let pool = pool.clone();
+                    async move {
+                        let app = route.app;
+                        let page =
+                            super::render_index_page(&pool, &route.ws_path, move || app()).await;
+                        HttpResponse::Ok()
+                            .content_type("text/html; charset=utf-8")
+                            .body(page)
+                    }
+                }
+            };
+            // Serve the page on the route and every path under it
+            let base = route.route.trim_end_matches('/');
+            cfg.route(
+                if base.is_empty() { "/" } else { base },
+                web::get().to(page(route.clone(), self.pool.clone())),
+            );
+            cfg.route(
+                &format!("{base}/{{route:.*}}"),
+                web::get().to(page(route.clone(), self.pool.clone())),
+            );
+        }
+    }
+}
+
+impl LiveviewRouter for ActixRouter {
+    fn create_default_liveview_router() -> Self {
+        Self::new()
+    }
+
+    fn with_virtual_dom(
+        mut self,
+        route: &str,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push(ActixRoute {
+            route: format!("/{}", route.trim_matches('/')),
+            ws_path: super::ws_path(route),
+            app: Arc::new(app),
+        });
+        self
+    }
+
+    async fn start(self, address: impl Into<SocketAddr>) {
+        let server = HttpServer::new(move || {
+            let router = self.clone();
+            App::new().configure(move |cfg| router.configure(cfg))
+        })
+        .bind(address.into());
+        let result = match server {
+            Ok(server) => server.run().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to start actix server: {}", err);
+        }
+    }
+}
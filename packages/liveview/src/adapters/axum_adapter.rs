@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{interpreter_glue, LiveViewError, LiveViewSocket, LiveviewRouter};
+use crate::{LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter};
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -43,54 +43,63 @@ impl LiveviewRouter for Router {
         route: &str,
         app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
     ) -> Self {
-        let view = crate::LiveViewPool::new();
+        axum_route_with_pool(self, LiveViewPool::new(), route, app)
+    }
 
-        let ws_path = format!("{}/ws", route.trim_start_matches('/'));
-        let title = crate::app_title();
+    async fn start(self, address: impl Into<std::net::SocketAddr>) {
+        let listener = tokio::net::TcpListener::bind(address.into()).await.unwrap();
+        if let Err(err) = axum::serve(listener, self.into_make_service()).await {
+            eprintln!("Failed to start axum server: {}", err);
+        }
+    }
+}
 
-        let index_page_with_glue = move |glue: &str| {
-            Html(format!(
-                r#"
-        <!DOCTYPE html>
-        <html>
-            <head><title>{title}</title></head>
-            <body><div id="main"></div></body>
-            {glue}
-        </html>
-        "#,
-            ))
-        };
+/// Add a liveview route to an axum router that runs its sessions in a custom pool.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_liveview::{axum_route_with_pool, LiveViewPool};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello world" }
+/// }
+///
+/// let pool = LiveViewPool::new().pre_render(true);
+/// let router = axum_route_with_pool(axum::Router::new(), pool, "/", || VirtualDom::new(app));
+/// ```
+pub fn axum_route_with_pool(
+    router: Router,
+    pool: LiveViewPool,
+    route: &str,
+    app: impl Fn() -> dioxus_core::prelude::VirtualDom + Send + Sync + 'static,
+) -> Router {
+    let ws_path = super::ws_path(route);
 
-        let app = Arc::new(app);
-        // Add an extra catch all segment to the route
-        let mut route = route.trim_matches('/').to_string();
-        if route.is_empty() {
-            route = "/*route".to_string();
-        } else {
-            route = format!("/{route}/*route");
-        }
+    let app = Arc::new(app);
+    // Serve the page on the route and add an extra catch all segment for every path under it
+    let route = format!("/{}", route.trim_matches('/'));
+    let catch_all = format!("{}/*route", route.trim_end_matches('/'));
+    let page = {
+        let pool = pool.clone();
+        let app = app.clone();
+        let ws_path = ws_path.clone();
+        get(
+            move || async move { Html(super::render_index_page(&pool, &ws_path, move || app()).await) },
+        )
+    };
 
-        self.route(
+    router
+        .route(
             &ws_path,
             get(move |ws: WebSocketUpgrade| async move {
                 let app = app.clone();
                 ws.on_upgrade(move |socket| async move {
-                    _ = view
+                    _ = pool
                         .launch_virtualdom(axum_socket(socket), move || app())
                         .await;
                 })
             }),
         )
-        .route(
-            &route,
-            get(move || async move { index_page_with_glue(&interpreter_glue(&ws_path)) }),
-        )
-    }
-
-    async fn start(self, address: impl Into<std::net::SocketAddr>) {
-        let listener = tokio::net::TcpListener::bind(address.into()).await.unwrap();
-        if let Err(err) = axum::serve(listener, self.into_make_service()).await {
-            eprintln!("Failed to start axum server: {}", err);
-        }
-    }
+        .route(&route, page.clone())
+        .route(&catch_all, page)
 }
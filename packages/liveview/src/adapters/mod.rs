@@ -7,6 +7,16 @@ pub mod axum_adapter;
 #[cfg(feature = "axum")]
 pub use axum_adapter::*;
 
+#[cfg(feature = "actix")]
+pub mod actix_adapter;
+#[cfg(feature = "actix")]
+pub use actix_adapter::*;
+
+#[cfg(feature = "tower")]
+pub mod tower_adapter;
+#[cfg(feature = "tower")]
+pub use tower_adapter::*;
+
 /// A trait for servers that can be used to host a LiveView app.
 pub trait LiveviewRouter {
     /// Create a new router.
@@ -30,3 +40,48 @@ pub trait LiveviewRouter {
     /// Start the server on an address.
     fn start(self, address: impl Into<std::net::SocketAddr>) -> impl Future<Output = ()>;
 }
+
+/// Get the path of the websocket endpoint for a liveview route
+#[allow(unused)]
+pub(crate) fn ws_path(route: &str) -> String {
+    match route.trim_matches('/') {
+        "" => "/ws".to_string(),
+        route => format!("/{route}/ws"),
+    }
+}
+
+/// Render the page that loads the interpreter glue and connects to the websocket endpoint. If the app was
+/// pre-rendered, the client hydrates it.
+#[allow(unused)]
+pub(crate) fn index_page(ws_path: &str, pre_rendered: Option<&str>) -> String {
+    let title = crate::app_title();
+    let glue = crate::interpreter_glue(ws_path);
+    let main = match pre_rendered {
+        Some(body) => format!(r#"<div id="main" data-dioxus-hydrate>{body}</div>"#),
+        None => r#"<div id="main"></div>"#.to_string(),
+    };
+    format!(
+        r#"
+        <!DOCTYPE html>
+        <html>
+            <head><title>{title}</title></head>
+            <body>{main}</body>
+            {glue}
+        </html>
+        "#,
+    )
+}
+
+/// Render the page for a route, pre-rendering the app if the pool is configured to
+#[allow(unused)]
+pub(crate) async fn render_index_page(
+    pool: &crate::LiveViewPool,
+    ws_path: &str,
+    make_app: impl FnOnce() -> VirtualDom + Send + 'static,
+) -> String {
+    let pre_rendered = match pool.pre_renders() {
+        true => pool.pre_render_virtualdom(make_app).await.ok(),
+        false => None,
+    };
+    index_page(ws_path, pre_rendered.as_deref())
+}
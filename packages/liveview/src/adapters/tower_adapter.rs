@@ -0,0 +1,238 @@
+use std::{
+    convert::Infallible,
+    future::ready,
+    net::SocketAddr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use crate::{LiveViewError, LiveViewPool, LiveViewSocket, LiveviewRouter};
+use dioxus_core::prelude::VirtualDom;
+use futures_util::{future::BoxFuture, FutureExt, SinkExt, StreamExt};
+use http::{header, HeaderValue, Method, Request, Response, StatusCode};
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper_util::rt::TokioIo;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+/// Convert a tungstenite WebSocket into a `LiveViewSocket`.
+///
+/// This works with any framework that can hand out the raw upgraded connection of a websocket request.
+pub fn tungstenite_socket<S>(ws: WebSocketStream<S>) -> impl LiveViewSocket
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    ws.filter_map(transform_rx)
+        .with(transform_tx)
+        .sink_map_err(|_| LiveViewError::SendingFailed)
+}
+
+async fn transform_rx(
+    message: Result<Message, tokio_tungstenite::tungstenite::Error>,
+) -> Option<Result<Vec<u8>, LiveViewError>> {
+    match message {
+        Ok(Message::Text(text)) => Some(Ok(text.into_bytes())),
+        Ok(Message::Binary(bytes)) => Some(Ok(bytes)),
+        // Tungstenite answers pings on its own
+        Ok(Message::Ping(_) | Message::Pong(_) | Message::Close(_) | Message::Frame(_)) => None,
+        Err(_) => Some(Err(LiveViewError::SendingFailed)),
+    }
+}
+
+async fn transform_tx(message: Vec<u8>) -> Result<Message, tokio_tungstenite::tungstenite::Error> {
+    Ok(Message::Binary(message))
+}
+
+/// A LiveView router that implements [`tower::Service`](tower::Service).
+///
+/// The service serves the page with the interpreter glue and upgrades requests to the websocket endpoint with hyper.
+/// It can be served on its own with [`LiveviewRouter::start`] or [`TowerRouter::serve`], or mounted in any framework
+/// built on tower and hyper.
+///
+/// ```rust, no_run
+/// use dioxus::prelude::*;
+/// use dioxus_liveview::{LiveviewRouter, TowerRouter};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello from tower!" }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let listener = tokio::net::TcpListener::bind("127.0.0.1:3030").await.unwrap();
+///     TowerRouter::new().with_app("/", app).serve(listener).await.unwrap();
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct TowerRouter {
+    pool: LiveViewPool,
+    routes: Vec<TowerRoute>,
+}
+
+#[derive(Clone)]
+struct TowerRoute {
+    route: String,
+    ws_path: String,
+    app: Arc<dyn Fn() -> VirtualDom + Send + Sync>,
+}
+
+impl TowerRouter {
+    /// Create a new router with no routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a custom pool to run the sessions of the router
+    pub fn with_pool(mut self, pool: LiveViewPool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Serve the router on a listener with hyper
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let service = hyper_util::service::TowerToHyperService::new(self.clone());
+            tokio::spawn(async move {
+                _ = hyper::server::conn::http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .with_upgrades()
+                    .await;
+            });
+        }
+    }
+
+    fn respond<B>(&self, mut request: Request<B>) -> BoxFuture<'static, Response<Full<Bytes>>> {
+        if request.method() != Method::GET {
+            return ready(status(StatusCode::METHOD_NOT_ALLOWED)).boxed();
+        }
+        let path = request.uri().path();
+
+        if let Some(route) = self.routes.iter().find(|route| route.ws_path == path) {
+            let Some(accept) = websocket_accept_key(&request) else {
+                return ready(status(StatusCode::BAD_REQUEST)).boxed();
+            };
+            let app = route.app.clone();
+            let pool = self.pool.clone();
+            let upgrade = hyper::upgrade::on(&mut request);
+            tokio::spawn(async move {
+                let Ok(upgraded) = upgrade.await else {
+                    return;
+                };
+                let ws =
+                    WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None)
+                        .await;
+                _ = pool
+                    .launch_virtualdom(tungstenite_socket(ws), move || app())
+                    .await;
+            });
+
+            let mut response = status(StatusCode::SWITCHING_PROTOCOLS);
+            let headers = response.headers_mut();
+            headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+            headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+            headers.insert(header::SEC_WEBSOCKET_ACCEPT, accept);
+            return ready(response).boxed();
+        }
+
+        let page = self.routes.iter().find(|route| {
+            let base = route.route.trim_end_matches('/');
+            path == route.route || path.starts_with(&format!("{base}/"))
+        });
+        match page {
+            Some(route) => {
+                let pool = self.pool.clone();
+                let route = route.clone();
+                async move {
+                    let app = route.app;
+                    let page = super::render_index_page(&pool, &route.ws_path, move || app()).await;
+                    let mut response = Response::new(Full::from(page));
+                    response.headers_mut().insert(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("text/html; charset=utf-8"),
+                    );
+                    response
+                }
+                .boxed()
+            }
+            None => ready(status(StatusCode::NOT_FOUND)).boxed(),
+        }
+    }
+}
+
+/// Get the `Sec-WebSocket-Accept` header for a websocket upgrade request, or `None` if this isn't one
+fn websocket_accept_key<B>(request: &Request<B>) -> Option<HeaderValue> {
+    let headers = request.headers();
+    let has_token = |name: header::HeaderName, token: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|value| value.trim().eq_ignore_ascii_case(token))
+    };
+    if !has_token(header::CONNECTION, "upgrade") || !has_token(header::UPGRADE, "websocket") {
+        return None;
+    }
+    if headers.get(header::SEC_WEBSOCKET_VERSION)? != "13" {
+        return None;
+    }
+    let key = headers.get(header::SEC_WEBSOCKET_KEY)?;
+    HeaderValue::from_str(&derive_accept_key(key.as_bytes())).ok()
+}
+
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = status;
+    response
+}
+
+impl<B> tower::Service<Request<B>> for TowerRouter {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        self.respond(request).map(Ok).boxed()
+    }
+}
+
+impl LiveviewRouter for TowerRouter {
+    fn create_default_liveview_router() -> Self {
+        Self::new()
+    }
+
+    fn with_virtual_dom(
+        mut self,
+        route: &str,
+        app: impl Fn() -> VirtualDom + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push(TowerRoute {
+            route: format!("/{}", route.trim_matches('/')),
+            ws_path: super::ws_path(route),
+            app: Arc::new(app),
+        });
+        self
+    }
+
+    async fn start(self, address: impl Into<SocketAddr>) {
+        let result = match TcpListener::bind(address.into()).await {
+            Ok(listener) => self.serve(listener).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to start tower server: {}", err);
+        }
+    }
+}
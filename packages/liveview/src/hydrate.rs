@@ -0,0 +1,18 @@
+//! Hydrating a page that was pre-rendered on the server.
+//!
+//! The page is rendered with `dioxus-ssr` from its own VirtualDom. When the client connects, the session rebuilds its
+//! VirtualDom without creating any nodes and sends the client the element ids of the nodes the server rendered, in the
+//! order `dioxus-ssr` numbered them. The interpreter then attaches those ids and listeners to the existing dom, just
+//! like `dioxus-web` does when it hydrates a fullstack page.
+
+use crate::{query::QueryEngine, session::provide_root_context};
+use dioxus_core::prelude::*;
+
+/// Render the first frame of an app to html with the hydration markers the client needs to hydrate it
+pub(crate) fn pre_render(mut vdom: VirtualDom) -> String {
+    // The app gets the same context as in a session so it renders the same way. There is no client to run queries yet.
+    let (query_tx, _) = tokio::sync::mpsc::unbounded_channel();
+    provide_root_context(&vdom, &QueryEngine::new(query_tx));
+    vdom.rebuild_in_place();
+    dioxus_ssr::pre_render(&vdom)
+}
@@ -7,10 +7,14 @@ mod adapters;
 pub use adapters::*;
 
 mod element;
+mod hydrate;
+mod metrics;
 pub mod pool;
 mod query;
+mod session;
 use dioxus_interpreter_js::NATIVE_JS;
 use futures_util::{SinkExt, StreamExt};
+pub use metrics::ConnectionMetrics;
 pub use pool::*;
 mod config;
 mod document;
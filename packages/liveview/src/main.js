@@ -1,5 +1,22 @@
 const intercept_link_redirects = false;
 
+// Edit frames can be compressed with raw deflate if the browser can decompress them
+const supportsCompression = (() => {
+  try {
+    new DecompressionStream("deflate-raw");
+    return true;
+  } catch {
+    return false;
+  }
+})();
+
+async function inflate(data) {
+  const stream = new Blob([data])
+    .stream()
+    .pipeThrough(new DecompressionStream("deflate-raw"));
+  return await new Response(stream).arrayBuffer();
+}
+
 function main() {
   let root = window.document.getElementById("main");
   if (root != null) {
@@ -13,6 +30,23 @@ class IPC {
     window.interpreter.initialize(root);
     window.interpreter.liveview = true;
     window.interpreter.ipc = this;
+    this.root = root;
+
+    // If the server pre-rendered the page, we hydrate the nodes that are already there instead of building them again
+    this.hydrate = root.hasAttribute("data-dioxus-hydrate");
+
+    // The token of the session on the server. If the socket is closed, we reconnect with it to resume the session
+    this.session = null;
+    // The number of edit frames we applied. The server uses it to replay the edits we missed while reconnecting
+    this.editsApplied = 0;
+    this.reconnectAttempts = 0;
+    // Messages are handled one after another because decompressing edit frames is asynchronous
+    this.pending = Promise.resolve();
+
+    this.connect();
+  }
+
+  connect() {
     const ws = new WebSocket(WS_ADDR);
     ws.binaryType = "arraybuffer";
 
@@ -20,27 +54,71 @@ class IPC {
       ws.send("__ping__");
     }
 
+    let pingInterval = null;
+
     ws.onopen = () => {
+      this.reconnectAttempts = 0;
       // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      pingInterval = setInterval(ping, 30000);
+      // Wait for the frames of the last connection to be applied so the edit count is up to date
+      this.pending = this.pending.then(() => {
+        if (this.session != null && this.hydrate) {
+          // The connection dropped before the page was hydrated. Replaying edits can't fix that, so start over.
+          window.location.reload();
+        } else if (this.session != null) {
+          ws.send(
+            window.interpreter.serializeIpcMessage("resume", {
+              token: this.session,
+              edits: this.editsApplied,
+              compression: supportsCompression,
+            })
+          );
+        } else {
+          ws.send(
+            window.interpreter.serializeIpcMessage("initialize", {
+              compression: supportsCompression,
+              hydrate: this.hydrate,
+            })
+          );
+        }
+      });
     };
 
-    ws.onerror = (err) => {
-      // todo: retry the connection
+    ws.onclose = () => {
+      clearInterval(pingInterval);
+      // Without a session there is nothing to resume
+      if (this.session == null) {
+        return;
+      }
+      // Back off exponentially up to 10 seconds between attempts
+      const delay = Math.min(10000, 250 * 2 ** this.reconnectAttempts);
+      this.reconnectAttempts += 1;
+      setTimeout(() => this.connect(), delay);
     };
 
     ws.onmessage = (message) => {
-      const u8view = new Uint8Array(message.data);
-      const binaryFrame = u8view[0] == 1;
-      const messageData = message.data.slice(1);
-      // The first byte tells the shim if this is a binary of text frame
-      if (binaryFrame) {
-        // binary frame
-        window.interpreter.run_from_bytes(messageData);
-      } else {
-        // text frame
+      this.pending = this.pending
+        .then(() => this.handleMessage(message.data))
+        .catch((err) => console.error(err));
+    };
 
+    this.ws = ws;
+  }
+
+  async handleMessage(data) {
+    const u8view = new Uint8Array(data);
+    const messageData = data.slice(1);
+    // The first byte tells the shim if this is a text frame, an edit frame or a compressed edit frame
+    switch (u8view[0]) {
+      case 1:
+        window.interpreter.run_from_bytes(messageData);
+        this.editsApplied += 1;
+        break;
+      case 2:
+        window.interpreter.run_from_bytes(await inflate(messageData));
+        this.editsApplied += 1;
+        break;
+      default: {
         let decoder = new TextDecoder("utf-8");
 
         // Using decode method to get string output
@@ -52,16 +130,34 @@ class IPC {
             case "query":
               Function("Eval", `"use strict";${event.data};`)();
               break;
+            case "hydrate":
+              window.interpreter.hydrate(event.data, [this.root]);
+              this.hydrate = false;
+              break;
+            case "rebuild":
+              // The server couldn't hydrate the pre-rendered page, so it sends the edits to build it from scratch
+              this.root.replaceChildren();
+              this.hydrate = false;
+              break;
+            case "session":
+              this.session = event.data;
+              break;
+            case "expired":
+              // The server dropped our session, so the page is out of sync. Start over with a new session.
+              this.session = null;
+              window.location.reload();
+              break;
           }
         }
       }
-    };
-
-    this.ws = ws;
+    }
   }
 
   postMessage(msg) {
-    this.ws.send(msg);
+    // Events that happen while we are reconnecting can't be delivered
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
 
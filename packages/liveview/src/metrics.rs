@@ -0,0 +1,133 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// A report of the traffic of a single LiveView session, passed to the hook set with
+/// [`LiveViewPool::on_metrics`](crate::LiveViewPool::on_metrics).
+///
+/// Totals count everything since the session started. Rates and latencies only cover the time since the last report.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ConnectionMetrics {
+    /// A number that identifies the session. It stays the same when the client reconnects to the session.
+    pub session: u64,
+    /// The total number of bytes sent to the client, after compression
+    pub bytes_sent: u64,
+    /// The total number of bytes sent to the client, before compression
+    pub uncompressed_bytes_sent: u64,
+    /// The total number of edit frames sent to the client
+    pub edit_frames_sent: u64,
+    /// The number of edit frames sent per second since the last report
+    pub edits_per_second: f64,
+    /// The total number of events the client sent
+    pub events_handled: u64,
+    /// The average time between receiving an event and sending the edits it caused since the last report
+    pub average_event_latency: Option<Duration>,
+    /// The longest time between receiving an event and sending the edits it caused since the last report
+    pub max_event_latency: Option<Duration>,
+    /// If this is the last report because the session ended
+    pub closed: bool,
+}
+
+/// The callback for metrics reports and how often it is called
+#[derive(Clone)]
+pub(crate) struct MetricsHook {
+    pub(crate) interval: Duration,
+    pub(crate) callback: Arc<dyn Fn(&ConnectionMetrics) + Send + Sync>,
+}
+
+/// Tracks the traffic of a session
+pub(crate) struct Metrics {
+    report: ConnectionMetrics,
+    last_report: Instant,
+    /// Edit frames sent since the last report
+    interval_edit_frames: u64,
+    /// Latency of the events that were flushed since the last report
+    interval_latency_total: Duration,
+    interval_latency_count: u32,
+    /// When the events that haven't been flushed yet were received
+    pending_events: Vec<Instant>,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        static NEXT_SESSION: AtomicU64 = AtomicU64::new(0);
+        Self {
+            report: ConnectionMetrics {
+                session: NEXT_SESSION.fetch_add(1, Ordering::Relaxed),
+                bytes_sent: 0,
+                uncompressed_bytes_sent: 0,
+                edit_frames_sent: 0,
+                edits_per_second: 0.0,
+                events_handled: 0,
+                average_event_latency: None,
+                max_event_latency: None,
+                closed: false,
+            },
+            last_report: Instant::now(),
+            interval_edit_frames: 0,
+            interval_latency_total: Duration::ZERO,
+            interval_latency_count: 0,
+            pending_events: Vec::new(),
+        }
+    }
+
+    /// Record a frame that was sent to the client
+    pub(crate) fn frame_sent(&mut self, uncompressed: usize, sent: usize) {
+        self.report.bytes_sent += sent as u64;
+        self.report.uncompressed_bytes_sent += uncompressed as u64;
+    }
+
+    /// Record an edit frame that was sent to the client
+    pub(crate) fn edits_sent(&mut self) {
+        self.report.edit_frames_sent += 1;
+        self.interval_edit_frames += 1;
+    }
+
+    /// Record an event from the client
+    pub(crate) fn event_received(&mut self) {
+        self.report.events_handled += 1;
+        self.pending_events.push(Instant::now());
+    }
+
+    /// Record that the edits of every pending event were sent
+    pub(crate) fn events_flushed(&mut self) {
+        let now = Instant::now();
+        for received in self.pending_events.drain(..) {
+            let latency = now - received;
+            self.interval_latency_total += latency;
+            self.interval_latency_count += 1;
+            self.report.max_event_latency = self.report.max_event_latency.max(Some(latency));
+        }
+    }
+
+    /// Create a report and start a new interval
+    pub(crate) fn report(&mut self, closed: bool) -> ConnectionMetrics {
+        let now = Instant::now();
+        let elapsed = (now - self.last_report).as_secs_f64();
+        self.report.edits_per_second = match elapsed > 0.0 {
+            true => self.interval_edit_frames as f64 / elapsed,
+            false => 0.0,
+        };
+        self.report.average_event_latency = (self.interval_latency_count > 0)
+            .then(|| self.interval_latency_total / self.interval_latency_count);
+        self.report.closed = closed;
+        let report = self.report.clone();
+
+        self.last_report = now;
+        self.interval_edit_frames = 0;
+        self.interval_latency_total = Duration::ZERO;
+        self.interval_latency_count = 0;
+        self.report.max_event_latency = None;
+        report
+    }
+
+    /// When the next report is due
+    pub(crate) fn next_report(&self, interval: Duration) -> Instant {
+        self.last_report + interval
+    }
+}
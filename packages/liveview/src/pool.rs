@@ -1,21 +1,41 @@
 use crate::{
-    document::init_document,
-    element::LiveviewElement,
     events::SerializedHtmlEventConverter,
-    query::{QueryEngine, QueryResult},
+    metrics::{ConnectionMetrics, MetricsHook},
+    session::{ClientUpdate, Session, SessionOptions},
     LiveViewError,
 };
 use dioxus_core::prelude::*;
-use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
-use dioxus_interpreter_js::MutationState;
-use futures_util::{pin_mut, SinkExt, StreamExt};
-use serde::Serialize;
-use std::{any::Any, rc::Rc, time::Duration};
+use futures_util::{pin_mut, Sink, SinkExt, Stream, StreamExt};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
 use tokio_util::task::LocalPoolHandle;
 
+/// A pool of threads that run LiveView sessions.
+///
+/// When the socket of a session is closed, the pool keeps the VirtualDom alive for a grace period so the client can
+/// reconnect and pick up where it left off. The client glue from [`interpreter_glue`](crate::interpreter_glue)
+/// reconnects automatically and the pool replays the edits the client missed. If the session expired in the meantime,
+/// the client reloads the page to start a new one.
+///
+/// ```rust, no_run
+/// use dioxus_liveview::LiveViewPool;
+/// use std::time::Duration;
+///
+/// let pool = LiveViewPool::new()
+///     // Keep sessions for two minutes after the socket is closed
+///     .session_grace_period(Duration::from_secs(120))
+///     // But never keep more than 1000 disconnected sessions around
+///     .max_parked_sessions(1000);
+/// ```
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: SessionStore,
 }
 
 impl Default for LiveViewPool {
@@ -31,9 +51,120 @@ impl LiveViewPool {
 
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            sessions: SessionStore::default(),
         }
     }
 
+    /// Set how long a session is kept alive after its socket is closed. Defaults to 30 seconds.
+    ///
+    /// A grace period of zero disables session resumption: the VirtualDom is dropped as soon as the socket is closed.
+    pub fn session_grace_period(self, grace_period: Duration) -> Self {
+        self.sessions.inner.lock().unwrap().config.grace_period = grace_period;
+        self
+    }
+
+    /// Set the maximum number of sessions that are kept alive without a socket. When the limit is reached, the session
+    /// that was disconnected the longest is dropped. Defaults to 100.
+    pub fn max_parked_sessions(self, max_parked_sessions: usize) -> Self {
+        self.sessions.inner.lock().unwrap().config.max_parked = max_parked_sessions;
+        self
+    }
+
+    /// Compress large edit frames for clients that support it. Defaults to false.
+    ///
+    /// Frames are compressed with raw deflate, which the client glue decompresses with the `DecompressionStream` API
+    /// of the browser. Clients that don't support it keep getting uncompressed frames.
+    pub fn compression(self, enabled: bool) -> Self {
+        self.sessions.inner.lock().unwrap().config.compression = enabled;
+        self
+    }
+
+    /// Collect the edits of every render in a window and send them to the client together. Defaults to zero, which
+    /// sends the edits of every render right away.
+    ///
+    /// This saves bandwidth and work on the client for apps that render many times a second, like dashboards, at the
+    /// cost of up to `window` of extra latency.
+    pub fn edit_coalescing(self, window: Duration) -> Self {
+        self.sessions.inner.lock().unwrap().config.coalesce = window;
+        self
+    }
+
+    /// Call a function with the [`ConnectionMetrics`] of every session once per `interval`, and once more when the
+    /// session ends.
+    ///
+    /// ```rust, no_run
+    /// use dioxus_liveview::LiveViewPool;
+    /// use std::time::Duration;
+    ///
+    /// let pool = LiveViewPool::new().on_metrics(Duration::from_secs(10), |metrics| {
+    ///     println!(
+    ///         "session {} sent {} bytes at {:.1} edits/s",
+    ///         metrics.session, metrics.bytes_sent, metrics.edits_per_second
+    ///     );
+    /// });
+    /// ```
+    pub fn on_metrics(
+        self,
+        interval: Duration,
+        callback: impl Fn(&ConnectionMetrics) + Send + Sync + 'static,
+    ) -> Self {
+        self.sessions.inner.lock().unwrap().config.metrics = Some(MetricsHook {
+            interval,
+            callback: Arc::new(callback),
+        });
+        self
+    }
+
+    /// Pre-render the first frame of the app into the page the routers serve. Defaults to false.
+    ///
+    /// The page shows the content of the app before the socket connects and crawlers can read it. The client then
+    /// hydrates the page instead of building it again. The app is rendered once for the page and once more for the
+    /// session, so the first render has to be the same every time for hydration to work.
+    pub fn pre_render(self, enabled: bool) -> Self {
+        self.sessions.inner.lock().unwrap().config.pre_render = enabled;
+        self
+    }
+
+    /// If the routers pre-render the pages of this pool
+    pub(crate) fn pre_renders(&self) -> bool {
+        self.sessions.inner.lock().unwrap().config.pre_render
+    }
+
+    /// Render the first frame of the VirtualDom from `make_app` to html the client can hydrate.
+    ///
+    /// If you serve the page yourself, put the html in the root element and mark it with `data-dioxus-hydrate`.
+    /// The client glue hydrates the nodes when it connects:
+    ///
+    /// ```rust, no_run
+    /// # async fn page(pool: dioxus_liveview::LiveViewPool) {
+    /// use dioxus::prelude::*;
+    ///
+    /// fn app() -> Element {
+    ///     rsx! { "Hello world" }
+    /// }
+    ///
+    /// let body = pool.pre_render_virtualdom(|| VirtualDom::new(app)).await.unwrap();
+    /// let page = format!(
+    ///     r#"<div id="main" data-dioxus-hydrate>{body}</div>{glue}"#,
+    ///     glue = dioxus_liveview::interpreter_glue("/ws")
+    /// );
+    /// # }
+    /// ```
+    pub async fn pre_render_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
+        &self,
+        make_app: F,
+    ) -> Result<String, LiveViewError> {
+        self.pool
+            .spawn_pinned(move || async move { crate::hydrate::pre_render(make_app()) })
+            .await
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    /// Get the number of sessions that are waiting for their client to reconnect
+    pub fn parked_sessions(&self) -> usize {
+        self.sessions.inner.lock().unwrap().parked.len()
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -52,12 +183,48 @@ impl LiveViewPool {
             .await
     }
 
+    /// Serve a client over a socket. If the client is resuming a session that is still alive, the socket is handed
+    /// to that session and `make_app` is never called. Otherwise a new session is started with the VirtualDom from
+    /// `make_app`.
     pub async fn launch_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
+        let mut ws: BoxedSocket = Box::pin(ws);
+
+        // The client tells us if it wants a new session or to resume an old one before anything else
+        let handshake = match ws.next().await {
+            Some(Ok(message)) => serde_json::from_slice::<Handshake>(&message).ok(),
+            Some(Err(err)) => return Err(err),
+            None => return Ok(()),
+        };
+
+        let (compression, hydrate) = match handshake {
+            Some(Handshake::Resume {
+                token,
+                edits,
+                compression,
+            }) => {
+                if let Err(mut ws) = self.sessions.resume(&token, ws, edits, compression).await {
+                    // The session is gone, so the client needs to reload the page to get a new one
+                    ws.send(ClientUpdate::Expired.into_frame()).await?;
+                }
+                return Ok(());
+            }
+            Some(Handshake::Initialize {
+                compression,
+                hydrate,
+            }) => (compression, hydrate),
+            None => (false, false),
+        };
+
+        let sessions = self.sessions.clone();
+        match self
+            .pool
+            .spawn_pinned(move || run_session(make_app(), ws, sessions, compression, hydrate))
+            .await
+        {
             Ok(Ok(_)) => Ok(()),
             Ok(Err(e)) => Err(e),
             Err(_) => Err(LiveViewError::SendingFailed),
@@ -65,6 +232,238 @@ impl LiveViewPool {
     }
 }
 
+/// The first message the client sends over a new socket
+#[derive(serde::Deserialize)]
+#[serde(tag = "method", content = "params")]
+enum Handshake {
+    #[serde(rename = "initialize")]
+    Initialize {
+        /// If the client can decompress edit frames
+        #[serde(default)]
+        compression: bool,
+        /// If the page was pre-rendered, so the client only needs the element ids of the nodes it already has
+        #[serde(default)]
+        hydrate: bool,
+    },
+    #[serde(rename = "resume")]
+    Resume {
+        token: String,
+        /// The number of edit frames the client applied
+        edits: u64,
+        #[serde(default)]
+        compression: bool,
+    },
+}
+
+/// Run a session, keeping it alive while the client reconnects
+async fn run_session(
+    vdom: VirtualDom,
+    mut ws: BoxedSocket,
+    sessions: SessionStore,
+    compression: bool,
+    hydrate: bool,
+) -> Result<(), LiveViewError> {
+    let options = sessions.options();
+    let resumable = options.resumable;
+    let mut session = Session::new(vdom, options);
+    session.set_client_compression(compression);
+    if !resumable {
+        session.start(&mut ws, hydrate).await?;
+        return session.serve(&mut ws).await;
+    }
+
+    let token = SessionStore::new_token();
+    let mut result = match ws
+        .send(ClientUpdate::Session(token.clone()).into_frame())
+        .await
+    {
+        Ok(()) => session.start(&mut ws, hydrate).await,
+        Err(err) => Err(err),
+    };
+
+    loop {
+        if result.is_ok() {
+            result = session.serve(&mut ws).await;
+        }
+        drop(ws);
+
+        // Wait for the client to come back
+        let Some(resumed) = sessions.park(&token) else {
+            return result;
+        };
+        let grace_period = sessions.grace_period();
+        let request = tokio::select! {
+            request = resumed => request,
+            _ = tokio::time::sleep(grace_period) => {
+                sessions.remove(&token);
+                return result;
+            }
+        };
+        // If the sender was dropped, the session was evicted to make room for other sessions
+        let Ok(ResumeRequest {
+            socket: mut new_ws,
+            applied,
+            compression,
+            reply,
+        }) = request
+        else {
+            return result;
+        };
+
+        session.set_client_compression(compression);
+        match session.resume(&mut new_ws, applied).await {
+            Some(resumed) => {
+                _ = reply.send(Ok(()));
+                result = resumed;
+                ws = new_ws;
+            }
+            None => {
+                _ = reply.send(Err(new_ws));
+                return result;
+            }
+        }
+    }
+}
+
+/// A socket whose type was erased so it can be handed to a session on another thread
+type BoxedSocket = Pin<Box<dyn ErasedSocket>>;
+
+trait ErasedSocket:
+    Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+impl<S> ErasedSocket for S where
+    S: Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+/// A request from a client to resume a parked session
+struct ResumeRequest {
+    socket: BoxedSocket,
+    applied: u64,
+    compression: bool,
+    /// Sends the socket back if the session can't resynchronise the client
+    reply: oneshot::Sender<Result<(), BoxedSocket>>,
+}
+
+/// The sessions that are waiting for their client to reconnect
+#[derive(Clone, Default)]
+struct SessionStore {
+    inner: Arc<Mutex<SessionStoreInner>>,
+}
+
+struct SessionStoreInner {
+    config: SessionConfig,
+    parked: HashMap<String, ParkedSession>,
+}
+
+impl Default for SessionStoreInner {
+    fn default() -> Self {
+        Self {
+            config: SessionConfig {
+                grace_period: Duration::from_secs(30),
+                max_parked: 100,
+                compression: false,
+                coalesce: Duration::ZERO,
+                metrics: None,
+                pre_render: false,
+            },
+            parked: HashMap::new(),
+        }
+    }
+}
+
+struct SessionConfig {
+    grace_period: Duration,
+    max_parked: usize,
+    compression: bool,
+    coalesce: Duration,
+    metrics: Option<MetricsHook>,
+    pre_render: bool,
+}
+
+struct ParkedSession {
+    resume: oneshot::Sender<ResumeRequest>,
+    parked_at: Instant,
+}
+
+impl SessionStore {
+    fn new_token() -> String {
+        format!("{:032x}", rand::random::<u128>())
+    }
+
+    fn options(&self) -> SessionOptions {
+        let config = &self.inner.lock().unwrap().config;
+        SessionOptions {
+            resumable: config.grace_period > Duration::ZERO,
+            compression: config.compression,
+            coalesce: config.coalesce,
+            metrics: config.metrics.clone(),
+        }
+    }
+
+    fn grace_period(&self) -> Duration {
+        self.inner.lock().unwrap().config.grace_period
+    }
+
+    /// Park a session until the client reconnects. Returns `None` if sessions can't be parked.
+    fn park(&self, token: &str) -> Option<oneshot::Receiver<ResumeRequest>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.config.max_parked == 0 {
+            return None;
+        }
+        // Make room by dropping the sessions that were disconnected the longest
+        while inner.parked.len() >= inner.config.max_parked {
+            let oldest = inner
+                .parked
+                .iter()
+                .min_by_key(|(_, parked)| parked.parked_at)
+                .map(|(token, _)| token.clone())?;
+            inner.parked.remove(&oldest);
+        }
+        let (tx, rx) = oneshot::channel();
+        inner.parked.insert(
+            token.to_string(),
+            ParkedSession {
+                resume: tx,
+                parked_at: Instant::now(),
+            },
+        );
+        Some(rx)
+    }
+
+    fn remove(&self, token: &str) {
+        self.inner.lock().unwrap().parked.remove(token);
+    }
+
+    /// Hand a socket to a parked session. Returns the socket if the session doesn't exist or can't be resumed.
+    async fn resume(
+        &self,
+        token: &str,
+        socket: BoxedSocket,
+        applied: u64,
+        compression: bool,
+    ) -> Result<(), BoxedSocket> {
+        let Some(parked) = self.inner.lock().unwrap().parked.remove(token) else {
+            return Err(socket);
+        };
+        let (reply, accepted) = oneshot::channel();
+        let request = ResumeRequest {
+            socket,
+            applied,
+            compression,
+            reply,
+        };
+        if let Err(request) = parked.resume.send(request) {
+            return Err(request.socket);
+        }
+        // If the session ended before it answered, the socket was dropped with the request. The client will try to
+        // resume again and find that the session is gone.
+        accepted.await.unwrap_or(Ok(()))
+    }
+}
+
 /// A LiveViewSocket is a Sink and Stream of Strings that Dioxus uses to communicate with the client
 ///
 /// Most websockets from most HTTP frameworks can be converted into a LiveViewSocket using the appropriate adapter.
@@ -115,156 +514,15 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Bytes, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        if let Some(endpoint) = dioxus_cli_config::devserver_ws_endpoint() {
-            dioxus_devtools::connect(endpoint, move |template| _ = tx.send(template));
-        }
-        rx
-    };
-
-    let mut mutations = MutationState::default();
-
-    // Create the a proxy for query engine
-    let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
-    let query_engine = QueryEngine::new(query_tx);
-    vdom.runtime().on_scope(ScopeId::ROOT, || {
-        provide_context(query_engine.clone());
-        init_document();
-    });
+///
+/// Sessions started with this function end when the socket is closed. Use [`LiveViewPool`] to let clients resume
+/// their session after reconnecting.
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    let mut session = Session::new(vdom, SessionOptions::default());
 
-    // pin the futures so we can use select!
+    // pin the socket so we can use select!
     pin_mut!(ws);
 
-    if let Some(edits) = {
-        vdom.rebuild(&mut mutations);
-        take_edits(&mut mutations)
-    } {
-        // send the initial render to the client
-        ws.send(edits).await?;
-    }
-
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(Box<HtmlEvent>),
-        #[serde(rename = "query")]
-        Query(QueryResult),
-    }
-
-    loop {
-        #[cfg(all(feature = "devtools", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
-        #[cfg(not(all(feature = "devtools", debug_assertions)))]
-        let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
-
-        tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(text_frame("__pong__")).await?;
-                    }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    let event = if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        Event::new(
-                                            Rc::new(PlatformEventData::new(Box::new(element))) as Rc<dyn Any>,
-                                            evt.bubbles,
-                                        )
-                                    } else {
-                                        Event::new(
-                                            evt.data.into_any(),
-                                            evt.bubbles,
-                                        )
-                                    };
-                                    vdom.runtime().handle_event(
-                                        &evt.name,
-                                        event,
-                                        evt.element,
-                                    );
-                                }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
-                            }
-                        }
-                    }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {}
-                    None => return Ok(()),
-                }
-            }
-
-            // handle any new queries
-            Some(query) = query_rx.recv() => {
-                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
-            }
-
-            Some(msg) = hot_reload_wait => {
-                #[cfg(all(feature = "devtools", debug_assertions))]
-                match msg{
-                    dioxus_devtools::DevserverMsg::HotReload(msg)=> {
-                        dioxus_devtools::apply_changes(&vdom, &msg);
-                    }
-                    dioxus_devtools::DevserverMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
-                    dioxus_devtools::DevserverMsg::FullReloadCommand
-                    | dioxus_devtools::DevserverMsg::FullReloadStart
-                    | dioxus_devtools::DevserverMsg::FullReloadFailed => {
-                        // usually only web gets this message - what are we supposed to do?
-                        // Maybe we could just binary patch ourselves in place without losing window state?
-                    },
-                }
-                #[cfg(not(all(feature = "devtools", debug_assertions)))]
-                let () = msg;
-            }
-        }
-
-        // wait for suspense to resolve in a 10ms window
-        tokio::select! {
-            _ = tokio::time::sleep(Duration::from_millis(10)) => {}
-            _ = vdom.wait_for_suspense() => {}
-        }
-
-        // render the vdom
-        vdom.render_immediate(&mut mutations);
-
-        if let Some(edits) = take_edits(&mut mutations) {
-            ws.send(edits).await?;
-        }
-    }
-}
-
-fn text_frame(text: &str) -> Vec<u8> {
-    let mut bytes = vec![0];
-    bytes.extend(text.as_bytes());
-    bytes
-}
-
-fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
-    // Add an extra one at the beginning to tell the shim this is a binary frame
-    let mut bytes = vec![1];
-    mutations.write_memory_into(&mut bytes);
-    (bytes.len() > 1).then_some(bytes)
-}
-
-#[derive(Serialize)]
-#[serde(tag = "type", content = "data")]
-enum ClientUpdate {
-    #[serde(rename = "query")]
-    Query(String),
+    session.rebuild(&mut ws).await?;
+    session.serve(&mut ws).await
 }
@@ -0,0 +1,436 @@
+use crate::{
+    document::init_document,
+    element::LiveviewElement,
+    metrics::{Metrics, MetricsHook},
+    query::{QueryEngine, QueryResult},
+    LiveViewError,
+};
+use dioxus_core::{prelude::*, ElementId, HydrationIds};
+use dioxus_html::{EventData, HtmlEvent, PlatformEventData};
+use dioxus_interpreter_js::MutationState;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use std::{any::Any, collections::VecDeque, io::Write, rc::Rc, time::Duration};
+use tokio::{sync::mpsc::UnboundedReceiver, time::Instant};
+
+/// The number of edit frames a session keeps to resynchronise a client that reconnects
+const REPLAY_FRAMES: usize = 64;
+
+/// Edit frames smaller than this are sent uncompressed because compression wouldn't save much
+const COMPRESSION_THRESHOLD: usize = 512;
+
+/// How a session talks to its client
+#[derive(Clone, Default)]
+pub(crate) struct SessionOptions {
+    /// Keep the latest edit frames so they can be replayed to a client that reconnects
+    pub(crate) resumable: bool,
+    /// Compress edit frames for clients that support it
+    pub(crate) compression: bool,
+    /// How long to collect renders before sending their edits
+    pub(crate) coalesce: Duration,
+    pub(crate) metrics: Option<MetricsHook>,
+}
+
+/// A running VirtualDom and the state it needs to talk to a client. A session can outlive the socket it was created
+/// with, so the client can reconnect to it.
+pub(crate) struct Session {
+    vdom: VirtualDom,
+    mutations: MutationState,
+    query_engine: QueryEngine,
+    query_rx: UnboundedReceiver<String>,
+    #[cfg(all(feature = "devtools", debug_assertions))]
+    hot_reload_rx: UnboundedReceiver<dioxus_devtools::DevserverMsg>,
+    history: EditHistory,
+    options: SessionOptions,
+    /// If the current client can decompress edit frames
+    compress: bool,
+    metrics: Metrics,
+}
+
+impl Session {
+    pub(crate) fn new(vdom: VirtualDom, options: SessionOptions) -> Self {
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        let hot_reload_rx = {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            if let Some(endpoint) = dioxus_cli_config::devserver_ws_endpoint() {
+                dioxus_devtools::connect(endpoint, move |template| _ = tx.send(template));
+            }
+            rx
+        };
+
+        // Create the a proxy for query engine
+        let (query_tx, query_rx) = tokio::sync::mpsc::unbounded_channel();
+        let query_engine = QueryEngine::new(query_tx);
+        provide_root_context(&vdom, &query_engine);
+
+        Self {
+            vdom,
+            mutations: MutationState::default(),
+            query_engine,
+            query_rx,
+            #[cfg(all(feature = "devtools", debug_assertions))]
+            hot_reload_rx,
+            history: EditHistory::new(if options.resumable { REPLAY_FRAMES } else { 0 }),
+            options,
+            compress: false,
+            metrics: Metrics::new(),
+        }
+    }
+
+    /// Set if the client that is connected can decompress edit frames
+    pub(crate) fn set_client_compression(&mut self, supported: bool) {
+        self.compress = self.options.compression && supported;
+    }
+
+    /// Run the initial render and send it to the client
+    pub(crate) async fn rebuild<S>(&mut self, ws: &mut S) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        self.vdom.rebuild(&mut self.mutations);
+        self.flush(ws).await
+    }
+
+    /// Run the initial render, hydrating the page if the client pre-rendered it
+    pub(crate) async fn start<S>(&mut self, ws: &mut S, hydrate: bool) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        match hydrate {
+            true => self.hydrate(ws).await,
+            false => self.rebuild(ws).await,
+        }
+    }
+
+    /// Run the initial render over the nodes the client pre-rendered from the page. Instead of edits, the client gets
+    /// the element ids of the nodes it already has.
+    ///
+    /// If the page can't be hydrated, the client drops the pre-rendered nodes and gets the edits of a normal rebuild.
+    async fn hydrate<S>(&mut self, ws: &mut S) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        // The edits are kept apart until we know if the client needs them
+        let mut rebuild = MutationState::default();
+        self.vdom.rebuild(&mut rebuild);
+        let Some(hydration) = HydrationIds::collect(&self.vdom, self.vdom.base_scope(), |_| {})
+        else {
+            tracing::error!("Hydration failed. The VirtualDom was not fully mounted, rebuilding the page instead");
+            self.send(ws, ClientUpdate::Rebuild.into_frame()).await?;
+            self.mutations = rebuild;
+            return self.flush(ws).await;
+        };
+        self.send(ws, ClientUpdate::Hydrate(hydration.ids).into_frame())
+            .await?;
+        for id in hydration.mounted {
+            self.mounted(id, false);
+        }
+        Ok(())
+    }
+
+    /// Send the edit frames a reconnecting client missed. `applied` is the number of edit frames the client applied
+    /// before the connection dropped.
+    ///
+    /// Returns `None` without sending anything if the frames the client missed are no longer available.
+    pub(crate) async fn resume<S>(
+        &mut self,
+        ws: &mut S,
+        applied: u64,
+    ) -> Option<Result<(), LiveViewError>>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        let missed: Vec<_> = self.history.since(applied)?.cloned().collect();
+        for frame in missed {
+            let frame = self.encode(frame);
+            if let Err(err) = ws.feed(frame).await {
+                return Some(Err(err));
+            }
+        }
+        Some(ws.flush().await)
+    }
+
+    /// The primary event loop for the VirtualDom. This returns when the socket is closed or a message can't be sent.
+    pub(crate) async fn serve<S>(&mut self, ws: &mut S) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError>
+            + Stream<Item = Result<Vec<u8>, LiveViewError>>
+            + Unpin,
+    {
+        // desktop uses this wrapper struct thing around the actual event itself
+        // this is sorta driven by tao/wry
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(tag = "method", content = "params")]
+        enum IpcMessage {
+            #[serde(rename = "user_event")]
+            Event(Box<HtmlEvent>),
+            #[serde(rename = "query")]
+            Query(QueryResult),
+        }
+
+        // Send any edits that were collected before the client disconnected
+        self.flush(ws).await?;
+
+        // When the edits that are being collected should be sent
+        let mut flush_at: Option<Instant> = None;
+
+        loop {
+            #[cfg(all(feature = "devtools", debug_assertions))]
+            let hot_reload_wait = self.hot_reload_rx.recv();
+            #[cfg(not(all(feature = "devtools", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            let report_at = self.options.metrics.as_ref().map(|hook| {
+                Instant::from_std(
+                    self.metrics
+                        .next_report(hook.interval.max(Duration::from_millis(1))),
+                )
+            });
+
+            tokio::select! {
+                _ = tokio::time::sleep_until(flush_at.unwrap_or_else(Instant::now)), if flush_at.is_some() => {
+                    flush_at = None;
+                    self.flush(ws).await?;
+                    continue;
+                }
+
+                _ = tokio::time::sleep_until(report_at.unwrap_or_else(Instant::now)), if report_at.is_some() => {
+                    self.report(false);
+                    continue;
+                }
+
+                // poll any futures or suspense
+                _ = self.vdom.wait_for_work() => {}
+
+                evt = ws.next() => {
+                    match evt.as_ref().map(|o| o.as_deref()) {
+                        // respond with a pong every ping to keep the websocket alive
+                        Some(Ok(b"__ping__")) => {
+                            self.send(ws, text_frame("__pong__")).await?;
+                        }
+                        Some(Ok(evt)) => {
+                            if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
+                                match message {
+                                    IpcMessage::Event(evt) => {
+                                        self.metrics.event_received();
+                                        // Intercept the mounted event and insert a custom element type
+                                        if let EventData::Mounted = &evt.data {
+                                            self.mounted(evt.element, evt.bubbles);
+                                        } else {
+                                            self.vdom.runtime().handle_event(
+                                                &evt.name,
+                                                Event::new(evt.data.into_any(), evt.bubbles),
+                                                evt.element,
+                                            );
+                                        }
+                                    }
+                                    IpcMessage::Query(result) => {
+                                        self.query_engine.send(result);
+                                    },
+                                }
+                            }
+                        }
+                        // log this I guess? when would we get an error here?
+                        Some(Err(_e)) => {}
+                        None => return Ok(()),
+                    }
+                }
+
+                // handle any new queries
+                Some(query) = self.query_rx.recv() => {
+                    self.send(ws, ClientUpdate::Query(query).into_frame()).await?;
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "devtools", debug_assertions))]
+                    match msg{
+                        dioxus_devtools::DevserverMsg::HotReload(msg)=> {
+                            dioxus_devtools::apply_changes(&self.vdom, &msg);
+                        }
+                        dioxus_devtools::DevserverMsg::Shutdown => {
+                            std::process::exit(0);
+                        },
+                        dioxus_devtools::DevserverMsg::FullReloadCommand
+                        | dioxus_devtools::DevserverMsg::FullReloadStart
+                        | dioxus_devtools::DevserverMsg::FullReloadFailed => {
+                            // usually only web gets this message - what are we supposed to do?
+                            // Maybe we could just binary patch ourselves in place without losing window state?
+                        },
+                    }
+                    #[cfg(not(all(feature = "devtools", debug_assertions)))]
+                    let () = msg;
+                }
+            }
+
+            // wait for suspense to resolve in a 10ms window
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+                _ = self.vdom.wait_for_suspense() => {}
+            }
+
+            // render the vdom
+            self.vdom.render_immediate(&mut self.mutations);
+
+            // Keep collecting edits until the coalescing window is over
+            if self.options.coalesce.is_zero() {
+                self.flush(ws).await?;
+            } else if flush_at.is_none() {
+                flush_at = Some(Instant::now() + self.options.coalesce);
+            }
+        }
+    }
+
+    /// Send the mounted event for an element with a [`LiveviewElement`] the app can query
+    fn mounted(&mut self, element: ElementId, bubbles: bool) {
+        let element_data = LiveviewElement::new(element, self.query_engine.clone());
+        let event = Event::new(
+            Rc::new(PlatformEventData::new(Box::new(element_data))) as Rc<dyn Any>,
+            bubbles,
+        );
+        self.vdom.runtime().handle_event("mounted", event, element);
+    }
+
+    /// Send the pending edits to the client. The frame is recorded before it is sent so it can be replayed if sending
+    /// fails.
+    async fn flush<S>(&mut self, ws: &mut S) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        let result = match take_edits(&mut self.mutations) {
+            Some(edits) => {
+                self.history.push(&edits);
+                self.metrics.edits_sent();
+                let frame = self.encode(edits);
+                ws.send(frame).await
+            }
+            None => Ok(()),
+        };
+        self.metrics.events_flushed();
+        result
+    }
+
+    /// Send a frame that doesn't contain edits
+    async fn send<S>(&mut self, ws: &mut S, frame: Vec<u8>) -> Result<(), LiveViewError>
+    where
+        S: Sink<Vec<u8>, Error = LiveViewError> + Unpin,
+    {
+        self.metrics.frame_sent(frame.len(), frame.len());
+        ws.send(frame).await
+    }
+
+    /// Compress an edit frame if the client supports it and it is worth it
+    fn encode(&mut self, frame: Vec<u8>) -> Vec<u8> {
+        let uncompressed = frame.len();
+        let frame = match self.compress && uncompressed >= COMPRESSION_THRESHOLD {
+            true => compress(&frame).unwrap_or(frame),
+            false => frame,
+        };
+        self.metrics.frame_sent(uncompressed, frame.len());
+        frame
+    }
+
+    /// Pass the metrics of the session to the hook
+    fn report(&mut self, closed: bool) {
+        if let Some(hook) = &self.options.metrics {
+            (hook.callback)(&self.metrics.report(closed));
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.report(true);
+    }
+}
+
+/// Give the root of an app the liveview document, history and query engine
+pub(crate) fn provide_root_context(vdom: &VirtualDom, query_engine: &QueryEngine) {
+    vdom.runtime().on_scope(ScopeId::ROOT, || {
+        provide_context(query_engine.clone());
+        init_document();
+    });
+}
+
+/// Compress an edit frame with raw deflate. The first byte tells the shim the rest of the frame is compressed edits.
+///
+/// Returns `None` if the compressed frame isn't smaller.
+fn compress(frame: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = flate2::write::DeflateEncoder::new(vec![2], flate2::Compression::fast());
+    encoder.write_all(&frame[1..]).ok()?;
+    let compressed = encoder.finish().ok()?;
+    (compressed.len() < frame.len()).then_some(compressed)
+}
+
+/// The edit frames that were sent most recently
+struct EditHistory {
+    frames: VecDeque<Vec<u8>>,
+    capacity: usize,
+    /// The total number of frames that were sent
+    sent: u64,
+}
+
+impl EditHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            capacity,
+            sent: 0,
+        }
+    }
+
+    fn push(&mut self, frame: &[u8]) {
+        self.sent += 1;
+        if self.capacity == 0 {
+            return;
+        }
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame.to_vec());
+    }
+
+    /// Get the frames that were sent after the first `applied` frames, if they are still available
+    fn since(&self, applied: u64) -> Option<impl Iterator<Item = &Vec<u8>>> {
+        let missed = usize::try_from(self.sent.checked_sub(applied)?).ok()?;
+        let skip = self.frames.len().checked_sub(missed)?;
+        Some(self.frames.iter().skip(skip))
+    }
+}
+
+pub(crate) fn text_frame(text: &str) -> Vec<u8> {
+    let mut bytes = vec![0];
+    bytes.extend(text.as_bytes());
+    bytes
+}
+
+fn take_edits(mutations: &mut MutationState) -> Option<Vec<u8>> {
+    // Add an extra one at the beginning to tell the shim this is a binary frame
+    let mut bytes = vec![1];
+    mutations.write_memory_into(&mut bytes);
+    (bytes.len() > 1).then_some(bytes)
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data")]
+pub(crate) enum ClientUpdate {
+    #[serde(rename = "query")]
+    Query(String),
+    /// The token the client can use to resume the session after the socket is closed
+    #[serde(rename = "session")]
+    Session(String),
+    /// The element ids of the nodes in the pre-rendered page, indexed by their hydration marker
+    #[serde(rename = "hydrate")]
+    Hydrate(Vec<u32>),
+    /// The pre-rendered page can't be hydrated. The client removes it and builds the page from the edits that follow.
+    #[serde(rename = "rebuild")]
+    Rebuild,
+    /// The session the client tried to resume is gone. The client needs to start over.
+    #[serde(rename = "expired")]
+    Expired,
+}
+
+impl ClientUpdate {
+    pub(crate) fn into_frame(self) -> Vec<u8> {
+        text_frame(&serde_json::to_string(&self).unwrap())
+    }
+}
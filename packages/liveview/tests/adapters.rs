@@ -0,0 +1,168 @@
+//! End to end tests that serve the same app with every adapter and check that they behave the same way.
+
+use dioxus::prelude::*;
+use dioxus_liveview::{
+    axum_route_with_pool, ActixRouter, LiveViewPool, LiveviewRouter, TowerRouter,
+};
+use futures_util::{SinkExt, StreamExt};
+use std::{net::SocketAddr, time::Duration};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_tungstenite::tungstenite::Message;
+
+fn app() -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! {
+        h1 { "Count: {count}" }
+        button { onclick: move |_| count += 1, "Increment" }
+    }
+}
+
+async fn listener() -> (tokio::net::TcpListener, SocketAddr) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    (listener, addr)
+}
+
+/// A pool that pre-renders the app into the page
+fn pre_rendering_pool() -> LiveViewPool {
+    LiveViewPool::new().pre_render(true)
+}
+
+async fn serve_axum() -> SocketAddr {
+    let (listener, addr) = listener().await;
+    let router = axum_route_with_pool(axum::Router::new(), pre_rendering_pool(), "/", || {
+        VirtualDom::new(app)
+    });
+    tokio::spawn(async move { axum::serve(listener, router.into_make_service()).await });
+    addr
+}
+
+async fn serve_tower() -> SocketAddr {
+    let (listener, addr) = listener().await;
+    tokio::spawn(
+        TowerRouter::new()
+            .with_pool(pre_rendering_pool())
+            .with_app("/", app)
+            .serve(listener),
+    );
+    addr
+}
+
+fn serve_actix() -> SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = ActixRouter::new()
+        .with_pool(pre_rendering_pool())
+        .with_app("/", app);
+    std::thread::spawn(move || {
+        actix_web::rt::System::new().block_on(async move {
+            actix_web::HttpServer::new(move || {
+                let router = router.clone();
+                actix_web::App::new().configure(move |cfg| router.configure(cfg))
+            })
+            .workers(1)
+            .listen(listener)
+            .unwrap()
+            .run()
+            .await
+        })
+    });
+    addr
+}
+
+/// Get a page from the server and return the status line and the body
+async fn get(addr: SocketAddr, path: &str) -> (String, String) {
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n").as_bytes(),
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.lines().next().unwrap().to_string(), body.to_string())
+}
+
+/// Start a session and return the edits of the initial render
+async fn initial_edits(addr: SocketAddr) -> Vec<u8> {
+    let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    ws.send(Message::Text(
+        r#"{"method":"initialize","params":{}}"#.to_string(),
+    ))
+    .await
+    .unwrap();
+
+    let mut edits = None;
+    ws.send(Message::Text("__ping__".to_string()))
+        .await
+        .unwrap();
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), ws.next())
+            .await
+            .expect("timed out waiting for the server")
+            .unwrap()
+            .unwrap();
+        let Message::Binary(frame) = message else {
+            panic!("expected a binary message, got {message:?}");
+        };
+        match frame[0] {
+            // The first edit frame is the initial render
+            1 if edits.is_none() => edits = Some(frame),
+            0 if frame[1..] == *b"__pong__" => break,
+            _ => {}
+        }
+    }
+    edits.expect("the server never sent the initial render")
+}
+
+#[tokio::test]
+async fn adapters_serve_the_same_page() {
+    let axum = serve_axum().await;
+    let tower = serve_tower().await;
+    let actix = serve_actix();
+
+    for path in ["/", "/nested/route"] {
+        let (status, expected) = get(axum, path).await;
+        assert!(status.contains("200"), "{status}");
+        assert!(expected.contains("WS_ADDR"));
+        // The app is pre-rendered into the page so the client can hydrate it
+        assert!(expected.contains("data-dioxus-hydrate"));
+        assert!(expected.contains("Count: 0"));
+        for addr in [tower, actix] {
+            let (status, page) = get(addr, path).await;
+            assert!(status.contains("200"), "{status}");
+            assert_eq!(page, expected);
+        }
+    }
+}
+
+#[tokio::test]
+async fn adapters_render_the_same_app() {
+    let axum = serve_axum().await;
+    let tower = serve_tower().await;
+    let actix = serve_actix();
+
+    let expected = initial_edits(axum).await;
+    assert_eq!(initial_edits(tower).await, expected);
+    assert_eq!(initial_edits(actix).await, expected);
+}
+
+#[tokio::test]
+async fn pages_are_not_pre_rendered_by_default() {
+    let (listener, addr) = listener().await;
+    tokio::spawn(TowerRouter::new().with_app("/", app).serve(listener));
+    let (_, page) = get(addr, "/").await;
+    assert!(page.contains(r#"<div id="main"></div>"#));
+    assert!(!page.contains("Count: 0"));
+}
+
+#[tokio::test]
+async fn tower_rejects_requests_to_the_socket_that_are_not_upgrades() {
+    let tower = serve_tower().await;
+    let (status, _) = get(tower, "/ws").await;
+    assert!(status.contains("400"), "{status}");
+}
@@ -0,0 +1,394 @@
+use dioxus::prelude::*;
+use dioxus_liveview::{ConnectionMetrics, LiveViewError, LiveViewPool};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{Sink, Stream, StreamExt};
+use std::{
+    io::Read,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// One end of an in memory socket
+struct MemorySocket {
+    rx: UnboundedReceiver<Vec<u8>>,
+    tx: UnboundedSender<Vec<u8>>,
+}
+
+impl Stream for MemorySocket {
+    type Item = Result<Vec<u8>, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx).map(|message| message.map(Ok))
+    }
+}
+
+impl Sink<Vec<u8>> for MemorySocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        self.tx
+            .unbounded_send(item)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The client side of a connection to the pool
+struct Client {
+    tx: UnboundedSender<Vec<u8>>,
+    rx: UnboundedReceiver<Vec<u8>>,
+}
+
+impl Client {
+    fn send(&self, message: serde_json::Value) {
+        self.tx
+            .unbounded_send(message.to_string().into_bytes())
+            .unwrap();
+    }
+
+    /// Ping the server and wait for the pong, skipping any edits or queries that are sent before it
+    async fn ping(&mut self) {
+        self.tx.unbounded_send(b"__ping__".to_vec()).unwrap();
+        while self.recv().await.unwrap() != b"\0__pong__" {}
+    }
+
+    async fn recv(&mut self) -> Option<Vec<u8>> {
+        tokio::time::timeout(Duration::from_secs(5), self.rx.next())
+            .await
+            .expect("timed out waiting for the server")
+    }
+
+    /// Receive frames until the server goes quiet for `idle`
+    async fn recv_until_idle(&mut self, idle: Duration) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        while let Ok(Some(frame)) = tokio::time::timeout(idle, self.rx.next()).await {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    async fn recv_text(&mut self) -> serde_json::Value {
+        let frame = self.recv().await.unwrap();
+        assert_eq!(frame[0], 0, "expected a text frame");
+        serde_json::from_slice(&frame[1..]).unwrap()
+    }
+}
+
+/// Connect a new client to the pool
+fn connect(pool: &LiveViewPool, launched: Arc<AtomicUsize>) -> Client {
+    connect_app(pool, launched, app)
+}
+
+/// Connect a new client to the pool that runs a specific app
+fn connect_app(pool: &LiveViewPool, launched: Arc<AtomicUsize>, app: fn() -> Element) -> Client {
+    let (client_tx, server_rx) = unbounded();
+    let (server_tx, client_rx) = unbounded();
+    let socket = MemorySocket {
+        rx: server_rx,
+        tx: server_tx,
+    };
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        _ = pool
+            .launch_virtualdom(socket, move || {
+                launched.fetch_add(1, Ordering::SeqCst);
+                VirtualDom::new(app)
+            })
+            .await;
+    });
+    Client {
+        tx: client_tx,
+        rx: client_rx,
+    }
+}
+
+fn app() -> Element {
+    rsx! { div { "hello world" } }
+}
+
+/// Start a new session and return its token and the edit frames from the initial render
+async fn initialize(client: &mut Client) -> (String, Vec<u8>) {
+    client.send(serde_json::json!({ "method": "initialize", "params": {} }));
+    let session = client.recv_text().await;
+    assert_eq!(session["type"], "session");
+    let token = session["data"].as_str().unwrap().to_string();
+    let edits = client.recv().await.unwrap();
+    assert_eq!(edits[0], 1, "expected an edit frame");
+    (token, edits)
+}
+
+fn resume(token: &str, edits: u64) -> serde_json::Value {
+    serde_json::json!({ "method": "resume", "params": { "token": token, "edits": edits } })
+}
+
+async fn wait_for_parked(pool: &LiveViewPool, count: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while pool.parked_sessions() != count {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for sessions to be parked");
+}
+
+#[tokio::test]
+async fn sessions_resume_after_reconnecting() {
+    let pool = LiveViewPool::new();
+    let launched = Arc::new(AtomicUsize::new(0));
+
+    let mut client = connect(&pool, launched.clone());
+    let (token, initial_edits) = initialize(&mut client).await;
+    drop(client);
+    wait_for_parked(&pool, 1).await;
+
+    // The client never applied the initial render, so it is replayed
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume(&token, 0));
+    assert_eq!(client.recv().await.unwrap(), initial_edits);
+    assert_eq!(pool.parked_sessions(), 0);
+    drop(client);
+    wait_for_parked(&pool, 1).await;
+
+    // The client is up to date, so nothing is replayed and the session keeps running
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume(&token, 1));
+    client.ping().await;
+
+    assert_eq!(launched.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn unknown_sessions_expire() {
+    let pool = LiveViewPool::new();
+    let launched = Arc::new(AtomicUsize::new(0));
+
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume("not a session", 0));
+    assert_eq!(client.recv_text().await["type"], "expired");
+    assert_eq!(client.recv().await, None);
+
+    // A client that is ahead of the session can't be resynchronised either
+    let mut client = connect(&pool, launched.clone());
+    let (token, _) = initialize(&mut client).await;
+    drop(client);
+    wait_for_parked(&pool, 1).await;
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume(&token, 10));
+    assert_eq!(client.recv_text().await["type"], "expired");
+    assert_eq!(pool.parked_sessions(), 0);
+}
+
+#[tokio::test]
+async fn parked_sessions_expire_after_the_grace_period() {
+    let pool = LiveViewPool::new().session_grace_period(Duration::from_millis(50));
+    let launched = Arc::new(AtomicUsize::new(0));
+
+    let mut client = connect(&pool, launched.clone());
+    let (token, _) = initialize(&mut client).await;
+    drop(client);
+    wait_for_parked(&pool, 1).await;
+    wait_for_parked(&pool, 0).await;
+
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume(&token, 1));
+    assert_eq!(client.recv_text().await["type"], "expired");
+}
+
+#[tokio::test]
+async fn the_oldest_parked_session_is_evicted() {
+    let pool = LiveViewPool::new().max_parked_sessions(2);
+    let launched = Arc::new(AtomicUsize::new(0));
+
+    let mut tokens = Vec::new();
+    for parked in 1..=3 {
+        let mut client = connect(&pool, launched.clone());
+        let (token, _) = initialize(&mut client).await;
+        tokens.push(token);
+        drop(client);
+        wait_for_parked(&pool, parked.min(2)).await;
+    }
+    // The count doesn't change when the last session replaces the oldest one, so give it time to be parked
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let mut client = connect(&pool, launched.clone());
+    client.send(resume(&tokens[0], 1));
+    assert_eq!(client.recv_text().await["type"], "expired");
+
+    for token in &tokens[1..] {
+        let mut client = connect(&pool, launched.clone());
+        client.send(resume(token, 1));
+        client.ping().await;
+    }
+}
+
+fn large_app() -> Element {
+    rsx! {
+        for i in 0..200 {
+            p { "This is paragraph number {i} of a long page" }
+        }
+    }
+}
+
+#[tokio::test]
+async fn large_edit_frames_are_compressed() {
+    let pool = LiveViewPool::new().compression(true);
+    let launched = Arc::new(AtomicUsize::new(0));
+
+    let mut plain = connect_app(&pool, launched.clone(), large_app);
+    let (_, expected) = initialize(&mut plain).await;
+
+    let mut client = connect_app(&pool, launched.clone(), large_app);
+    client.send(serde_json::json!({ "method": "initialize", "params": { "compression": true } }));
+    assert_eq!(client.recv_text().await["type"], "session");
+    let compressed = client.recv().await.unwrap();
+    assert_eq!(compressed[0], 2, "expected a compressed edit frame");
+    assert!(compressed.len() < expected.len());
+
+    let mut edits = vec![1];
+    flate2::read::DeflateDecoder::new(&compressed[1..])
+        .read_to_end(&mut edits)
+        .unwrap();
+    assert_eq!(edits, expected);
+}
+
+fn ticking_app() -> Element {
+    let mut ticks = use_signal(|| 0);
+    use_future(move || async move {
+        for _ in 0..10 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            ticks += 1;
+        }
+    });
+    rsx! { "{ticks}" }
+}
+
+/// Count the edit frames a client gets from the ticking app after the initial render
+async fn tick_frames(pool: &LiveViewPool) -> usize {
+    let mut client = connect_app(pool, Arc::default(), ticking_app);
+    initialize(&mut client).await;
+    client
+        .recv_until_idle(Duration::from_millis(300))
+        .await
+        .iter()
+        .filter(|frame| frame[0] == 1)
+        .count()
+}
+
+#[tokio::test]
+async fn rapid_renders_are_coalesced() {
+    let uncoalesced = tick_frames(&LiveViewPool::new()).await;
+    let coalesced =
+        tick_frames(&LiveViewPool::new().edit_coalescing(Duration::from_millis(200))).await;
+    assert!(coalesced < uncoalesced, "{coalesced} >= {uncoalesced}");
+    assert!(coalesced <= 2, "{coalesced} frames were sent");
+}
+
+fn counter() -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! {
+        button { onclick: move |_| count += 1, "{count}" }
+    }
+}
+
+/// A click on an element
+fn click(element: u64) -> serde_json::Value {
+    serde_json::json!({
+        "method": "user_event",
+        "params": {
+            "name": "click",
+            "element": element,
+            "bubbles": true,
+            "data": {
+                "alt_key": false, "button": 0, "buttons": 0, "client_x": 0, "client_y": 0,
+                "ctrl_key": false, "meta_key": false, "offset_x": 0, "offset_y": 0,
+                "page_x": 0, "page_y": 0, "screen_x": 0, "screen_y": 0, "shift_key": false
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn metrics_are_reported() {
+    let reports = Arc::new(Mutex::new(Vec::<ConnectionMetrics>::new()));
+    let pool = LiveViewPool::new()
+        .session_grace_period(Duration::ZERO)
+        .on_metrics(Duration::from_millis(20), {
+            let reports = reports.clone();
+            move |metrics| reports.lock().unwrap().push(metrics.clone())
+        });
+
+    let mut client = connect_app(&pool, Arc::default(), counter);
+    client.send(serde_json::json!({ "method": "initialize", "params": {} }));
+    client.recv().await.unwrap();
+    client.send(click(1));
+    client.ping().await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    drop(client);
+
+    let last = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(last) = reports.lock().unwrap().last().filter(|last| last.closed) {
+                return last.clone();
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("the session never sent its last report");
+
+    let reports = reports.lock().unwrap();
+    assert!(reports.len() > 1, "only the last report was sent");
+    assert!(reports.iter().all(|report| report.session == last.session));
+    assert_eq!(last.events_handled, 1);
+    assert!(last.edit_frames_sent >= 2);
+    assert_eq!(last.bytes_sent, last.uncompressed_bytes_sent);
+    assert!(last.bytes_sent > 0);
+    assert!(reports
+        .iter()
+        .any(|report| report.average_event_latency.is_some()));
+}
+
+#[tokio::test]
+async fn pre_rendered_pages_are_hydrated() {
+    let pool = LiveViewPool::new();
+    let html = pool
+        .pre_render_virtualdom(|| VirtualDom::new(counter))
+        .await
+        .unwrap();
+    // The button has the first hydration marker and listens for clicks. The count is the second one.
+    assert_eq!(
+        html,
+        r#"<button data-node-hydration="0,click:1"><!--node-id1-->0<!--#--></button>"#
+    );
+
+    let mut client = connect_app(&pool, Arc::default(), counter);
+    client.send(serde_json::json!({ "method": "initialize", "params": { "hydrate": true } }));
+    assert_eq!(client.recv_text().await["type"], "session");
+
+    // The client gets the ids of the nodes it already has instead of edits that create them
+    let hydrate = client.recv_text().await;
+    assert_eq!(hydrate["type"], "hydrate");
+    let ids: Vec<u64> = serde_json::from_value(hydrate["data"].clone()).unwrap();
+    assert_eq!(ids.len(), 2);
+
+    // The hydrated nodes are live
+    client.send(click(ids[0]));
+    let edits = client.recv().await.unwrap();
+    assert_eq!(edits[0], 1, "expected an edit frame");
+}
@@ -136,6 +136,37 @@ mod segment;
 ///
 /// Redirects allow you to redirect a route to another route. The function must take all dynamic parameters of the route and all parent nests.
 ///
+/// # `#[guard(function)]`
+///
+/// The `#[guard]` attribute attaches a navigation guard to the route it is placed on. It takes 1 parameter:
+/// - `function`: An async function that takes a `NavigationRequest` and returns a `NavigationDecision`
+///
+/// The guard runs before the router navigates to the route, after any guards registered with `RouterConfig::before_navigate`.
+/// It can allow the navigation, cancel it or redirect to another route. If a route has multiple guards, they run in order
+/// until one of them does not allow the navigation.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // Redirect to the index page instead of showing the admin page
+///     #[route("/admin")]
+///     #[guard(admin_only)]
+///     Admin {},
+/// }
+///
+/// async fn admin_only(request: NavigationRequest<Route>) -> NavigationDecision<Route> {
+///     NavigationDecision::Redirect(Route::Index {}.into())
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Admin() -> Element { VNode::empty() }
+/// ```
+///
 /// # `#[nest("path")]`
 ///
 /// The `#[nest]` attribute is used to define a nest. It takes 1 parameter:
@@ -241,7 +272,7 @@ mod segment;
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(route, nest, end_nest, layout, end_layout, redirect, child, guard)
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -660,14 +691,32 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut guard_matches = Vec::new();
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
                 matches.push(route.routable_match(&self.layouts, &self.nests, name));
+                guard_matches.extend(route.guard_match());
             }
         }
 
+        // Only override the default guard if a route has a guard attached
+        let guard_impl = (!guard_matches.is_empty()).then(|| {
+            quote! {
+                fn guard(
+                    &self,
+                    request: dioxus_router::guard::NavigationRequest<Self>,
+                ) -> Option<dioxus_router::guard::GuardFuture<Self>> {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #(#guard_matches)*
+                        _ => None,
+                    }
+                }
+            }
+        });
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
@@ -681,6 +730,8 @@ impl RouteEnum {
                         _ => VNode::empty()
                     }
                 }
+
+                #guard_impl
             }
         }
     }
@@ -60,6 +60,7 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub guards: Vec<syn::Expr>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -146,6 +147,13 @@ impl Route {
             _ => Vec::new(),
         };
 
+        let guards = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("guard"))
+            .map(|attr| attr.parse_args::<syn::Expr>())
+            .collect::<syn::Result<Vec<_>>>()?;
+
         let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -163,6 +171,7 @@ impl Route {
             hash,
             nests,
             layouts,
+            guards,
             fields,
         })
     }
@@ -211,6 +220,26 @@ impl Route {
         }
     }
 
+    pub(crate) fn guard_match(&self) -> Option<TokenStream2> {
+        if self.guards.is_empty() {
+            return None;
+        }
+
+        let name = &self.route_name;
+        let guards = &self.guards;
+        Some(quote! {
+            Self::#name { .. } => Some(Box::pin(async move {
+                #(
+                    match (#guards)(request.clone()).await {
+                        dioxus_router::guard::NavigationDecision::Allow => {}
+                        decision => return decision,
+                    }
+                )*
+                dioxus_router::guard::NavigationDecision::Allow
+            })),
+        })
+    }
+
     pub(crate) fn routable_match(
         &self,
         layouts: &[Layout],
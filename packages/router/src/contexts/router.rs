@@ -2,15 +2,21 @@ use std::{
     collections::HashSet,
     error::Error,
     fmt::Display,
+    rc::Rc,
     sync::{Arc, Mutex},
 };
 
 use dioxus_history::history;
 use dioxus_lib::prelude::*;
+use futures_util::{FutureExt, StreamExt};
 
 use crate::{
-    components::child_router::consume_child_route_mapping, navigation::NavigationTarget,
-    prelude::SiteMapSegment, routable::Routable, router_cfg::RouterConfig,
+    components::child_router::consume_child_route_mapping,
+    guard::{GuardFuture, NavigationDecision, NavigationKind, NavigationRequest},
+    navigation::NavigationTarget,
+    prelude::SiteMapSegment,
+    routable::Routable,
+    router_cfg::RouterConfig,
 };
 
 /// An error that is thrown when the router fails to parse a route
@@ -60,6 +66,11 @@ pub struct ExternalNavigationFailure(pub String);
 pub(crate) type RoutingCallback<R> =
     Arc<dyn Fn(GenericRouterContext<R>) -> Option<NavigationTarget<R>>>;
 pub(crate) type AnyRoutingCallback = Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>;
+/// Collects the guards that apply to a navigation. Returns `None` if no guard applies.
+type AnyNavigationGuard = Rc<dyn Fn(NavigationRequest<String>) -> Option<GuardFuture<String>>>;
+
+/// The maximum number of guard redirects the router will follow for a single navigation.
+const MAX_GUARD_REDIRECTS: usize = 16;
 
 struct RouterContextInner {
     unresolved_error: Option<ExternalNavigationFailure>,
@@ -67,6 +78,17 @@ struct RouterContextInner {
     subscribers: Arc<Mutex<HashSet<ReactiveContext>>>,
     routing_callback: Option<AnyRoutingCallback>,
 
+    navigation_guard: AnyNavigationGuard,
+    /// A navigation that is waiting on a guard
+    pending_navigation: Option<(Task, NavigationRequest<String>)>,
+
+    /// The route the router shows. Moves through the history only change it once the guards allow them.
+    route: String,
+    /// The routes before and after the route the router shows, like the history provider has them. This tells which
+    /// way the history moved when it moves on its own.
+    back_routes: Vec<String>,
+    forward_routes: Vec<String>,
+
     failure_external_navigation: fn() -> Element,
 
     internal_route: fn(&str) -> bool,
@@ -134,6 +156,67 @@ impl RouterContext {
                 }) as Arc<dyn Fn(RouterContext) -> Option<NavigationTarget>>
             }),
 
+            navigation_guard: {
+                let guards = cfg.before_navigate;
+                let mapping = consume_child_route_mapping::<R>();
+                Rc::new(move |request: NavigationRequest<String>| {
+                    let parse = |route: Option<String>| {
+                        let route = route?;
+                        match mapping.as_ref() {
+                            Some(mapping) => mapping.parse_route_from_root_route(&route),
+                            None => R::from_str(&route).ok(),
+                        }
+                    };
+                    let request = NavigationRequest {
+                        from: parse(request.from),
+                        to: parse(request.to),
+                        kind: request.kind,
+                    };
+                    let route_guard = request.to.as_ref().and_then(|to| to.guard(request.clone()));
+                    if guards.is_empty() && route_guard.is_none() {
+                        return None;
+                    }
+
+                    let guards = guards.clone();
+                    let mapping = mapping.clone();
+                    Some(Box::pin(async move {
+                        let mut decision = NavigationDecision::Allow;
+                        for guard in &guards {
+                            decision = guard(request.clone()).await;
+                            if !matches!(decision, NavigationDecision::Allow) {
+                                break;
+                            }
+                        }
+                        if let (NavigationDecision::Allow, Some(route_guard)) =
+                            (&decision, route_guard)
+                        {
+                            decision = route_guard.await;
+                        }
+
+                        match decision {
+                            NavigationDecision::Allow => NavigationDecision::Allow,
+                            NavigationDecision::Cancel => NavigationDecision::Cancel,
+                            NavigationDecision::Redirect(NavigationTarget::Internal(r)) => {
+                                NavigationDecision::Redirect(NavigationTarget::Internal(
+                                    match mapping.as_ref() {
+                                        Some(mapping) => mapping.format_route_as_root_route(r),
+                                        None => r.to_string(),
+                                    },
+                                ))
+                            }
+                            NavigationDecision::Redirect(NavigationTarget::External(s)) => {
+                                NavigationDecision::Redirect(NavigationTarget::External(s))
+                            }
+                        }
+                    }) as GuardFuture<String>)
+                })
+            },
+            pending_navigation: None,
+
+            route: history().current_route(),
+            back_routes: Vec::new(),
+            forward_routes: Vec::new(),
+
             failure_external_navigation: cfg.failure_external_navigation,
 
             internal_route: |route| R::from_str(route).is_ok(),
@@ -141,16 +224,25 @@ impl RouterContext {
             site_map: R::SITE_MAP,
         };
 
-        // set the updater
+        // set the updater. The history provider calls it when the history changes without the router, like when the
+        // user presses the back button of the browser
+        let (history_moved, mut history_moves) = futures_channel::mpsc::unbounded();
         history().updater(Arc::new(move || {
             for &rc in subscribers.lock().unwrap().iter() {
                 rc.mark_dirty();
             }
+            _ = history_moved.unbounded_send(());
         }));
 
-        Self {
+        let myself = Self {
             inner: CopyValue::new_in_scope(myself, ScopeId::ROOT),
-        }
+        };
+        spawn_forever(async move {
+            while history_moves.next().await.is_some() {
+                myself.history_moved();
+            }
+        });
+        myself
     }
 
     /// Check if the router is running in a liveview context
@@ -175,69 +267,243 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
-        history().go_back();
-        self.change_route();
+        self.navigate(None, NavigationKind::Back);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
-        history().go_forward();
-        self.change_route();
+        self.navigate(None, NavigationKind::Forward);
     }
 
     pub(crate) fn push_any(&self, target: NavigationTarget) -> Option<ExternalNavigationFailure> {
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => history().push(p),
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
-
-        self.change_route()
+        self.navigate(Some(target), NavigationKind::Push)
     }
 
     /// Push a new location.
     ///
     /// The previous location will be available to go back to.
+    ///
+    /// If a navigation guard is registered, the location only changes once every guard allows it.
     pub fn push(&self, target: impl Into<NavigationTarget>) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut write = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.push(p)
-                }
-                NavigationTarget::External(e) => return write.external(e),
-            }
-        }
-
-        self.change_route()
+        self.navigate(Some(target.into()), NavigationKind::Push)
     }
 
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
+    ///
+    /// If a navigation guard is registered, the location only changes once every guard allows it.
     pub fn replace(
         &self,
         target: impl Into<NavigationTarget>,
     ) -> Option<ExternalNavigationFailure> {
-        let target = target.into();
-        {
-            let mut state = self.inner.write_unchecked();
-            match target {
-                NavigationTarget::Internal(p) => {
-                    let history = history();
-                    history.replace(p)
+        self.navigate(Some(target.into()), NavigationKind::Replace)
+    }
+
+    /// Check whether a navigation is waiting on a guard.
+    pub fn is_navigation_pending(&self) -> bool {
+        let inner = self.inner.read();
+        inner.subscribe_to_current_context();
+        inner.pending_navigation.is_some()
+    }
+
+    /// Run the navigation guards for a navigation and apply it if they allow it.
+    ///
+    /// `target` is `None` when moving through the history stack. The history moves right away so the guards know
+    /// where it leads, but the router keeps showing the current route until the guards allow the move.
+    fn navigate(
+        &self,
+        target: Option<NavigationTarget>,
+        kind: NavigationKind,
+    ) -> Option<ExternalNavigationFailure> {
+        self.navigate_with_redirects(target, kind, 0)
+    }
+
+    fn navigate_with_redirects(
+        &self,
+        target: Option<NavigationTarget>,
+        kind: NavigationKind,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        // A new navigation replaces any navigation that is still waiting on a guard
+        self.cancel_pending_navigation();
+
+        let to = match target {
+            Some(NavigationTarget::Internal(p)) => p,
+            // External navigations leave the app, so there is nothing to guard
+            Some(NavigationTarget::External(e)) => return self.inner.write_unchecked().external(e),
+            None => {
+                let history = history();
+                match kind {
+                    NavigationKind::Forward => history.go_forward(),
+                    _ => history.go_back(),
                 }
-                NavigationTarget::External(e) => return state.external(e),
+                history.current_route()
             }
+        };
+        let request = NavigationRequest {
+            from: Some(self.inner.read().route.clone()),
+            to: Some(to),
+            kind,
+        };
+        self.guard_navigation(request, redirects)
+    }
+
+    fn guard_navigation(
+        &self,
+        request: NavigationRequest<String>,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        let guard = self.inner.read().navigation_guard.clone();
+        let Some(mut decision) = guard(request.clone()) else {
+            return self.resolve_navigation(NavigationDecision::Allow, request, redirects);
+        };
+
+        // Most guards resolve immediately. Apply those without waiting for the next poll
+        // so the navigation is visible in the same render.
+        let myself = *self;
+        if let Some(decision) = (&mut decision).now_or_never() {
+            return myself.resolve_navigation(decision, request, redirects);
         }
 
-        self.change_route()
+        let task = spawn_forever({
+            let request = request.clone();
+            async move {
+                let decision = decision.await;
+                myself.inner.write_unchecked().pending_navigation = None;
+                myself.resolve_navigation(decision, request, redirects);
+                myself.inner.read().update_subscribers();
+            }
+        });
+        self.inner.write_unchecked().pending_navigation = task.map(|task| (task, request));
+        self.inner.read().update_subscribers();
+
+        None
+    }
+
+    fn resolve_navigation(
+        &self,
+        decision: NavigationDecision,
+        request: NavigationRequest<String>,
+        redirects: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        match decision {
+            NavigationDecision::Allow => {
+                match (request.kind, &request.to) {
+                    (NavigationKind::Push, Some(to)) => history().push(to.clone()),
+                    (NavigationKind::Replace, Some(to)) => history().replace(to.clone()),
+                    // Moves through the history already happened
+                    _ => {}
+                }
+                self.record_history_move(&request);
+                self.change_route()
+            }
+            NavigationDecision::Cancel => {
+                self.undo_history_move(&request);
+                None
+            }
+            NavigationDecision::Redirect(redirect) => {
+                if redirects >= MAX_GUARD_REDIRECTS {
+                    tracing::error!(
+                        "Navigation guards redirected more than {MAX_GUARD_REDIRECTS} times. Cancelling the navigation to {redirect:?}"
+                    );
+                    self.undo_history_move(&request);
+                    return None;
+                }
+                // Moving through history can't be redirected in place, so redirects become a push
+                let kind = match request.kind {
+                    kind if kind.moves_through_history() => NavigationKind::Push,
+                    kind => kind,
+                };
+                self.navigate_with_redirects(Some(redirect), kind, redirects + 1)
+            }
+        }
+    }
+
+    /// Move the history back to the route the router shows after a move through the history was cancelled
+    fn undo_history_move(&self, request: &NavigationRequest<String>) {
+        let history = history();
+        match self.history_move_direction(request) {
+            Some(NavigationKind::Back) => history.go_forward(),
+            Some(NavigationKind::Forward) => history.go_back(),
+            // The history jumped to a route the router doesn't know, so the entry it jumped to shows the old route again
+            Some(_) => history.replace(self.inner.read().route.clone()),
+            None => return,
+        }
+        self.inner.read().update_subscribers();
+    }
+
+    /// The way a navigation moved through the history, or None if it didn't move through the history
+    fn history_move_direction(
+        &self,
+        request: &NavigationRequest<String>,
+    ) -> Option<NavigationKind> {
+        let inner = self.inner.read();
+        match request.kind {
+            NavigationKind::Push | NavigationKind::Replace => None,
+            NavigationKind::Pop if inner.back_routes.last() == request.to.as_ref() => {
+                Some(NavigationKind::Back)
+            }
+            NavigationKind::Pop if inner.forward_routes.last() == request.to.as_ref() => {
+                Some(NavigationKind::Forward)
+            }
+            kind => Some(kind),
+        }
+    }
+
+    /// Keep track of the routes before and after the current route after a navigation was allowed
+    fn record_history_move(&self, request: &NavigationRequest<String>) {
+        let direction = self.history_move_direction(request);
+        let mut inner = self.inner.write_unchecked();
+        let old = inner.route.clone();
+        match (request.kind, direction) {
+            // Pushing the current route again doesn't add an entry
+            (NavigationKind::Push, _) if request.to.as_ref() != Some(&old) => {
+                inner.back_routes.push(old);
+                inner.forward_routes.clear();
+            }
+            (_, Some(NavigationKind::Back)) => {
+                inner.back_routes.pop();
+                inner.forward_routes.push(old);
+            }
+            (_, Some(NavigationKind::Forward)) => {
+                inner.forward_routes.pop();
+                inner.back_routes.push(old);
+            }
+            _ => {}
+        }
+    }
+
+    /// Guard a move through the history that the router didn't make
+    fn history_moved(&self) {
+        let inner = self.inner.read();
+        let current = history().current_route();
+        let moving = inner
+            .pending_navigation
+            .as_ref()
+            .is_some_and(|(_, request)| request.kind.moves_through_history());
+        if current == inner.route || moving {
+            return;
+        }
+
+        let request = NavigationRequest {
+            from: Some(inner.route.clone()),
+            to: Some(current),
+            kind: NavigationKind::Pop,
+        };
+        drop(inner);
+        self.cancel_pending_navigation();
+        self.guard_navigation(request, 0);
+    }
+
+    fn cancel_pending_navigation(&self) {
+        let pending = self.inner.write_unchecked().pending_navigation.take();
+        if let Some((task, request)) = pending {
+            task.cancel();
+            self.undo_history_move(&request);
+        }
     }
 
     /// The route that is currently active.
@@ -267,8 +533,7 @@ impl RouterContext {
     pub fn full_route_string(&self) -> String {
         let inner = self.inner.read();
         inner.subscribe_to_current_context();
-        let history = history();
-        history.current_route()
+        inner.route.clone()
     }
 
     /// The prefix that is currently active.
@@ -300,12 +565,9 @@ impl RouterContext {
     }
 
     fn change_route(&self) -> Option<ExternalNavigationFailure> {
-        let self_read = self.inner.read();
-        if let Some(callback) = &self_read.routing_callback {
-            let myself = *self;
-            let callback = callback.clone();
-            drop(self_read);
-            if let Some(new) = callback(myself) {
+        let callback = self.inner.read().routing_callback.clone();
+        if let Some(callback) = callback {
+            if let Some(new) = callback(*self) {
                 let mut self_write = self.inner.write_unchecked();
                 match new {
                     NavigationTarget::Internal(p) => {
@@ -317,7 +579,9 @@ impl RouterContext {
             }
         }
 
-        self.inner.read().update_subscribers();
+        let mut inner = self.inner.write_unchecked();
+        inner.route = history().current_route();
+        inner.update_subscribers();
 
         None
     }
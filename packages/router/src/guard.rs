@@ -0,0 +1,97 @@
+//! Types for intercepting navigation before the router applies it.
+//!
+//! Guards can be registered globally with [`RouterConfig::before_navigate`](crate::prelude::RouterConfig::before_navigate)
+//! or attached to a single route with the `#[guard(..)]` attribute of the [`Routable`] derive. Every guard
+//! returns a future, so it can wait on anything (a confirmation dialog, an authentication check, a server
+//! function) before deciding what happens to the navigation.
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! # use dioxus_router::guard::{NavigationDecision, NavigationRequest};
+//! # #[component]
+//! # fn Home() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Admin() -> Element { VNode::empty() }
+//! # #[component]
+//! # fn Login() -> Element { VNode::empty() }
+//! #[derive(Clone, Routable, Debug, PartialEq)]
+//! enum Route {
+//!     #[route("/")]
+//!     Home {},
+//!     // Only logged in users can see the admin page
+//!     #[route("/admin")]
+//!     #[guard(require_login)]
+//!     Admin {},
+//!     #[route("/login")]
+//!     Login {},
+//! }
+//!
+//! async fn require_login(_: NavigationRequest<Route>) -> NavigationDecision<Route> {
+//!     let logged_in = false;
+//!     match logged_in {
+//!         true => NavigationDecision::Allow,
+//!         false => NavigationDecision::Redirect(Route::Login {}.into()),
+//!     }
+//! }
+//! ```
+
+use std::{future::Future, pin::Pin};
+
+use crate::navigation::NavigationTarget;
+
+/// The history operation that triggered a navigation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationKind {
+    /// A new location is pushed onto the history stack.
+    Push,
+    /// The current location is replaced.
+    Replace,
+    /// The router moves back through the history stack.
+    Back,
+    /// The router moves forward through the history stack.
+    Forward,
+    /// The history provider moved through the history stack on its own, like when the user pressed the back or
+    /// forward button of the browser.
+    ///
+    /// Cancelling this navigation pushes the previous route again, since the direction of the move is unknown.
+    Pop,
+}
+
+impl NavigationKind {
+    /// Check if the history already moved when the guards run
+    pub(crate) fn moves_through_history(self) -> bool {
+        matches!(self, Self::Back | Self::Forward | Self::Pop)
+    }
+}
+
+/// A navigation the router is about to perform.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavigationRequest<R> {
+    /// The route that is currently active.
+    pub from: Option<R>,
+    /// The route the router is navigating to.
+    ///
+    /// This is [`None`] if the location is not a route of the app.
+    pub to: Option<R>,
+    /// The history operation that triggered the navigation.
+    pub kind: NavigationKind,
+}
+
+/// What the router should do with a [`NavigationRequest`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum NavigationDecision<R = String> {
+    /// Let the navigation through. The next guard runs, or the navigation is applied if this was the last one.
+    Allow,
+    /// Drop the navigation. The current route stays active.
+    Cancel,
+    /// Navigate somewhere else instead, with the same [`NavigationKind`] as the original request.
+    ///
+    /// Redirects to internal routes run through the guards again.
+    Redirect(NavigationTarget<R>),
+}
+
+/// The future returned by a navigation guard.
+pub type GuardFuture<R> = Pin<Box<dyn Future<Output = NavigationDecision<R>>>>;
+
+/// A guard registered with [`RouterConfig::before_navigate`](crate::prelude::RouterConfig::before_navigate).
+pub(crate) type NavigationGuard<R> = std::rc::Rc<dyn Fn(NavigationRequest<R>) -> GuardFuture<R>>;
@@ -5,6 +5,7 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod guard;
 pub mod navigation;
 pub mod routable;
 
@@ -65,6 +66,7 @@ pub mod prelude {
         RouterProps,
     };
     pub use crate::contexts::*;
+    pub use crate::guard::{NavigationDecision, NavigationKind, NavigationRequest};
     pub use crate::hooks::*;
     pub use crate::navigation::*;
     pub use crate::routable::*;
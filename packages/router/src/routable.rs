@@ -3,6 +3,8 @@
 #![allow(non_snake_case)]
 use dioxus_lib::prelude::*;
 
+use crate::guard::{GuardFuture, NavigationRequest};
+
 use std::iter::FlatMap;
 use std::slice::Iter;
 use std::{fmt::Display, str::FromStr};
@@ -599,6 +601,15 @@ pub trait Routable: FromStr<Err: Display> + Display + Clone + 'static {
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
+    /// Get the navigation guard attached to this route with the `#[guard(..)]` attribute.
+    ///
+    /// The router calls this on the route it is about to navigate to. Returns [`None`] if the route
+    /// has no guard.
+    #[allow(unused_variables)]
+    fn guard(&self, request: NavigationRequest<Self>) -> Option<GuardFuture<Self>> {
+        None
+    }
+
     /// Checks if this route is a child of the given route.
     ///
     /// # Example
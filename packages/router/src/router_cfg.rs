@@ -1,6 +1,10 @@
-use crate::{components::FailureExternalNavigation, prelude::*};
+use crate::{
+    components::FailureExternalNavigation,
+    guard::{NavigationDecision, NavigationGuard, NavigationRequest},
+    prelude::*,
+};
 use dioxus_lib::prelude::*;
-use std::sync::Arc;
+use std::{future::Future, rc::Rc, sync::Arc};
 
 /// Global configuration options for the router.
 ///
@@ -29,6 +33,7 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) before_navigate: Vec<NavigationGuard<R>>,
 }
 
 impl<R> Default for RouterConfig<R> {
@@ -36,6 +41,7 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: FailureExternalNavigation,
             on_update: None,
+            before_navigate: Vec::new(),
         }
     }
 }
@@ -67,6 +73,50 @@ where
         }
     }
 
+    /// Add a guard that runs before every internal navigation.
+    ///
+    /// Guards run in the order they were added, before any `#[guard(..)]` attached to the target
+    /// route. The first guard that does not return [`NavigationDecision::Allow`] decides what happens
+    /// to the navigation. While a guard is pending, the current route stays active, so a guard can
+    /// wait for the user to confirm the navigation.
+    ///
+    /// Guards do not run for external navigations or for the target of [`RouterConfig::on_update`].
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus_router::guard::NavigationDecision;
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Index() -> Element { VNode::empty() }
+    /// # #[component]
+    /// # fn Editor() -> Element { VNode::empty() }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Index {},
+    ///     #[route("/editor")]
+    ///     Editor {},
+    /// }
+    ///
+    /// static UNSAVED_CHANGES: GlobalSignal<bool> = Signal::global(|| false);
+    ///
+    /// let cfg = RouterConfig::<Route>::default().before_navigate(|request| async move {
+    ///     // Keep the user in the editor while they have unsaved changes
+    ///     match request.from {
+    ///         Some(Route::Editor {}) if UNSAVED_CHANGES() => NavigationDecision::Cancel,
+    ///         _ => NavigationDecision::Allow,
+    ///     }
+    /// });
+    /// ```
+    pub fn before_navigate<F>(mut self, guard: impl Fn(NavigationRequest<R>) -> F + 'static) -> Self
+    where
+        F: Future<Output = NavigationDecision<R>> + 'static,
+    {
+        self.before_navigate
+            .push(Rc::new(move |request| Box::pin(guard(request))));
+        self
+    }
+
     /// A component to render when an external navigation fails.
     ///
     /// Defaults to a router-internal component called [`FailureExternalNavigation`]
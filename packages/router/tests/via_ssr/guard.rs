@@ -0,0 +1,335 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::History;
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/admin")]
+    #[guard(require_login)]
+    Admin {},
+    #[route("/login")]
+    Login {},
+    #[route("/editor")]
+    Editor {},
+}
+
+async fn require_login(_: NavigationRequest<Route>) -> NavigationDecision<Route> {
+    NavigationDecision::Redirect(Route::Login {}.into())
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! { "Home" }
+}
+
+#[component]
+fn Admin() -> Element {
+    rsx! { "Admin" }
+}
+
+#[component]
+fn Login() -> Element {
+    rsx! { "Login" }
+}
+
+#[component]
+fn Editor() -> Element {
+    rsx! { "Editor" }
+}
+
+/// A history that works like the history of a browser: the back and forward buttons move it without the router and
+/// tell the router with the updater
+#[derive(Default)]
+struct BrowserHistory {
+    entries: RefCell<Vec<String>>,
+    index: RefCell<usize>,
+    updater: RefCell<Option<Arc<dyn Fn() + Send + Sync>>>,
+}
+
+impl BrowserHistory {
+    fn new() -> Rc<Self> {
+        let history = Self::default();
+        history.entries.borrow_mut().push("/".to_string());
+        Rc::new(history)
+    }
+
+    fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    fn press_back(&self) {
+        self.go_back();
+        (self.updater.borrow().as_ref().unwrap())();
+    }
+}
+
+impl History for BrowserHistory {
+    fn current_route(&self) -> String {
+        self.entries.borrow()[*self.index.borrow()].clone()
+    }
+
+    fn can_go_back(&self) -> bool {
+        *self.index.borrow() > 0
+    }
+
+    fn go_back(&self) {
+        let mut index = self.index.borrow_mut();
+        *index = index.saturating_sub(1);
+    }
+
+    fn can_go_forward(&self) -> bool {
+        *self.index.borrow() + 1 < self.len()
+    }
+
+    fn go_forward(&self) {
+        let mut index = self.index.borrow_mut();
+        *index = (*index + 1).min(self.len() - 1);
+    }
+
+    fn push(&self, route: String) {
+        let mut index = self.index.borrow_mut();
+        let mut entries = self.entries.borrow_mut();
+        entries.truncate(*index + 1);
+        entries.push(route);
+        *index += 1;
+    }
+
+    fn replace(&self, route: String) {
+        self.entries.borrow_mut()[*self.index.borrow()] = route;
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        *self.updater.borrow_mut() = Some(callback);
+    }
+}
+
+#[derive(Props, Clone)]
+struct AppProps {
+    navigate_to: Route,
+    config: fn() -> RouterConfig<Route>,
+    history: Option<Rc<BrowserHistory>>,
+}
+
+impl PartialEq for AppProps {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn App(props: AppProps) -> Element {
+    // Renderers provide their history in the root scope
+    use_hook(|| {
+        if let Some(history) = props.history.clone() {
+            provide_root_context(history as Rc<dyn History>);
+        }
+    });
+    use_effect(move || {
+        root_router().unwrap().push(props.navigate_to.clone());
+    });
+
+    rsx! {
+        Router::<Route> { config: move |_| (props.config)() }
+    }
+}
+
+fn render_after_navigation(navigate_to: Route, config: fn() -> RouterConfig<Route>) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            navigate_to,
+            config,
+            history: None,
+        },
+    );
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+    dioxus_ssr::render(&vdom)
+}
+
+#[test]
+fn global_guard_allows_navigation() {
+    let html = render_after_navigation(Route::Editor {}, || {
+        RouterConfig::default().before_navigate(|_| async { NavigationDecision::Allow })
+    });
+    assert_eq!(html, "Editor");
+}
+
+#[test]
+fn global_guard_cancels_navigation() {
+    let html = render_after_navigation(Route::Editor {}, || {
+        RouterConfig::default().before_navigate(|request| async move {
+            assert_eq!(request.from, Some(Route::Home {}));
+            assert_eq!(request.to, Some(Route::Editor {}));
+            assert_eq!(request.kind, NavigationKind::Push);
+            NavigationDecision::Cancel
+        })
+    });
+    assert_eq!(html, "Home");
+}
+
+#[test]
+fn route_guard_redirects() {
+    let html = render_after_navigation(Route::Admin {}, RouterConfig::default);
+    assert_eq!(html, "Login");
+}
+
+#[test]
+fn global_guards_run_before_route_guards() {
+    let html = render_after_navigation(Route::Admin {}, || {
+        RouterConfig::default().before_navigate(|_| async { NavigationDecision::Cancel })
+    });
+    assert_eq!(html, "Home");
+}
+
+#[test]
+fn redirect_loops_are_cancelled() {
+    let html = render_after_navigation(Route::Editor {}, || {
+        RouterConfig::default().before_navigate(|request| async move {
+            match request.to {
+                Some(Route::Editor {}) => NavigationDecision::Redirect(Route::Login {}.into()),
+                _ => NavigationDecision::Redirect(Route::Editor {}.into()),
+            }
+        })
+    });
+    assert_eq!(html, "Home");
+}
+
+#[tokio::test]
+async fn async_guard_defers_navigation() {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            navigate_to: Route::Editor {},
+            config: || {
+                RouterConfig::default().before_navigate(|_| async {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    NavigationDecision::Allow
+                })
+            },
+            history: None,
+        },
+    );
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+
+    // The guard is still pending, so the old route stays active
+    assert_eq!(dioxus_ssr::render(&vdom), "Home");
+
+    tokio::time::timeout(Duration::from_secs(5), vdom.wait_for_work())
+        .await
+        .unwrap();
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "Editor");
+}
+
+/// Render the app after navigating to the editor, then run `f` in the root scope and render again
+fn render_after_moving(config: fn() -> RouterConfig<Route>, f: impl FnOnce()) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            navigate_to: Route::Editor {},
+            config,
+            history: None,
+        },
+    );
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "Editor");
+
+    vdom.in_runtime(|| ScopeId::ROOT.in_runtime(f));
+    vdom.render_immediate(&mut NoOpMutations);
+    dioxus_ssr::render(&vdom)
+}
+
+fn cancel_moves_home() -> RouterConfig<Route> {
+    RouterConfig::default().before_navigate(|request| async move {
+        match request.kind {
+            NavigationKind::Push => NavigationDecision::Allow,
+            _ => {
+                assert_eq!(request.from, Some(Route::Editor {}));
+                assert_eq!(request.to, Some(Route::Home {}));
+                NavigationDecision::Cancel
+            }
+        }
+    })
+}
+
+#[test]
+fn guards_can_cancel_going_back() {
+    let html = render_after_moving(cancel_moves_home, || {
+        root_router().unwrap().go_back();
+        // The history moves back to where it was
+        assert_eq!(history().current_route(), "/editor");
+        assert!(history().can_go_back());
+    });
+    assert_eq!(html, "Editor");
+}
+
+/// Render the app after navigating to the editor in a browser history, then move the history without the router
+/// and render again once the router caught up
+async fn render_after_browser_moves(
+    config: fn() -> RouterConfig<Route>,
+    f: impl FnOnce(&BrowserHistory),
+) -> (String, Rc<BrowserHistory>) {
+    let history = BrowserHistory::new();
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            navigate_to: Route::Editor {},
+            config,
+            history: Some(history.clone()),
+        },
+    );
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "Editor");
+
+    f(&history);
+    while tokio::time::timeout(Duration::from_millis(100), vdom.wait_for_work())
+        .await
+        .is_ok()
+    {
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+    (dioxus_ssr::render(&vdom), history)
+}
+
+#[tokio::test]
+async fn guards_run_when_the_history_moves_on_its_own() {
+    let (html, history) =
+        render_after_browser_moves(cancel_moves_home, BrowserHistory::press_back).await;
+    assert_eq!(html, "Editor");
+    assert_eq!(history.current_route(), "/editor");
+}
+
+#[tokio::test]
+async fn cancelled_history_moves_keep_the_history() {
+    let (html, history) = render_after_browser_moves(cancel_moves_home, |history| {
+        history.push("/login".to_string());
+        history.go_back();
+        // The user moves back to the editor, then back home, which the guard cancels
+        history.press_back();
+    })
+    .await;
+    assert_eq!(html, "Editor");
+    assert_eq!(history.current_route(), "/editor");
+    // The move is undone without adding an entry or losing the entries after the editor
+    assert_eq!(history.len(), 3);
+    assert!(history.can_go_back());
+    assert!(history.can_go_forward());
+}
+
+#[tokio::test]
+async fn route_guards_run_when_the_history_moves_on_its_own() {
+    let (html, _) = render_after_browser_moves(RouterConfig::default, |history| {
+        history.push("/admin".to_string());
+        history.push("/editor".to_string());
+        history.press_back();
+    })
+    .await;
+    assert_eq!(html, "Login");
+}
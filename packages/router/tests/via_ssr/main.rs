@@ -1,3 +1,4 @@
+mod guard;
 mod link;
 mod navigation;
 mod outlet;
@@ -0,0 +1,126 @@
+use dioxus_core::{prelude::ReactiveContext, ReactiveGraph};
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+type Subscribers = Arc<Mutex<HashSet<ReactiveContext>>>;
+
+thread_local! {
+    static BATCH: RefCell<Batch> = const {
+        RefCell::new(Batch {
+            depth: 0,
+            pending: Vec::new(),
+        })
+    };
+}
+
+struct Batch {
+    depth: usize,
+    /// The subscribers of every value written during the batch
+    pending: Vec<Subscribers>,
+}
+
+/// Run a closure, waiting to notify the subscribers of any signals written inside it until the closure finishes.
+///
+/// Without a batch, each write immediately marks the components, effects and memos that read the signal as dirty. If
+/// you write several signals in a row, a memo that reads more than one of them may be recomputed in between the writes
+/// and observe some of the new values mixed with some of the old values. Inside a batch, memos keep the value they had
+/// before the batch and every subscriber is marked dirty once when the outermost batch ends, so they only ever see the
+/// finished update.
+///
+/// Batches only delay notifications for writes on the current thread.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn app() -> Element {
+///     let mut first = use_signal(|| "Jane".to_string());
+///     let mut last = use_signal(|| "Doe".to_string());
+///     let full_name = use_memo(move || format!("{first} {last}"));
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| batch(|| {
+///                 first.set("John".to_string());
+///                 last.set("Smith".to_string());
+///                 // The memo still has the name from before the batch
+///                 assert_eq!(full_name(), "Jane Doe");
+///             }),
+///             "{full_name}"
+///         }
+///     }
+/// }
+/// ```
+pub fn batch<O>(f: impl FnOnce() -> O) -> O {
+    BATCH.with(|batch| batch.borrow_mut().depth += 1);
+
+    // End the batch even if the closure panics, so later writes aren't deferred forever
+    struct EndBatch;
+    impl Drop for EndBatch {
+        fn drop(&mut self) {
+            let pending = BATCH.with(|batch| {
+                let mut batch = batch.borrow_mut();
+                batch.depth -= 1;
+                match batch.depth {
+                    0 => std::mem::take(&mut batch.pending),
+                    _ => Vec::new(),
+                }
+            });
+            flush(pending);
+        }
+    }
+    let _end = EndBatch;
+
+    f()
+}
+
+/// Check if writes on this thread are currently being batched
+pub fn is_batching() -> bool {
+    BATCH.with(|batch| batch.borrow().depth > 0)
+}
+
+/// Mark the subscribers of a value that was written as dirty, or wait until the current batch ends
+pub(crate) fn mark_dirty(subscribers: &Subscribers) {
+    let deferred = BATCH.with(|batch| {
+        let mut batch = batch.borrow_mut();
+        if batch.depth == 0 {
+            return false;
+        }
+        if !batch
+            .pending
+            .iter()
+            .any(|pending| Arc::ptr_eq(pending, subscribers))
+        {
+            batch.pending.push(subscribers.clone());
+        }
+        true
+    });
+    if !deferred {
+        flush(vec![subscribers.clone()]);
+    }
+}
+
+fn flush(pending: Vec<Subscribers>) {
+    // Contexts that read several of the written values are only marked dirty once
+    #[allow(clippy::mutable_key_type)]
+    let mut marked = HashSet::new();
+    for subscribers in pending {
+        // We cannot hold the subscribers lock while calling mark_dirty, because mark_dirty can run user code which may cause a new subscriber to be added. If we hold the lock, we will deadlock.
+        #[allow(clippy::mutable_key_type)]
+        let mut contexts = std::mem::take(&mut *subscribers.lock().unwrap());
+        contexts.retain(|reactive_context| {
+            marked.contains(reactive_context) || {
+                let alive = reactive_context.mark_dirty();
+                if alive {
+                    ReactiveGraph::record_dirty(&subscribers, reactive_context);
+                    marked.insert(*reactive_context);
+                }
+                alive
+            }
+        });
+        // Extend the subscribers list instead of overwriting it in case a subscriber is added while reactive contexts are marked dirty
+        subscribers.lock().unwrap().extend(contexts);
+    }
+}
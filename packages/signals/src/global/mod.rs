@@ -211,7 +211,7 @@ where
 /// The context for global signals
 #[derive(Clone, Default)]
 pub struct GlobalLazyContext {
-    map: Rc<RefCell<HashMap<GlobalKey<'static>, Box<dyn Any>>>>,
+    pub(crate) map: Rc<RefCell<HashMap<GlobalKey<'static>, Box<dyn Any>>>>,
 }
 
 /// A key used to identify a signal in the global signal context
@@ -0,0 +1,434 @@
+use crate::{read::*, write::*};
+use crate::{read_impls, write_impls};
+use crate::{CopyValue, Global, InitializeFromFunction, ReadOnlySignal, Signal, Write};
+use dioxus_core::prelude::*;
+use generational_box::{BorrowMutError, BorrowResult, UnsyncStorage};
+use std::{collections::VecDeque, ops::Deref};
+
+/// The number of steps a [`HistorySignal`] keeps if no depth is set
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// A signal that remembers its previous values so writes can be undone and redone.
+///
+/// Every write saves a snapshot of the value from before the write. Group several writes into one step with
+/// [`HistorySignal::transaction`]. Once the history holds more steps than its maximum depth, the oldest steps are
+/// forgotten.
+///
+/// [`HistorySignal::can_undo`] and [`HistorySignal::can_redo`] are reactive, so buttons that read them rerender when the
+/// history changes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn app() -> Element {
+///     let mut text = use_history(String::new);
+///
+///     rsx! {
+///         input { value: "{text}", oninput: move |event| text.set(event.value()) }
+///         button { disabled: !text.can_undo(), onclick: move |_| { text.undo(); }, "Undo" }
+///         button { disabled: !text.can_redo(), onclick: move |_| { text.redo(); }, "Redo" }
+///     }
+/// }
+/// ```
+pub struct HistorySignal<T: 'static> {
+    value: Signal<T>,
+    history: Signal<History<T>>,
+    transaction: CopyValue<Transaction<T>>,
+}
+
+struct History<T> {
+    /// Snapshots from before each step, with the most recent step last
+    undo: VecDeque<T>,
+    /// Snapshots from before each undo, with the most recently undone step last
+    redo: Vec<T>,
+    max_depth: usize,
+}
+
+struct Transaction<T> {
+    depth: usize,
+    /// The value from before the first write in the transaction
+    snapshot: Option<T>,
+}
+
+impl<T: Clone + 'static> HistorySignal<T> {
+    /// Create a new history signal that keeps up to [`DEFAULT_HISTORY_DEPTH`] steps. The signal is owned by the current
+    /// component and dropped when the component is dropped.
+    ///
+    /// Prefer [`use_history`](https://docs.rs/dioxus-hooks/latest/dioxus_hooks/fn.use_history.html) inside of
+    /// components.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self::new_with_caller(value, DEFAULT_HISTORY_DEPTH, std::panic::Location::caller())
+    }
+
+    /// Create a new history signal that keeps up to `max_depth` steps
+    #[track_caller]
+    pub fn with_max_depth(value: T, max_depth: usize) -> Self {
+        Self::new_with_caller(value, max_depth, std::panic::Location::caller())
+    }
+
+    /// Create a new history signal with an explicit caller for debugging
+    pub fn new_with_caller(
+        value: T,
+        max_depth: usize,
+        caller: &'static std::panic::Location<'static>,
+    ) -> Self {
+        Self {
+            value: Signal::new_with_caller(value, caller),
+            history: Signal::new_with_caller(
+                History {
+                    undo: VecDeque::new(),
+                    redo: Vec::new(),
+                    max_depth,
+                },
+                caller,
+            ),
+            transaction: CopyValue::new_with_caller(
+                Transaction {
+                    depth: 0,
+                    snapshot: None,
+                },
+                caller,
+            ),
+        }
+    }
+
+    /// Run a closure, recording every write to this signal inside it as a single step. Transactions can be nested, and
+    /// the step is recorded when the outermost transaction ends.
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    ///
+    /// fn app() -> Element {
+    ///     let mut shapes = use_history(Vec::<String>::new);
+    ///
+    ///     rsx! {
+    ///         button {
+    ///             // Undoing removes both shapes at once
+    ///             onclick: move |_| shapes.transaction(move || {
+    ///                 shapes.push("circle".to_string());
+    ///                 shapes.push("square".to_string());
+    ///             }),
+    ///             "Add shapes"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn transaction<O>(&self, f: impl FnOnce() -> O) -> O {
+        self.transaction.write_unchecked().depth += 1;
+
+        // End the transaction even if the closure panics, so later writes are recorded as their own steps
+        struct EndTransaction<T: Clone + 'static>(HistorySignal<T>);
+        impl<T: Clone + 'static> Drop for EndTransaction<T> {
+            fn drop(&mut self) {
+                let snapshot = {
+                    let mut transaction = self.0.transaction.write_unchecked();
+                    transaction.depth -= 1;
+                    match transaction.depth {
+                        0 => transaction.snapshot.take(),
+                        _ => None,
+                    }
+                };
+                if let Some(snapshot) = snapshot {
+                    self.0.record(snapshot);
+                }
+            }
+        }
+        let _end = EndTransaction(*self);
+
+        f()
+    }
+
+    /// Restore the value from before the last step. Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if self.history.peek().undo.is_empty() {
+            return false;
+        }
+        let mut history = self.history.write();
+        let Some(previous) = history.undo.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut *self.value.write(), previous);
+        history.redo.push(current);
+        true
+    }
+
+    /// Restore the value from before the last undo. Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if self.history.peek().redo.is_empty() {
+            return false;
+        }
+        let mut history = self.history.write();
+        let Some(next) = history.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut *self.value.write(), next);
+        history.undo.push_back(current);
+        true
+    }
+
+    /// Save a snapshot of the value from before a step
+    fn record(&self, snapshot: T) {
+        if self.history.peek_unchecked().max_depth == 0 {
+            return;
+        }
+        let mut history = self.history.write_unchecked();
+        history.undo.push_back(snapshot);
+        while history.undo.len() > history.max_depth {
+            history.undo.pop_front();
+        }
+        history.redo.clear();
+    }
+}
+
+impl<T: 'static> HistorySignal<T> {
+    /// Check if there is a step to undo. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn can_undo(&self) -> bool {
+        !self.history.read().undo.is_empty()
+    }
+
+    /// Check if there is an undone step to redo. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn can_redo(&self) -> bool {
+        !self.history.read().redo.is_empty()
+    }
+
+    /// The number of steps that can be undone. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn undo_len(&self) -> usize {
+        self.history.read().undo.len()
+    }
+
+    /// The number of steps that can be redone. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn redo_len(&self) -> usize {
+        self.history.read().redo.len()
+    }
+
+    /// Forget every step without changing the value
+    pub fn clear_history(&mut self) {
+        let mut history = self.history.write();
+        history.undo.clear();
+        history.redo.clear();
+    }
+
+    /// Change the number of steps that are kept. If the history is longer than the new depth, the oldest steps are
+    /// forgotten.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        let mut history = self.history.write();
+        history.max_depth = max_depth;
+        let extra = history.undo.len().saturating_sub(max_depth);
+        history.undo.drain(..extra);
+    }
+
+    /// Get the generational id of the signal
+    pub fn id(&self) -> generational_box::GenerationalBoxId {
+        self.value.id()
+    }
+
+    /// Get the scope the signal was created in
+    pub fn origin_scope(&self) -> ScopeId {
+        self.value.origin_scope()
+    }
+}
+
+/// Records the snapshot from before a write once the write is dropped
+struct RecordOnDrop<T: Clone + 'static> {
+    signal: HistorySignal<T>,
+    snapshot: Option<T>,
+}
+
+impl<T: Clone + 'static> Drop for RecordOnDrop<T> {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            self.signal.record(snapshot);
+        }
+    }
+}
+
+impl<T: 'static> Readable for HistorySignal<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.value.try_read_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.value.try_peek_unchecked()
+    }
+}
+
+impl<T: Clone + 'static> Writable for HistorySignal<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(&self) -> Result<WritableRef<'static, Self>, BorrowMutError> {
+        // Inside a transaction, only the value from before the first write is saved
+        let snapshot = {
+            let mut transaction = self.transaction.try_write_unchecked()?;
+            match transaction.depth {
+                0 => Some(self.value.try_peek_unchecked()?.clone()),
+                _ => {
+                    if transaction.snapshot.is_none() {
+                        transaction.snapshot = Some(self.value.try_peek_unchecked()?.clone());
+                    }
+                    None
+                }
+            }
+        };
+        let write = self.value.try_write_unchecked()?;
+        let drop_signal: Box<dyn std::any::Any> = match snapshot {
+            Some(snapshot) => Box::new((
+                write.drop_signal,
+                RecordOnDrop {
+                    signal: *self,
+                    snapshot: Some(snapshot),
+                },
+            )),
+            None => write.drop_signal,
+        };
+        Ok(Write {
+            write: write.write,
+            drop_signal,
+        })
+    }
+}
+
+impl<T: 'static> From<HistorySignal<T>> for ReadOnlySignal<T> {
+    fn from(history: HistorySignal<T>) -> Self {
+        history.value.into()
+    }
+}
+
+impl<T: 'static> Clone for HistorySignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for HistorySignal<T> {}
+
+impl<T: 'static> PartialEq for HistorySignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> IntoAttributeValue for HistorySignal<T>
+where
+    T: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T> IntoDynNode for HistorySignal<T>
+where
+    T: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self().into_dyn_node()
+    }
+}
+
+/// Allow calling a signal with signal() syntax
+///
+/// Currently only limited to copy types, though could probably specialize for string/arc/rc
+impl<T: Clone + 'static> Deref for HistorySignal<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+read_impls!(HistorySignal<T>);
+write_impls!(HistorySignal<T> where T: Clone);
+
+impl<T: Clone + 'static> InitializeFromFunction<T> for HistorySignal<T> {
+    fn initialize_from_function(f: fn() -> T) -> Self {
+        HistorySignal::new(f())
+    }
+}
+
+/// A [`HistorySignal`] that can be accessed from anywhere in the application and created in a static. Use this to undo
+/// and redo app-wide state.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// static DOCUMENT: GlobalHistorySignal<String> = Global::new(String::new);
+///
+/// fn app() -> Element {
+///     rsx! {
+///         textarea { value: "{DOCUMENT}", oninput: move |event| *DOCUMENT.write() = event.value() }
+///         button { disabled: !DOCUMENT.can_undo(), onclick: move |_| { DOCUMENT.undo(); }, "Undo" }
+///     }
+/// }
+/// ```
+pub type GlobalHistorySignal<T> = Global<HistorySignal<T>, T>;
+
+impl<T: Clone + 'static> GlobalHistorySignal<T> {
+    /// Resolve the global signal. This will try to get the existing value from the current virtual dom, and if it doesn't exist, it will create a new one.
+    pub fn signal(&self) -> HistorySignal<T> {
+        self.resolve()
+    }
+
+    /// Run a closure, recording every write to this signal inside it as a single step
+    pub fn transaction<O>(&self, f: impl FnOnce() -> O) -> O {
+        self.resolve().transaction(f)
+    }
+
+    /// Restore the value from before the last step. Returns `false` if there is nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.resolve().undo()
+    }
+
+    /// Restore the value from before the last undo. Returns `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.resolve().redo()
+    }
+
+    /// Check if there is a step to undo. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn can_undo(&self) -> bool {
+        self.resolve().can_undo()
+    }
+
+    /// Check if there is an undone step to redo. This subscribes to changes in the history.
+    #[track_caller]
+    pub fn can_redo(&self) -> bool {
+        self.resolve().can_redo()
+    }
+}
+
+read_impls!(GlobalHistorySignal<T> where T: Clone);
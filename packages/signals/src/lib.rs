@@ -16,6 +16,9 @@ pub use read_only_signal::*;
 mod map;
 pub use map::*;
 
+mod store;
+pub use store::*;
+
 mod set_compare;
 pub use set_compare::*;
 
@@ -25,10 +28,22 @@ pub use memo::*;
 mod global;
 pub use global::*;
 
+mod history;
+pub use history::*;
+
+mod stream;
+pub use stream::*;
+
+#[cfg(feature = "persistent")]
+mod persistent;
+#[cfg(feature = "persistent")]
+pub use persistent::*;
+
 mod impls;
 
 pub use generational_box::{
-    AnyStorage, BorrowError, BorrowMutError, Owner, Storage, SyncStorage, UnsyncStorage,
+    AnyStorage, BorrowError, BorrowMutError, BoxGroup, BoxOwner, LeakReport, LeakTracker, Owner,
+    Storage, SyncStorage, UnsyncStorage,
 };
 
 mod read;
@@ -37,6 +52,9 @@ pub use read::*;
 mod write;
 pub use write::*;
 
+mod batch;
+pub use batch::{batch, is_batching};
+
 mod props;
 pub use props::*;
 
@@ -10,6 +10,7 @@ use std::{
 };
 
 use dioxus_core::prelude::*;
+use dioxus_core::{ReactiveContextKind, ReactiveGraph, ReactiveValueKind};
 use futures_util::StreamExt;
 use generational_box::{AnyStorage, BorrowResult, UnsyncStorage};
 use warnings::Warning;
@@ -77,6 +78,9 @@ impl<T: 'static> Memo<T> {
             callback: recompute,
         });
         let state: Signal<T> = Signal::new_with_caller(value, location);
+        let subscribers = state.inner.read().subscribers.clone();
+        ReactiveGraph::record_value(&subscribers, ReactiveValueKind::Memo, Some(location));
+        ReactiveGraph::record_context(&rc, ReactiveContextKind::Memo, Some(&subscribers));
 
         let memo = Memo {
             inner: state,
@@ -173,11 +177,14 @@ where
         // Read the inner generational box instead of the signal so we have more fine grained control over exactly when the subscription happens
         let read = self.inner.inner.try_read_unchecked()?;
 
-        let needs_update = self
-            .update
-            .read()
-            .dirty
-            .swap(false, std::sync::atomic::Ordering::Relaxed);
+        // Inside a batch, keep the last value until every write in the batch is finished so the memo never sees a
+        // half-applied update
+        let needs_update = !crate::is_batching()
+            && self
+                .update
+                .read()
+                .dirty
+                .swap(false, std::sync::atomic::Ordering::Relaxed);
         let result = if needs_update {
             drop(read);
             // We shouldn't be subscribed to the value here so we don't trigger the scope we are currently in to rerun even though that scope got the latest value because we synchronously update the value: https://github.com/DioxusLabs/dioxus/issues/2416
@@ -0,0 +1,135 @@
+use super::storage::{Listeners, PersistentStorage, StorageSubscription};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    rc::{Rc, Weak},
+};
+
+thread_local! {
+    /// The storages that are open for each file, so every window of a desktop app shares the same values
+    static OPEN_FILES: RefCell<HashMap<PathBuf, Weak<FileStorageInner>>> = Default::default();
+}
+
+/// A storage that saves values to a JSON file.
+///
+/// Every `FileStorage` for the same path in a process shares the same values, so signals in different windows of a
+/// desktop app are kept in sync. Changes made to the file by other processes are not picked up until the app restarts.
+///
+/// Desktop apps built with the `persistent` feature save into `persistent.json` in the directory set with
+/// `Config::with_data_directory`.
+#[derive(Clone)]
+pub struct FileStorage {
+    inner: Rc<FileStorageInner>,
+}
+
+struct FileStorageInner {
+    path: PathBuf,
+    values: RefCell<BTreeMap<String, String>>,
+    listeners: Listeners,
+}
+
+impl FileStorage {
+    /// Open the storage saved in a JSON file. The file is created when the first value is saved.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        OPEN_FILES.with(|files| {
+            let mut files = files.borrow_mut();
+            if let Some(inner) = files.get(&path).and_then(Weak::upgrade) {
+                return Self { inner };
+            }
+
+            let values = match std::fs::read(&path) {
+                Ok(contents) => serde_json::from_slice(&contents).unwrap_or_else(|err| {
+                    tracing::error!(
+                        "Failed to parse persistent storage {}: {err}",
+                        path.display()
+                    );
+                    Default::default()
+                }),
+                Err(_) => Default::default(),
+            };
+            let inner = Rc::new(FileStorageInner {
+                path: path.clone(),
+                values: RefCell::new(values),
+                listeners: Listeners::default(),
+            });
+            files.insert(path, Rc::downgrade(&inner));
+            Self { inner }
+        })
+    }
+
+    /// Open the storage saved in `persistent.json` in a directory
+    pub fn in_directory(directory: impl AsRef<Path>) -> Self {
+        Self::new(directory.as_ref().join("persistent.json"))
+    }
+
+    /// The path of the JSON file
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    fn save(&self) {
+        let path = &self.inner.path;
+        let result = (|| {
+            if let Some(directory) = path.parent() {
+                std::fs::create_dir_all(directory)?;
+            }
+            let contents = serde_json::to_vec_pretty(&*self.inner.values.borrow())?;
+            // Write to a temporary file first so the storage isn't corrupted if the app exits while saving
+            let temporary = path.with_extension("json.tmp");
+            std::fs::write(&temporary, contents)?;
+            std::fs::rename(&temporary, path)
+        })();
+        if let Err(err) = result {
+            tracing::error!(
+                "Failed to save persistent storage {}: {err}",
+                path.display()
+            );
+        }
+    }
+}
+
+impl PersistentStorage for FileStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.inner.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        let old = self
+            .inner
+            .values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        if old.as_deref() != Some(value) {
+            self.save();
+            self.inner.listeners.notify(key, Some(value));
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if self.inner.values.borrow_mut().remove(key).is_some() {
+            self.save();
+            self.inner.listeners.notify(key, None);
+        }
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn(Option<String>)>) -> StorageSubscription {
+        self.inner.listeners.subscribe(key, on_change)
+    }
+}
+
+impl Drop for FileStorageInner {
+    fn drop(&mut self) {
+        // The thread local may already be destroyed if the storage is dropped while the thread exits
+        let _ = OPEN_FILES.try_with(|files| {
+            let mut files = files.borrow_mut();
+            if files
+                .get(&self.path)
+                .is_some_and(|inner| inner.strong_count() == 0)
+            {
+                files.remove(&self.path);
+            }
+        });
+    }
+}
@@ -0,0 +1,652 @@
+use crate::{get_global_context, CopyValue, GlobalKey, ReadOnlySignal, Signal, Write};
+use crate::{read::*, write::*};
+use crate::{read_impls, write_impls};
+use dioxus_core::prelude::*;
+use generational_box::{BorrowMutError, BorrowResult, UnsyncStorage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::Deref, rc::Rc};
+
+mod storage;
+pub use storage::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::*;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
+
+/// A signal that saves its value into a [`PersistentStorage`] whenever it is written, and loads the saved value when it
+/// is created.
+///
+/// Values are serialized as JSON along with a version number. When the shape of the value changes, bump the version
+/// with [`PersistentOptions::with_version`] and add a migration from the old shape with
+/// [`PersistentOptions::with_migration`].
+///
+/// The storage is chosen in this order:
+/// 1. The storage passed to [`PersistentOptions::with_storage`]
+/// 2. A `Rc<dyn PersistentStorage>` context. Desktop apps provide a [`FileStorage`] in the directory set with
+///    `Config::with_data_directory`.
+/// 3. `LocalStorage` on the web. Other platforms fall back to a [`MemoryStorage`] that is shared by the signals of
+///    the virtual dom, and log a warning.
+///
+/// Signals with the same key are kept in sync when the value is changed in another signal, window or browser tab.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::signals::{MemoryStorage, PersistentOptions};
+///
+/// fn app() -> Element {
+///     let mut volume = use_persistent("volume", || 50);
+///     // Versioned values can be migrated from the shape that was saved by older versions of the app
+///     let mut theme = use_persistent_with(
+///         PersistentOptions::new("theme")
+///             .with_version(1)
+///             .with_migration(0, |dark: bool| if dark { "dark" } else { "light" }.to_string())
+///             .with_storage(MemoryStorage::new()),
+///         || "light".to_string(),
+///     );
+///
+///     rsx! {
+///         button { onclick: move |_| volume += 10, "Volume: {volume}" }
+///         button { onclick: move |_| theme.set("dark".to_string()), "Theme: {theme}" }
+///     }
+/// }
+/// ```
+pub struct PersistentSignal<T: 'static> {
+    signal: Signal<T>,
+    state: CopyValue<PersistentState>,
+}
+
+struct PersistentState {
+    key: String,
+    /// The key the value is saved under. This is the key unless a newer version of the app saved a value under it.
+    save_key: String,
+    storage: Rc<dyn PersistentStorage>,
+    versions: Rc<Versions>,
+    /// The last value we saved or loaded, used to ignore changes we made ourselves
+    last_saved: Option<String>,
+    subscription: Option<StorageSubscription>,
+}
+
+/// Options for creating a [`PersistentSignal`]
+pub struct PersistentOptions {
+    key: String,
+    storage: Option<Rc<dyn PersistentStorage>>,
+    versions: Versions,
+}
+
+impl PersistentOptions {
+    /// Create options for a signal saved under a key. The key must be unique within the storage.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            storage: None,
+            versions: Versions {
+                version: 0,
+                migrations: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Set the version of the saved value. The version starts at 0.
+    ///
+    /// Values saved with an older version are migrated with the migrations added in
+    /// [`PersistentOptions::with_migration`]. Values that can't be migrated are ignored.
+    ///
+    /// Values saved by a newer version of the app are never overwritten. Instead, this version loads and saves its
+    /// value under `"{key}.v{version}"`, so going back to an older version of the app doesn't lose the newer value.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.versions.version = version;
+        self
+    }
+
+    /// Add a migration from the value saved with version `from` to the value of version `from + 1`.
+    ///
+    /// Values saved with older versions run through every migration until they reach the current version.
+    pub fn with_migration<Old: DeserializeOwned, New: Serialize>(
+        mut self,
+        from: u32,
+        migrate: impl Fn(Old) -> New + 'static,
+    ) -> Self {
+        self.versions.migrations.insert(
+            from,
+            Box::new(move |value| serde_json::to_value(migrate(serde_json::from_value(value)?))),
+        );
+        self
+    }
+
+    /// Save the value in a specific storage instead of the default storage
+    pub fn with_storage(mut self, storage: impl PersistentStorage + 'static) -> Self {
+        self.storage = Some(Rc::new(storage));
+        self
+    }
+}
+
+impl From<&str> for PersistentOptions {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for PersistentOptions {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+type Migration = Box<dyn Fn(serde_json::Value) -> serde_json::Result<serde_json::Value>>;
+
+/// The current version of a value and the migrations from older versions
+struct Versions {
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+}
+
+#[derive(Serialize)]
+struct VersionedRef<'a, T> {
+    version: u32,
+    value: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Versioned {
+    version: u32,
+    value: serde_json::Value,
+}
+
+impl Versions {
+    /// Check if a saved value was saved by a newer version
+    fn is_newer(&self, raw: &str) -> bool {
+        serde_json::from_str::<Versioned>(raw).is_ok_and(|saved| saved.version > self.version)
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> serde_json::Result<String> {
+        serde_json::to_string(&VersionedRef {
+            version: self.version,
+            value,
+        })
+    }
+
+    /// Decode a saved value, returning the value and whether it was migrated from an older version
+    fn decode<T: DeserializeOwned>(&self, raw: &str) -> Result<(T, bool), String> {
+        let Versioned { version, mut value } =
+            serde_json::from_str(raw).map_err(|err| err.to_string())?;
+        if version > self.version {
+            return Err(format!(
+                "it was saved by version {version}, which is newer than version {}",
+                self.version
+            ));
+        }
+        for from in version..self.version {
+            let migrate = self
+                .migrations
+                .get(&from)
+                .ok_or_else(|| format!("there is no migration from version {from}"))?;
+            value =
+                migrate(value).map_err(|err| format!("migrating version {from} failed: {err}"))?;
+        }
+        let value = serde_json::from_value(value).map_err(|err| err.to_string())?;
+        Ok((value, version != self.version))
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> PersistentSignal<T> {
+    /// Create a new persistent signal saved under a key. If a value was saved under the key, it is used instead of
+    /// calling `init`.
+    ///
+    /// Prefer [`use_persistent`](https://docs.rs/dioxus-hooks/latest/dioxus_hooks/fn.use_persistent.html) inside of
+    /// components.
+    #[track_caller]
+    pub fn new(options: impl Into<PersistentOptions>, init: impl FnOnce() -> T) -> Self {
+        Self::new_with_caller(options.into(), init, std::panic::Location::caller())
+    }
+
+    /// Create a new persistent signal with an explicit caller for debugging
+    pub fn new_with_caller(
+        options: PersistentOptions,
+        init: impl FnOnce() -> T,
+        caller: &'static std::panic::Location<'static>,
+    ) -> Self {
+        let PersistentOptions {
+            key,
+            storage,
+            versions,
+        } = options;
+        let storage = storage.unwrap_or_else(default_storage);
+
+        let mut save_key = key.clone();
+        let mut saved = storage.get(&key);
+        if saved.as_deref().is_some_and(|raw| versions.is_newer(raw)) {
+            save_key = format!("{key}.v{}", versions.version);
+            tracing::warn!(
+                "The value of persistent signal {key:?} was saved by a newer version, so this version saves its value under {save_key:?}"
+            );
+            saved = storage.get(&save_key);
+        }
+
+        let mut last_saved = None;
+        let mut migrated = false;
+        let value = match saved.map(|raw| (versions.decode(&raw), raw)) {
+            Some((Ok((value, was_migrated)), raw)) => {
+                migrated = was_migrated;
+                last_saved = Some(raw);
+                value
+            }
+            Some((Err(err), _)) => {
+                tracing::warn!(
+                    "Ignoring the saved value of persistent signal {key:?} because {err}"
+                );
+                init()
+            }
+            None => init(),
+        };
+
+        let this = Self {
+            signal: Signal::new_with_caller(value, caller),
+            state: CopyValue::new_with_caller(
+                PersistentState {
+                    key,
+                    save_key: save_key.clone(),
+                    storage: storage.clone(),
+                    versions: Rc::new(versions),
+                    last_saved,
+                    subscription: None,
+                },
+                caller,
+            ),
+        };
+
+        let subscription = storage.subscribe(&save_key, Rc::new(move |raw| this.receive(raw)));
+        this.state.write_unchecked().subscription = Some(subscription);
+
+        // Save migrated values right away so they are only migrated once
+        if migrated {
+            this.save();
+        }
+
+        this
+    }
+
+    /// Save the current value if it changed since it was last saved
+    fn save(&self) {
+        let Ok(value) = self.signal.try_peek_unchecked() else {
+            return;
+        };
+        let Ok(mut state) = self.state.try_write_unchecked() else {
+            return;
+        };
+        let raw = match state.versions.encode(&*value) {
+            Ok(raw) => raw,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to serialize persistent signal {:?}: {err}",
+                    state.key
+                );
+                return;
+            }
+        };
+        drop(value);
+        if state.last_saved.as_ref() == Some(&raw) {
+            return;
+        }
+        state.last_saved = Some(raw.clone());
+        let storage = state.storage.clone();
+        let key = state.save_key.clone();
+        // The storage may notify other signals, so release the state before saving
+        drop(state);
+        storage.set(&key, &raw);
+    }
+
+    /// Update the value after it was changed in the storage by someone else
+    fn receive(&self, raw: Option<String>) {
+        let Ok(mut state) = self.state.try_write_unchecked() else {
+            return;
+        };
+        if state.last_saved == raw {
+            return;
+        }
+        // Removed values keep their current value until the signal is written again
+        let Some(raw) = raw else {
+            state.last_saved = None;
+            return;
+        };
+        match state.versions.decode(&raw) {
+            Ok((value, _)) => {
+                state.last_saved = Some(raw);
+                drop(state);
+                if let Ok(mut write) = self.signal.try_write_unchecked() {
+                    *write = value;
+                }
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Ignoring the new value of persistent signal {:?} because {err}",
+                    state.key
+                )
+            }
+        }
+    }
+}
+
+impl<T: 'static> PersistentSignal<T> {
+    /// The key the value is saved under
+    pub fn key(&self) -> String {
+        self.state.peek_unchecked().key.clone()
+    }
+
+    /// Remove the saved value from the storage. The signal keeps its current value until it is written again.
+    pub fn remove(&self) {
+        let (storage, key) = {
+            let mut state = self.state.write_unchecked();
+            state.last_saved = None;
+            (state.storage.clone(), state.save_key.clone())
+        };
+        storage.remove(&key);
+    }
+
+    /// Get the generational id of the signal
+    pub fn id(&self) -> generational_box::GenerationalBoxId {
+        self.signal.id()
+    }
+
+    /// Get the scope the signal was created in
+    pub fn origin_scope(&self) -> ScopeId {
+        self.signal.origin_scope()
+    }
+}
+
+/// Get the storage a signal saves into if it isn't set in the options
+fn default_storage() -> Rc<dyn PersistentStorage> {
+    if let Some(storage) = try_consume_context::<Rc<dyn PersistentStorage>>() {
+        return storage;
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        Rc::new(LocalStorage)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        static WARNING: std::sync::Once = std::sync::Once::new();
+        WARNING.call_once(|| {
+            tracing::warn!(
+                "No persistent storage was provided, so persistent signals won't be saved when the app closes. Provide a `Rc<dyn PersistentStorage>` context from the root of your app to choose where they are saved."
+            )
+        });
+        // Each virtual dom gets its own storage, so apps rendered on the same thread don't share values
+        provide_root_context(Rc::new(MemoryStorage::new()) as Rc<dyn PersistentStorage>)
+    }
+}
+
+/// Saves the signal after a write is dropped
+struct SaveOnDrop<T: Serialize + DeserializeOwned + 'static>(PersistentSignal<T>);
+
+impl<T: Serialize + DeserializeOwned + 'static> Drop for SaveOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.save();
+    }
+}
+
+impl<T: 'static> Readable for PersistentSignal<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.signal.try_read_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.signal.try_peek_unchecked()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Writable for PersistentSignal<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(&self) -> Result<WritableRef<'static, Self>, BorrowMutError> {
+        self.signal.try_write_unchecked().map(|write| Write {
+            write: write.write,
+            // The signal notifies its subscribers before the value is saved
+            drop_signal: Box::new((write.drop_signal, SaveOnDrop(*self))),
+        })
+    }
+}
+
+impl<T: 'static> From<PersistentSignal<T>> for ReadOnlySignal<T> {
+    fn from(persistent: PersistentSignal<T>) -> Self {
+        persistent.signal.into()
+    }
+}
+
+impl<T: 'static> Clone for PersistentSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for PersistentSignal<T> {}
+
+impl<T: 'static> PartialEq for PersistentSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T> IntoAttributeValue for PersistentSignal<T>
+where
+    T: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T> IntoDynNode for PersistentSignal<T>
+where
+    T: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self().into_dyn_node()
+    }
+}
+
+/// Allow calling a signal with signal() syntax
+///
+/// Currently only limited to copy types, though could probably specialize for string/arc/rc
+impl<T: Clone + 'static> Deref for PersistentSignal<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+read_impls!(PersistentSignal<T>);
+write_impls!(PersistentSignal<T> where T: Serialize, T: DeserializeOwned);
+
+/// A [`PersistentSignal`] that can be accessed from anywhere in the application and created in a static.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus::signals::GlobalPersistentSignal;
+///
+/// static HIGH_SCORE: GlobalPersistentSignal<u32> = GlobalPersistentSignal::new("high-score", || 0);
+///
+/// fn app() -> Element {
+///     rsx! {
+///         button { onclick: move |_| *HIGH_SCORE.write() += 1, "High score: {HIGH_SCORE}" }
+///     }
+/// }
+/// ```
+pub struct GlobalPersistentSignal<T> {
+    key: &'static str,
+    configure: fn(PersistentOptions) -> PersistentOptions,
+    init: fn() -> T,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> GlobalPersistentSignal<T> {
+    /// Create a new global persistent signal saved under a key
+    pub const fn new(key: &'static str, init: fn() -> T) -> Self {
+        Self {
+            key,
+            configure: |options| options,
+            init,
+        }
+    }
+
+    /// Create a new global persistent signal saved under a key, with options for the version, migrations and storage
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus::signals::GlobalPersistentSignal;
+    ///
+    /// static VOLUME: GlobalPersistentSignal<f64> = GlobalPersistentSignal::with_options(
+    ///     "volume",
+    ///     // The volume used to be saved as a percentage
+    ///     |options| options.with_version(1).with_migration(0, |percent: u32| percent as f64 / 100.0),
+    ///     || 0.5,
+    /// );
+    /// ```
+    pub const fn with_options(
+        key: &'static str,
+        configure: fn(PersistentOptions) -> PersistentOptions,
+        init: fn() -> T,
+    ) -> Self {
+        Self {
+            key,
+            configure,
+            init,
+        }
+    }
+
+    /// Resolve the global signal. This will try to get the existing value from the current virtual dom, and if it doesn't exist, it will create a new one.
+    pub fn resolve(&self) -> PersistentSignal<T> {
+        let key = GlobalKey::Raw(self.key);
+        let context = get_global_context();
+
+        if let Some(signal) = context.map.borrow().get(&key) {
+            return *signal
+                .downcast_ref::<PersistentSignal<T>>()
+                .unwrap_or_else(|| {
+                    panic!("Global persistent signal {key:?} is not of the expected type")
+                });
+        }
+
+        // Like other globals, persistent signals are always created in the root scope
+        let options = (self.configure)(PersistentOptions::new(self.key));
+        let signal = ScopeId::ROOT.in_runtime(|| PersistentSignal::new(options, self.init));
+        context.map.borrow_mut().insert(key, Box::new(signal));
+        signal
+    }
+
+    /// Get the key the value is saved under
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Write this value
+    #[track_caller]
+    pub fn write(&self) -> Write<'static, T> {
+        self.resolve().try_write_unchecked().unwrap()
+    }
+
+    /// Run a closure with a mutable reference to the signal's value.
+    /// If the signal has been dropped, this will panic.
+    #[track_caller]
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        self.resolve().with_mut(f)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Readable for GlobalPersistentSignal<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.resolve().try_read_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        self.resolve().try_peek_unchecked()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> Writable for GlobalPersistentSignal<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(&self) -> Result<WritableRef<'static, Self>, BorrowMutError> {
+        self.resolve().try_write_unchecked()
+    }
+}
+
+/// Allow calling a signal with signal() syntax
+///
+/// Currently only limited to copy types, though could probably specialize for string/arc/rc
+impl<T: Serialize + DeserializeOwned + Clone + 'static> Deref for GlobalPersistentSignal<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+read_impls!(GlobalPersistentSignal<T> where T: Serialize, T: DeserializeOwned);
@@ -0,0 +1,147 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+/// A key-value store that [`PersistentSignal`](crate::PersistentSignal)s save their values into.
+///
+/// Values are saved as JSON strings. Implement this trait to persist signals somewhere other than the built in
+/// [`MemoryStorage`], [`FileStorage`](crate::FileStorage) and
+/// [`LocalStorage`](https://docs.rs/dioxus-signals/latest/dioxus_signals/struct.LocalStorage.html) storages, and
+/// provide it as a `Rc<dyn PersistentStorage>` context from the root of your app to make it the default storage.
+pub trait PersistentStorage {
+    /// Get the value saved under a key
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Save a value under a key
+    fn set(&self, key: &str, value: &str);
+
+    /// Remove the value saved under a key
+    fn remove(&self, key: &str);
+
+    /// Call `on_change` with the new value when the value under a key is changed by someone else, like a signal in
+    /// another tab or window. The callback stops being called when the returned subscription is dropped.
+    ///
+    /// By default, storages don't report changes.
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn(Option<String>)>) -> StorageSubscription {
+        let _ = (key, on_change);
+        StorageSubscription::default()
+    }
+}
+
+/// A subscription to changes in a [`PersistentStorage`]. The subscription ends when this is dropped.
+#[derive(Default)]
+pub struct StorageSubscription {
+    unsubscribe: Option<Box<dyn FnOnce()>>,
+}
+
+impl StorageSubscription {
+    /// Create a subscription that calls `unsubscribe` when it is dropped
+    pub fn new(unsubscribe: impl FnOnce() + 'static) -> Self {
+        Self {
+            unsubscribe: Some(Box::new(unsubscribe)),
+        }
+    }
+}
+
+impl Drop for StorageSubscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+/// The callbacks subscribed to each key of a storage
+#[derive(Clone, Default)]
+pub(crate) struct Listeners {
+    next_id: Rc<Cell<usize>>,
+    listeners: Rc<RefCell<HashMap<String, Vec<(usize, Rc<dyn Fn(Option<String>)>)>>>>,
+}
+
+impl Listeners {
+    pub(crate) fn subscribe(
+        &self,
+        key: &str,
+        on_change: Rc<dyn Fn(Option<String>)>,
+    ) -> StorageSubscription {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.listeners
+            .borrow_mut()
+            .entry(key.to_string())
+            .or_default()
+            .push((id, on_change));
+
+        let key = key.to_string();
+        let listeners = Rc::downgrade(&self.listeners);
+        StorageSubscription::new(move || {
+            if let Some(listeners) = listeners.upgrade() {
+                let mut listeners = listeners.borrow_mut();
+                if let Some(subscribed) = listeners.get_mut(&key) {
+                    subscribed.retain(|(other, _)| *other != id);
+                    if subscribed.is_empty() {
+                        listeners.remove(&key);
+                    }
+                }
+            }
+        })
+    }
+
+    pub(crate) fn notify(&self, key: &str, value: Option<&str>) {
+        // Collect the callbacks first so they can subscribe or unsubscribe while they run
+        let subscribed: Vec<_> = self
+            .listeners
+            .borrow()
+            .get(key)
+            .map(|subscribed| subscribed.iter().map(|(_, f)| f.clone()).collect())
+            .unwrap_or_default();
+        for on_change in subscribed {
+            on_change(value.map(str::to_string));
+        }
+    }
+}
+
+/// A storage that keeps values in memory. Clones share the same values.
+///
+/// Nothing is saved when the app closes, which makes this storage useful for tests. Signals that share a
+/// `MemoryStorage` are kept in sync with each other.
+#[derive(Clone, Default)]
+pub struct MemoryStorage {
+    values: Rc<RefCell<HashMap<String, String>>>,
+    listeners: Listeners,
+}
+
+impl MemoryStorage {
+    /// Create a new empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PersistentStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        let old = self
+            .values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        if old.as_deref() != Some(value) {
+            self.listeners.notify(key, Some(value));
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if self.values.borrow_mut().remove(key).is_some() {
+            self.listeners.notify(key, None);
+        }
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn(Option<String>)>) -> StorageSubscription {
+        self.listeners.subscribe(key, on_change)
+    }
+}
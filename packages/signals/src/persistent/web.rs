@@ -0,0 +1,136 @@
+use super::storage::{Listeners, PersistentStorage, StorageSubscription};
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+thread_local! {
+    static LOCAL_LISTENERS: Listeners = Listeners::default();
+    static SESSION_LISTENERS: Listeners = Listeners::default();
+}
+
+/// A storage that saves values in the browser's [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+///
+/// Values are kept after the browser is closed and shared with every tab of the same origin. Signals in other tabs are
+/// updated when a value changes. This is the default storage on the web.
+#[derive(Clone, Copy, Default)]
+pub struct LocalStorage;
+
+impl LocalStorage {
+    /// Create a handle to the browser's local storage
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PersistentStorage for LocalStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        get(local_storage(), key)
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        LOCAL_LISTENERS.with(|listeners| set(local_storage(), listeners, key, value))
+    }
+
+    fn remove(&self, key: &str) {
+        LOCAL_LISTENERS.with(|listeners| remove(local_storage(), listeners, key))
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn(Option<String>)>) -> StorageSubscription {
+        LOCAL_LISTENERS.with(|listeners| subscribe(local_storage(), listeners, key, on_change))
+    }
+}
+
+/// A storage that saves values in the browser's [`sessionStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/sessionStorage).
+///
+/// Values are kept while the tab is open, including across reloads, but are not shared with other tabs.
+#[derive(Clone, Copy, Default)]
+pub struct SessionStorage;
+
+impl SessionStorage {
+    /// Create a handle to the browser's session storage
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PersistentStorage for SessionStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        get(session_storage(), key)
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        SESSION_LISTENERS.with(|listeners| set(session_storage(), listeners, key, value))
+    }
+
+    fn remove(&self, key: &str) {
+        SESSION_LISTENERS.with(|listeners| remove(session_storage(), listeners, key))
+    }
+
+    fn subscribe(&self, key: &str, on_change: Rc<dyn Fn(Option<String>)>) -> StorageSubscription {
+        SESSION_LISTENERS.with(|listeners| subscribe(session_storage(), listeners, key, on_change))
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok().flatten()
+}
+
+fn get(storage: Option<web_sys::Storage>, key: &str) -> Option<String> {
+    storage?.get_item(key).ok().flatten()
+}
+
+fn set(storage: Option<web_sys::Storage>, listeners: &Listeners, key: &str, value: &str) {
+    let Some(storage) = storage else {
+        return;
+    };
+    if storage.get_item(key).ok().flatten().as_deref() == Some(value) {
+        return;
+    }
+    if let Err(err) = storage.set_item(key, value) {
+        tracing::error!("Failed to save {key} to web storage: {err:?}");
+        return;
+    }
+    // The browser only sends storage events to other documents, so notify signals in this document ourselves
+    listeners.notify(key, Some(value));
+}
+
+fn remove(storage: Option<web_sys::Storage>, listeners: &Listeners, key: &str) {
+    let Some(storage) = storage else {
+        return;
+    };
+    if storage.get_item(key).ok().flatten().is_some() && storage.remove_item(key).is_ok() {
+        listeners.notify(key, None);
+    }
+}
+
+fn subscribe(
+    storage: Option<web_sys::Storage>,
+    listeners: &Listeners,
+    key: &str,
+    on_change: Rc<dyn Fn(Option<String>)>,
+) -> StorageSubscription {
+    let local = listeners.subscribe(key, on_change.clone());
+    let (Some(window), Some(storage)) = (web_sys::window(), storage) else {
+        return local;
+    };
+
+    let key = key.to_string();
+    let listener =
+        Closure::<dyn FnMut(web_sys::StorageEvent)>::new(move |event: web_sys::StorageEvent| {
+            if event.key().as_deref() == Some(key.as_str())
+                && event.storage_area().as_ref() == Some(&storage)
+            {
+                on_change(event.new_value());
+            }
+        });
+    let _ = window.add_event_listener_with_callback("storage", listener.as_ref().unchecked_ref());
+
+    StorageSubscription::new(move || {
+        let _ = window
+            .remove_event_listener_with_callback("storage", listener.as_ref().unchecked_ref());
+        drop(local);
+    })
+}
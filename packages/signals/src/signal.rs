@@ -2,6 +2,7 @@ use crate::{default_impl, fmt_impls, write_impls, Global};
 use crate::{read::*, write::*, CopyValue, GlobalMemo, GlobalSignal, ReadableRef};
 use crate::{Memo, WritableRef};
 use dioxus_core::prelude::*;
+use dioxus_core::{ReactiveGraph, ReactiveValueKind};
 use generational_box::{AnyStorage, BorrowResult, Storage, SyncStorage, UnsyncStorage};
 use std::sync::Arc;
 use std::{
@@ -243,16 +244,9 @@ impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
     }
 
     fn update_subscribers(&self) {
-        {
-            let inner = self.inner.read();
-
-            // We cannot hold the subscribers lock while calling mark_dirty, because mark_dirty can run user code which may cause a new subscriber to be added. If we hold the lock, we will deadlock.
-            #[allow(clippy::mutable_key_type)]
-            let mut subscribers = std::mem::take(&mut *inner.subscribers.lock().unwrap());
-            subscribers.retain(|reactive_context| reactive_context.mark_dirty());
-            // Extend the subscribers list instead of overwriting it in case a subscriber is added while reactive contexts are marked dirty
-            inner.subscribers.lock().unwrap().extend(subscribers);
-        }
+        let subscribers = self.inner.read().subscribers.clone();
+        ReactiveGraph::record_write(&subscribers);
+        crate::batch::mark_dirty(&subscribers);
     }
 
     /// Get the generational id of the signal.
@@ -392,6 +386,11 @@ impl<T, S: Storage<SignalData<T>>> Readable for Signal<T, S> {
 
         if let Some(reactive_context) = ReactiveContext::current() {
             tracing::trace!("Subscribing to the reactive context {}", reactive_context);
+            ReactiveGraph::record_value(
+                &inner.subscribers,
+                ReactiveValueKind::Signal,
+                self.inner.value.created_at(),
+            );
             reactive_context.subscribe(inner.subscribers.clone());
         }
 
@@ -621,8 +620,8 @@ impl<'de, T: serde::Deserialize<'de> + 'static, Store: Storage<SignalData<T>>>
 /// - T is the current type of the write
 /// - S is the storage type of the signal. This type determines if the signal is local to the current thread, or it can be shared across threads.
 pub struct Write<'a, T: ?Sized + 'static, S: AnyStorage = UnsyncStorage> {
-    write: S::Mut<'a, T>,
-    drop_signal: Box<dyn Any>,
+    pub(crate) write: S::Mut<'a, T>,
+    pub(crate) drop_signal: Box<dyn Any>,
 }
 
 impl<'a, T: ?Sized + 'static, S: AnyStorage> Write<'a, T, S> {
@@ -0,0 +1,557 @@
+use crate::{fmt_impls, write_impls, CopyValue, Write};
+use crate::{read::*, write::*};
+use dioxus_core::prelude::*;
+use dioxus_core::{ReactiveGraph, ReactiveValueKind};
+use generational_box::{AnyStorage, BorrowMutError, BorrowResult, UnsyncStorage};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+/// A store is a reactive value, like a [`Signal`](crate::Signal), that tracks reads and writes to each part of the value
+/// separately.
+///
+/// Selecting a field, an index of a `Vec` or a key of a `HashMap` or `BTreeMap` returns a new store for that part of the
+/// value. Reading a store only subscribes to that part of the value, and writing a store only notifies readers of that
+/// part, the parts inside it, and the parts that contain it. Writing one todo in a list of todos won't rerun components
+/// that only read a different todo.
+///
+/// Derive `Store` on a struct to select its fields:
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Store)]
+/// struct TodoList {
+///     title: String,
+///     todos: Vec<Todo>,
+/// }
+///
+/// #[derive(Store)]
+/// struct Todo {
+///     label: String,
+///     done: bool,
+/// }
+///
+/// fn app() -> Element {
+///     let list = use_store(|| TodoList {
+///         title: "Groceries".to_string(),
+///         todos: vec![Todo { label: "Milk".to_string(), done: false }],
+///     });
+///
+///     rsx! {
+///         h1 { "{list.title()}" }
+///         for todo in list.todos().iter() {
+///             TodoItem { todo }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn TodoItem(todo: Store<Todo>) -> Element {
+///     // This component only reruns when this todo changes
+///     rsx! {
+///         input {
+///             r#type: "checkbox",
+///             checked: todo.done(),
+///             onchange: move |event| todo.done().set(event.checked()),
+///         }
+///         "{todo.label()}"
+///     }
+/// }
+/// ```
+pub struct Store<T: 'static> {
+    root: CopyValue<StoreRoot>,
+    /// The segments that select this part of the value from the root, used to find the subscribers of this part
+    path: Rc<[u64]>,
+    select: Selector<T>,
+    select_mut: SelectorMut<T>,
+}
+
+/// The value and subscriptions shared by every store selected from the same root
+struct StoreRoot {
+    value: Box<dyn Any>,
+    subscriptions: RefCell<StoreNode>,
+}
+
+type Subscribers = Arc<Mutex<HashSet<ReactiveContext>>>;
+
+/// The subscribers to one part of the value
+#[derive(Default)]
+struct StoreNode {
+    /// Subscribers that read the whole value, including everything inside it
+    deep: Subscribers,
+    /// Subscribers that only read the shape of the value, like the length of a `Vec`
+    shallow: Subscribers,
+    children: HashMap<u64, StoreNode>,
+}
+
+impl StoreNode {
+    fn collect(&self, subscribers: &mut Vec<Subscribers>) {
+        subscribers.push(self.deep.clone());
+        subscribers.push(self.shallow.clone());
+        for child in self.children.values() {
+            child.collect(subscribers);
+        }
+    }
+
+    /// Remove children without any subscribers. Returns true if this node is empty.
+    fn prune(&mut self) -> bool {
+        self.children.retain(|_, child| !child.prune());
+        self.children.is_empty()
+            && self.deep.lock().unwrap().is_empty()
+            && self.shallow.lock().unwrap().is_empty()
+    }
+}
+
+impl StoreRoot {
+    fn node(&self, path: &[u64], deep: bool) -> Subscribers {
+        let mut node = &mut *self.subscriptions.borrow_mut();
+        for segment in path {
+            node = node.children.entry(*segment).or_default();
+        }
+        match deep {
+            true => node.deep.clone(),
+            false => node.shallow.clone(),
+        }
+    }
+
+    /// Find the subscribers that need to rerun after a write to `path`. If `children` is `None`, every part inside the
+    /// value is assumed to have changed. Otherwise only the listed children and the shape of the value changed.
+    fn changed(&self, path: &[u64], children: Option<&[u64]>) -> Vec<Subscribers> {
+        let mut subscribers = Vec::new();
+        let tree = self.subscriptions.borrow();
+        let mut node = &*tree;
+        for segment in path {
+            subscribers.push(node.deep.clone());
+            match node.children.get(segment) {
+                Some(child) => node = child,
+                None => return subscribers,
+            }
+        }
+
+        subscribers.push(node.deep.clone());
+        subscribers.push(node.shallow.clone());
+        match children {
+            None => node
+                .children
+                .values()
+                .for_each(|child| child.collect(&mut subscribers)),
+            Some(children) => children
+                .iter()
+                .filter_map(|segment| node.children.get(segment))
+                .for_each(|child| child.collect(&mut subscribers)),
+        }
+        subscribers
+    }
+
+    fn prune(&self, path: &[u64]) {
+        let mut node = &mut *self.subscriptions.borrow_mut();
+        for segment in path {
+            match node.children.get_mut(segment) {
+                Some(child) => node = child,
+                None => return,
+            }
+        }
+        node.prune();
+    }
+}
+
+impl<T: 'static> Store<T> {
+    /// Create a new store. The store is owned by the current component and dropped when the component is dropped.
+    ///
+    /// Prefer [`use_store`](https://docs.rs/dioxus-hooks/latest/dioxus_hooks/fn.use_store.html) inside of components.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self::new_with_caller(value, std::panic::Location::caller())
+    }
+
+    /// Create a new store with an explicit caller for debugging.
+    pub fn new_with_caller(value: T, caller: &'static std::panic::Location<'static>) -> Self {
+        let root = CopyValue::new_with_caller(
+            StoreRoot {
+                value: Box::new(value),
+                subscriptions: Default::default(),
+            },
+            caller,
+        );
+        Self {
+            root,
+            path: Rc::from([]),
+            select: selector(|root| root.downcast_ref()),
+            select_mut: selector_mut(|root| root.downcast_mut()),
+        }
+    }
+
+    /// Select a part of the value. `segment` identifies the part among the other parts that can be selected from this
+    /// value, like the index of a field.
+    ///
+    /// If `select` returns `None`, reading the returned store panics. This is used by `#[derive(Store)]` and the
+    /// collection methods, but can also select parts of custom types:
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    ///
+    /// struct Point(f64, f64);
+    ///
+    /// fn x(point: &Store<Point>) -> Store<f64> {
+    ///     point.project(0, |point| Some(&point.0), |point| Some(&mut point.0))
+    /// }
+    /// ```
+    pub fn project<U: 'static>(
+        &self,
+        segment: u64,
+        select: impl Fn(&T) -> Option<&U> + 'static,
+        select_mut: impl Fn(&mut T) -> Option<&mut U> + 'static,
+    ) -> Store<U> {
+        let parent = self.select.clone();
+        let parent_mut = self.select_mut.clone();
+        let path = self.path.iter().copied().chain([segment]).collect();
+        Store {
+            root: self.root,
+            path,
+            select: selector(move |root| parent(root).and_then(&select)),
+            select_mut: selector_mut(move |root| parent_mut(root).and_then(&select_mut)),
+        }
+    }
+
+    /// Run a function with the value, only subscribing to changes in the shape of the value
+    fn read_shallow<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            self.subscribe(
+                reactive_context,
+                self.root.read_unchecked().node(&self.path, false),
+            );
+        }
+        f(&*self.peek())
+    }
+
+    /// Subscribe to changes in a part of the value
+    fn subscribe_child(&self, segment: u64) {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            let path: Vec<_> = self.path.iter().copied().chain([segment]).collect();
+            self.subscribe(
+                reactive_context,
+                self.root.read_unchecked().node(&path, false),
+            );
+        }
+    }
+
+    fn subscribe(&self, reactive_context: ReactiveContext, subscribers: Subscribers) {
+        ReactiveGraph::record_value(
+            &subscribers,
+            ReactiveValueKind::Store,
+            self.root.value.created_at(),
+        );
+        reactive_context.subscribe(subscribers);
+    }
+
+    #[track_caller]
+    fn try_write_changing(
+        &self,
+        children: Option<Vec<u64>>,
+    ) -> Result<Write<'static, T, UnsyncStorage>, BorrowMutError> {
+        let select_mut = self.select_mut.clone();
+        let root = self.root.try_write_unchecked()?;
+        Ok(Write {
+            write: UnsyncStorage::map_mut(root, move |root| {
+                select_mut(&mut *root.value).unwrap_or_else(|| missing_path())
+            }),
+            drop_signal: Box::new(StoreWriteDrop {
+                root: self.root,
+                path: self.path.clone(),
+                children,
+            }),
+        })
+    }
+
+    /// Write to the value, only notifying the readers of the shape of the value and the listed children
+    #[track_caller]
+    fn write_changing(&self, children: Vec<u64>) -> Write<'static, T, UnsyncStorage> {
+        self.try_write_changing(Some(children)).unwrap()
+    }
+}
+
+type Selector<T> = Rc<dyn for<'a> Fn(&'a (dyn Any + 'static)) -> Option<&'a T>>;
+type SelectorMut<T> = Rc<dyn for<'a> Fn(&'a mut (dyn Any + 'static)) -> Option<&'a mut T>>;
+
+// These functions make closures infer a signature that is generic over the lifetime of the root
+fn selector<T>(
+    select: impl for<'a> Fn(&'a (dyn Any + 'static)) -> Option<&'a T> + 'static,
+) -> Selector<T> {
+    Rc::new(select)
+}
+
+fn selector_mut<T>(
+    select_mut: impl for<'a> Fn(&'a mut (dyn Any + 'static)) -> Option<&'a mut T> + 'static,
+) -> SelectorMut<T> {
+    Rc::new(select_mut)
+}
+
+#[track_caller]
+fn missing_path() -> ! {
+    panic!("Tried to read a part of a store that no longer exists. The index or key may have been removed.")
+}
+
+/// Marks the subscribers of a store dirty when a write finishes
+struct StoreWriteDrop {
+    root: CopyValue<StoreRoot>,
+    path: Rc<[u64]>,
+    children: Option<Vec<u64>>,
+}
+
+impl Drop for StoreWriteDrop {
+    fn drop(&mut self) {
+        let Ok(root) = self.root.try_read_unchecked() else {
+            return;
+        };
+        let changed = root.changed(&self.path, self.children.as_deref());
+        drop(root);
+
+        for subscribers in changed {
+            ReactiveGraph::record_write(&subscribers);
+            crate::batch::mark_dirty(&subscribers);
+        }
+
+        if let Ok(root) = self.root.try_read_unchecked() {
+            root.prune(&self.path);
+        }
+    }
+}
+
+impl<T: 'static> Readable for Store<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        let root = self.root.try_read_unchecked()?;
+        if let Some(reactive_context) = ReactiveContext::current() {
+            self.subscribe(reactive_context, root.node(&self.path, true));
+        }
+        let select = self.select.clone();
+        Ok(UnsyncStorage::map(root, move |root| {
+            select(&*root.value).unwrap_or_else(|| missing_path())
+        }))
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> BorrowResult<ReadableRef<'static, Self>> {
+        let root = self.root.try_read_unchecked()?;
+        let select = self.select.clone();
+        Ok(UnsyncStorage::map(root, move |root| {
+            select(&*root.value).unwrap_or_else(|| missing_path())
+        }))
+    }
+}
+
+impl<T: 'static> Writable for Store<T> {
+    type Mut<'a, R: ?Sized + 'static> = Write<'a, R, UnsyncStorage>;
+
+    fn map_mut<I: ?Sized, U: ?Sized + 'static, F: FnOnce(&mut I) -> &mut U>(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Self::Mut<'_, U> {
+        Write::map(ref_, f)
+    }
+
+    fn try_map_mut<
+        I: ?Sized + 'static,
+        U: ?Sized + 'static,
+        F: FnOnce(&mut I) -> Option<&mut U>,
+    >(
+        ref_: Self::Mut<'_, I>,
+        f: F,
+    ) -> Option<Self::Mut<'_, U>> {
+        Write::filter_map(ref_, f)
+    }
+
+    fn downcast_lifetime_mut<'a: 'b, 'b, R: ?Sized + 'static>(
+        mut_: Self::Mut<'a, R>,
+    ) -> Self::Mut<'b, R> {
+        Write::downcast_lifetime(mut_)
+    }
+
+    #[track_caller]
+    fn try_write_unchecked(&self) -> Result<WritableRef<'static, Self>, BorrowMutError> {
+        self.try_write_changing(None)
+    }
+}
+
+impl<T: 'static> Store<Vec<T>> {
+    /// Select the item at an index
+    pub fn index(&self, index: usize) -> Store<T> {
+        self.project(
+            index as u64,
+            move |items| items.get(index),
+            move |items| items.get_mut(index),
+        )
+    }
+
+    /// The number of items. This only subscribes to changes in the length, not the items.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.read_shallow(|items| items.len())
+    }
+
+    /// Check if there are no items. This only subscribes to changes in the length, not the items.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Select every item. This only subscribes to changes in the length, not the items.
+    #[track_caller]
+    pub fn iter(&self) -> impl Iterator<Item = Store<T>> {
+        let this = self.clone();
+        (0..self.len()).map(move |index| this.index(index))
+    }
+
+    /// Add an item to the end. Readers of the other items are not notified.
+    #[track_caller]
+    pub fn push(&mut self, item: T) {
+        self.write_changing(Vec::new()).push(item);
+    }
+
+    /// Remove the last item. Readers of the other items are not notified.
+    #[track_caller]
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.peek().len().checked_sub(1)?;
+        self.write_changing(vec![last as u64]).pop()
+    }
+}
+
+macro_rules! map_impls {
+    ($map:ident, $($key_bound:tt)*) => {
+        impl<K: $($key_bound)* + Hash + Clone + 'static, V: 'static> Store<$map<K, V>> {
+            /// Select the value of a key. This subscribes to the key being inserted or removed.
+            #[track_caller]
+            pub fn get(&self, key: K) -> Option<Store<V>> {
+                let segment = key_segment(&key);
+                self.subscribe_child(segment);
+                if !self.peek().contains_key(&key) {
+                    return None;
+                }
+                let key_mut = key.clone();
+                Some(self.project(
+                    segment,
+                    move |map| map.get(&key),
+                    move |map| map.get_mut(&key_mut),
+                ))
+            }
+
+            /// Check if the map contains a key. This subscribes to the key being inserted or removed.
+            #[track_caller]
+            pub fn contains_key(&self, key: &K) -> bool {
+                self.subscribe_child(key_segment(key));
+                self.peek().contains_key(key)
+            }
+
+            /// The number of entries. This only subscribes to entries being inserted or removed, not the values.
+            #[track_caller]
+            pub fn len(&self) -> usize {
+                self.read_shallow(|map| map.len())
+            }
+
+            /// Check if there are no entries. This only subscribes to entries being inserted or removed, not the values.
+            #[track_caller]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Select every entry. This only subscribes to entries being inserted or removed, not the values.
+            #[track_caller]
+            pub fn iter(&self) -> impl Iterator<Item = (K, Store<V>)> {
+                let keys: Vec<K> = self.read_shallow(|map| map.keys().cloned().collect());
+                let this = self.clone();
+                keys.into_iter().map(move |key| {
+                    let (key_ref, key_mut) = (key.clone(), key.clone());
+                    let value = this.project(
+                        key_segment(&key),
+                        move |map| map.get(&key_ref),
+                        move |map| map.get_mut(&key_mut),
+                    );
+                    (key, value)
+                })
+            }
+
+            /// Insert a value, returning the old value. Only readers of this key are notified.
+            #[track_caller]
+            pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+                self.write_changing(vec![key_segment(&key)]).insert(key, value)
+            }
+
+            /// Remove a key, returning its value. Only readers of this key are notified.
+            #[track_caller]
+            pub fn remove(&mut self, key: &K) -> Option<V> {
+                if !self.peek().contains_key(key) {
+                    return None;
+                }
+                self.write_changing(vec![key_segment(key)]).remove(key)
+            }
+        }
+    };
+}
+
+map_impls!(HashMap, Eq);
+map_impls!(BTreeMap, Ord);
+
+fn key_segment<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T: 'static> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            path: self.path.clone(),
+            select: self.select.clone(),
+            select_mut: self.select_mut.clone(),
+        }
+    }
+}
+
+impl<T: 'static> PartialEq for Store<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.path == other.path
+    }
+}
+
+impl<T> IntoAttributeValue for Store<T>
+where
+    T: Clone + IntoAttributeValue,
+{
+    fn into_value(self) -> dioxus_core::AttributeValue {
+        self.with(|f| f.clone().into_value())
+    }
+}
+
+impl<T> IntoDynNode for Store<T>
+where
+    T: Clone + IntoDynNode,
+{
+    fn into_dyn_node(self) -> dioxus_core::DynamicNode {
+        self.cloned().into_dyn_node()
+    }
+}
+
+/// Allow calling a store with store() syntax
+///
+/// Currently only limited to clone types, though could probably specialize for string/arc/rc
+impl<T: Clone + 'static> Deref for Store<T> {
+    type Target = dyn Fn() -> T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { Readable::deref_impl(self) }
+    }
+}
+
+fmt_impls!(Store<T>);
+write_impls!(Store<T>);
@@ -0,0 +1,142 @@
+use crate::{Readable, SyncSignal};
+use dioxus_core::prelude::{ReactiveContext, Runtime};
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::{Stream, StreamExt};
+use generational_box::{AnyStorage, GenerationalBox, Owner, SyncStorage};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl<T: Clone + Send + Sync + 'static> SyncSignal<T> {
+    /// Create a [`Stream`] that yields the current value of the signal and then the new value every time the signal
+    /// is written to.
+    ///
+    /// The stream doesn't need the dioxus runtime. It can be created on any thread and moved to an async runtime like
+    /// tokio to observe the state of the UI from the rest of your app. If the signal is written to several times before
+    /// the stream is polled, the stream only yields the latest value.
+    ///
+    /// ```rust, no_run
+    /// use dioxus::prelude::*;
+    /// use futures_util::StreamExt;
+    ///
+    /// fn app() -> Element {
+    ///     let mut query = use_signal_sync(String::new);
+    ///
+    ///     use_hook(|| {
+    ///         let mut changes = query.subscribe();
+    ///         // The stream is Send, so it can be moved to a task on a multithreaded runtime
+    ///         tokio::spawn(async move {
+    ///             while let Some(query) = changes.next().await {
+    ///                 println!("The search changed to {query}");
+    ///             }
+    ///         });
+    ///     });
+    ///
+    ///     rsx! {
+    ///         input { value: "{query}", oninput: move |event| query.set(event.value()) }
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn subscribe(&self) -> SignalStream<T> {
+        SignalStream::new(*self, std::panic::Location::caller())
+    }
+}
+
+/// A [`Stream`] of the values of a signal created with [`Signal::subscribe`](crate::Signal::subscribe).
+///
+/// The stream ends when the component that owns the signal is dropped. Streams created outside of the dioxus runtime
+/// end the next time they are polled after the signal is dropped.
+pub struct SignalStream<T: Send + Sync + 'static> {
+    signal: SyncSignal<T>,
+    changed: UnboundedReceiver<()>,
+    started: bool,
+    /// The stream owns its reactive context, so the context unsubscribes from the signal when the stream is dropped
+    _owner: Owner<SyncStorage>,
+    /// Ends the stream when the owner of the signal is dropped
+    close_on_drop: Option<GenerationalBox<CloseOnDrop, SyncStorage>>,
+}
+
+/// Closes the channel of a stream when it is dropped, which wakes the stream if it is waiting for a change
+struct CloseOnDrop(UnboundedSender<()>);
+
+impl Drop for CloseOnDrop {
+    fn drop(&mut self) {
+        self.0.close_channel();
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SignalStream<T> {
+    fn new(signal: SyncSignal<T>, origin: &'static std::panic::Location<'static>) -> Self {
+        let (tx, changed) = futures_channel::mpsc::unbounded();
+        // The scope of a signal that still exists is still mounted, so its owner can hold the guard
+        let close_on_drop =
+            (Runtime::current().is_ok() && signal.inner.try_peek().is_ok()).then(|| {
+                signal
+                    .origin_scope()
+                    .owner()
+                    .insert(CloseOnDrop(tx.clone()))
+            });
+        let owner = SyncStorage::owner();
+        let context = ReactiveContext::new_with_owner(
+            move || {
+                // If there is already an update queued, we don't need to queue another
+                if tx.is_empty() {
+                    let _ = tx.unbounded_send(());
+                }
+            },
+            signal.origin_scope(),
+            &owner,
+            origin,
+        );
+        if let Ok(data) = signal.inner.try_peek() {
+            context.subscribe(data.subscribers.clone());
+        }
+
+        Self {
+            signal,
+            changed,
+            started: false,
+            _owner: owner,
+            close_on_drop,
+        }
+    }
+
+    /// Clear the queued updates and read the latest value
+    fn latest(&mut self) -> Option<T> {
+        while let Ok(Some(())) = self.changed.try_next() {}
+        self.signal.try_peek().ok().map(|value| value.clone())
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for SignalStream<T> {
+    fn drop(&mut self) {
+        if let Some(close_on_drop) = self.close_on_drop.take() {
+            close_on_drop.manually_drop();
+        }
+    }
+}
+
+// The stream never pins the value of the signal
+impl<T: Send + Sync + 'static> Unpin for SignalStream<T> {}
+
+impl<T: Clone + Send + Sync + 'static> Stream for SignalStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if !std::mem::replace(&mut this.started, true) {
+            return Poll::Ready(this.latest());
+        }
+        match this.changed.poll_next_unpin(cx) {
+            Poll::Ready(Some(())) => Poll::Ready(this.latest()),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => match this.signal.try_peek() {
+                // The signal can't change anymore once it is dropped
+                Err(_) => Poll::Ready(None),
+                Ok(_) => Poll::Pending,
+            },
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn memos_only_see_finished_batches() {
+    let computed = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |computed: Rc<RefCell<Vec<String>>>| {
+            let mut first = use_signal(|| "Jane".to_string());
+            let mut last = use_signal(|| "Doe".to_string());
+            let full_name = use_memo(move || {
+                let name = format!("{first} {last}");
+                computed.borrow_mut().push(name.clone());
+                name
+            });
+
+            batch(|| {
+                first.set("John".to_string());
+                last.set("Smith".to_string());
+                assert_eq!(full_name(), "Jane Doe");
+            });
+            assert_eq!(full_name(), "John Smith");
+
+            rsx! {}
+        },
+        computed.clone(),
+    );
+    dom.rebuild_in_place();
+
+    assert_eq!(*computed.borrow(), ["Jane Doe", "John Smith"]);
+}
+
+#[test]
+fn nested_batches_notify_once_at_the_end() {
+    let mut dom = VirtualDom::new(|| rsx! {});
+    dom.rebuild_in_place();
+
+    dom.in_runtime(|| {
+        ScopeId::APP.in_runtime(|| {
+            let mut a = Signal::new(0);
+            let mut b = Signal::new(0);
+
+            let notified = Arc::new(AtomicUsize::new(0));
+            let context = ReactiveContext::new_with_callback(
+                {
+                    let notified = notified.clone();
+                    move || {
+                        notified.fetch_add(1, Ordering::Relaxed);
+                    }
+                },
+                ScopeId::APP,
+                std::panic::Location::caller(),
+            );
+            context.run_in(|| a() + b());
+
+            batch(|| {
+                a += 1;
+                batch(|| b += 1);
+                assert!(is_batching());
+                a += 1;
+                assert_eq!(notified.load(Ordering::Relaxed), 0);
+            });
+            assert!(!is_batching());
+            assert_eq!(notified.load(Ordering::Relaxed), 1);
+
+            // Writes outside of a batch notify right away
+            a += 1;
+            assert_eq!(notified.load(Ordering::Relaxed), 2);
+        })
+    });
+}
+
+#[test]
+fn panicking_ends_the_batch() {
+    let result = std::panic::catch_unwind(|| batch(|| panic!("oops")));
+    assert!(result.is_err());
+    assert!(!is_batching());
+}
@@ -0,0 +1,140 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Create a history signal in a component and run a test against it in the runtime
+fn test_history<T: Clone + 'static>(
+    init: fn() -> T,
+    max_depth: usize,
+    test: impl FnOnce(HistorySignal<T>),
+) {
+    let signal = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        move |signal: Rc<RefCell<Option<HistorySignal<T>>>>| {
+            signal
+                .borrow_mut()
+                .replace(use_history_with_max_depth(max_depth, init));
+            rsx! {}
+        },
+        signal.clone(),
+    );
+    dom.rebuild_in_place();
+    let history = signal.borrow().unwrap();
+    dom.in_runtime(|| ScopeId::APP.in_runtime(|| test(history)));
+}
+
+#[test]
+fn writes_can_be_undone_and_redone() {
+    test_history(
+        || 0,
+        10,
+        |mut count| {
+            assert!(!count.can_undo());
+            count += 1;
+            count.set(5);
+            assert_eq!(count(), 5);
+            assert_eq!(count.undo_len(), 2);
+
+            assert!(count.undo());
+            assert_eq!(count(), 1);
+            assert!(count.undo());
+            assert_eq!(count(), 0);
+            assert!(!count.undo());
+
+            assert!(count.redo());
+            assert_eq!(count(), 1);
+
+            // A new write forgets the undone steps
+            count.set(2);
+            assert!(!count.can_redo());
+            assert!(!count.redo());
+            assert_eq!(count(), 2);
+        },
+    );
+}
+
+#[test]
+fn transactions_are_a_single_step() {
+    test_history(Vec::new, 10, |mut items| {
+        items.transaction(move || {
+            items.push(1);
+            items.transaction(move || items.push(2));
+            items.push(3);
+        });
+        assert_eq!(items(), [1, 2, 3]);
+        assert_eq!(items.undo_len(), 1);
+
+        items.undo();
+        assert_eq!(items(), Vec::<i32>::new());
+    });
+}
+
+#[test]
+fn panicking_ends_the_transaction() {
+    test_history(Vec::new, 10, |mut items| {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+            items.transaction(move || {
+                items.push(1);
+                panic!("oops");
+            })
+        }));
+        assert!(result.is_err());
+        // The writes before the panic are still a step
+        assert_eq!(items.undo_len(), 1);
+
+        items.push(2);
+        assert_eq!(items.undo_len(), 2);
+        items.undo();
+        assert_eq!(items(), [1]);
+    });
+}
+
+#[test]
+fn history_depth_is_bounded() {
+    test_history(
+        || 0,
+        3,
+        |mut count| {
+            for value in 1..=5 {
+                count.set(value);
+            }
+            assert_eq!(count.undo_len(), 3);
+            while count.undo() {}
+            assert_eq!(count(), 2);
+
+            count.set_max_depth(1);
+            assert_eq!(count.undo_len(), 0);
+            assert_eq!(count.redo_len(), 3);
+        },
+    );
+}
+
+#[test]
+fn can_undo_is_reactive() {
+    thread_local! {
+        static RENDERS: Cell<usize> = const { Cell::new(0) };
+    }
+    static HISTORY: GlobalHistorySignal<i32> = Global::new(|| 0);
+
+    let mut dom = VirtualDom::new(|| {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        let can_undo = HISTORY.can_undo();
+        rsx! { "{can_undo}" }
+    });
+    dom.rebuild_in_place();
+
+    dom.in_runtime(|| *HISTORY.write() += 1);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(RENDERS.with(Cell::get), 2);
+
+    dom.in_runtime(|| {
+        assert!(HISTORY.undo());
+        assert_eq!(HISTORY(), 0);
+        assert!(HISTORY.can_redo());
+    });
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(RENDERS.with(Cell::get), 3);
+}
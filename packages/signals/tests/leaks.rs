@@ -0,0 +1,53 @@
+// Boxes are only tracked in debug builds
+#![cfg(debug_assertions)]
+
+use dioxus::prelude::*;
+
+#[test]
+fn scope_owners_are_labeled_in_leak_reports() {
+    LeakTracker::enable();
+    let mut dom = VirtualDom::new(|| {
+        let count = use_signal(|| 0u8);
+        rsx! { "{count}" }
+    });
+    dom.rebuild_in_place();
+
+    let owned_by_scope = |report: &LeakReport| {
+        report.groups().iter().any(|group| {
+            group.type_name.contains("SignalData<u8>")
+                && matches!(&group.owner, BoxOwner::Owned(Some(label)) if label.starts_with("ScopeId("))
+        })
+    };
+    assert!(owned_by_scope(&LeakTracker::report()));
+
+    drop(dom);
+    assert!(!owned_by_scope(&LeakTracker::report()));
+}
+
+#[test]
+fn leak_reports_only_include_the_scopes_of_the_dom() {
+    LeakTracker::enable();
+    let mut first = VirtualDom::new(|| {
+        let count = use_signal(|| 0u16);
+        rsx! { "{count}" }
+    });
+    first.rebuild_in_place();
+    let mut second = VirtualDom::new(|| {
+        let count = use_signal(|| 0u32);
+        rsx! { "{count}" }
+    });
+    second.rebuild_in_place();
+
+    let has_type = |report: &LeakReport, ty: &str| {
+        report
+            .groups()
+            .iter()
+            .any(|group| group.type_name.contains(&format!("SignalData<{ty}>")))
+    };
+    let report = first.leak_report();
+    assert!(has_type(&report, "u16"));
+    assert!(!has_type(&report, "u32"));
+    let report = second.leak_report();
+    assert!(has_type(&report, "u32"));
+    assert!(!has_type(&report, "u16"));
+}
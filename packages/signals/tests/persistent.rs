@@ -0,0 +1,237 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Run a closure in the app component of a virtual dom
+fn in_app<O>(dom: &VirtualDom, f: impl FnOnce() -> O) -> O {
+    dom.in_runtime(|| ScopeId::APP.in_runtime(f))
+}
+
+#[test]
+fn values_are_loaded_and_saved() {
+    let storage = MemoryStorage::new();
+    storage.set("count", r#"{"version":0,"value":5}"#);
+
+    let mut dom = VirtualDom::new_with_props(
+        |storage: MemoryStorage| {
+            let mut count =
+                use_persistent_with(PersistentOptions::new("count").with_storage(storage), || 0);
+            assert_eq!(count(), 5);
+            count += 1;
+            rsx! {}
+        },
+        storage.clone(),
+    );
+    dom.rebuild_in_place();
+
+    assert_eq!(
+        storage.get("count").as_deref(),
+        Some(r#"{"version":0,"value":6}"#)
+    );
+}
+
+#[test]
+fn old_versions_are_migrated() {
+    let storage = MemoryStorage::new();
+    storage.set("volume", r#"{"version":0,"value":50}"#);
+
+    let signal = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |(storage, signal): (MemoryStorage, Rc<RefCell<Option<PersistentSignal<f64>>>>)| {
+            let volume = use_persistent_with(
+                PersistentOptions::new("volume")
+                    .with_version(2)
+                    .with_migration(0, |percent: u32| percent.to_string())
+                    .with_migration(1, |percent: String| percent.parse::<f64>().unwrap() / 100.0)
+                    .with_storage(storage),
+                || 1.0,
+            );
+            signal.borrow_mut().replace(volume);
+            rsx! {}
+        },
+        (storage.clone(), signal.clone()),
+    );
+    dom.rebuild_in_place();
+
+    let volume = signal.borrow().unwrap();
+    in_app(&dom, || assert_eq!(volume(), 0.5));
+    // The migrated value is saved so it only has to be migrated once
+    assert_eq!(
+        storage.get("volume").as_deref(),
+        Some(r#"{"version":2,"value":0.5}"#)
+    );
+}
+
+#[test]
+fn values_that_cannot_be_loaded_are_ignored() {
+    let storage = MemoryStorage::new();
+    storage.set("newer", r#"{"version":3,"value":"from the future"}"#);
+    storage.set("unmigrated", r#"{"version":0,"value":"old"}"#);
+    storage.set("invalid", "not json");
+
+    let mut dom = VirtualDom::new_with_props(
+        |storage: MemoryStorage| {
+            for key in ["newer", "unmigrated", "invalid"] {
+                let options = PersistentOptions::new(key)
+                    .with_version(1)
+                    .with_storage(storage.clone());
+                let value = PersistentSignal::new(options, || "default".to_string());
+                assert_eq!(value(), "default");
+            }
+            rsx! {}
+        },
+        storage.clone(),
+    );
+    dom.rebuild_in_place();
+
+    // The saved value is only replaced when the signal is written
+    assert_eq!(storage.get("invalid").as_deref(), Some("not json"));
+}
+
+#[test]
+fn signals_with_the_same_key_stay_in_sync() {
+    #[derive(Clone)]
+    struct Props {
+        storage: MemoryStorage,
+        renders: Rc<RefCell<Vec<String>>>,
+        first: Rc<RefCell<Option<PersistentSignal<String>>>>,
+    }
+
+    impl PartialEq for Props {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.renders, &other.renders)
+        }
+    }
+
+    fn app(props: Props) -> Element {
+        rsx! {
+            // Both windows share the same storage, like windows of a desktop app
+            Window { name: "first", props: props.clone() }
+            Window { name: "second", props: props.clone() }
+        }
+    }
+
+    #[component]
+    fn Window(name: String, props: Props) -> Element {
+        let theme = use_persistent_with(
+            PersistentOptions::new("theme").with_storage(props.storage.clone()),
+            || "light".to_string(),
+        );
+        if name == "first" {
+            props.first.borrow_mut().replace(theme);
+        }
+        props.renders.borrow_mut().push(format!("{name}: {theme}"));
+        rsx! {}
+    }
+
+    let props = Props {
+        storage: MemoryStorage::new(),
+        renders: Default::default(),
+        first: Default::default(),
+    };
+    let mut dom = VirtualDom::new_with_props(app, props.clone());
+    dom.rebuild_in_place();
+
+    let mut first = props.first.borrow().unwrap();
+    in_app(&dom, || first.set("dark".to_string()));
+    dom.render_immediate(&mut NoOpMutations);
+
+    assert_eq!(
+        *props.renders.borrow(),
+        [
+            "first: light",
+            "second: light",
+            "first: dark",
+            "second: dark"
+        ]
+    );
+    assert_eq!(
+        props.storage.get("theme").as_deref(),
+        Some(r#"{"version":0,"value":"dark"}"#)
+    );
+}
+
+#[test]
+fn file_storage_is_shared_and_saved_to_disk() {
+    let directory = std::env::temp_dir().join(format!("dioxus-persistent-{}", std::process::id()));
+    let path = directory.join("persistent.json");
+    let _ = std::fs::remove_file(&path);
+
+    let first = FileStorage::in_directory(&directory);
+    let second = FileStorage::new(&path);
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let _subscription = second.subscribe("key", {
+        let changes = changes.clone();
+        Rc::new(move |value| changes.borrow_mut().push(value))
+    });
+
+    first.set("key", "1");
+    first.set("key", "1");
+    first.remove("key");
+    first.set("other", "2");
+    assert_eq!(*changes.borrow(), [Some("1".to_string()), None]);
+    assert_eq!(second.get("other").as_deref(), Some("2"));
+
+    drop((first, second, _subscription));
+    let saved: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(saved, serde_json::json!({ "other": "2" }));
+    assert_eq!(FileStorage::new(&path).get("other").as_deref(), Some("2"));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn the_fallback_storage_is_not_shared_between_doms() {
+    fn app(loaded: Rc<RefCell<Vec<i32>>>) -> Element {
+        let mut visits = use_persistent("visits", || 0);
+        loaded.borrow_mut().push(visits());
+        visits += 1;
+
+        let visits_again = use_persistent("visits", || 0);
+        loaded.borrow_mut().push(visits_again());
+        rsx! {}
+    }
+
+    let loaded = Rc::new(RefCell::new(Vec::new()));
+    for _ in 0..2 {
+        VirtualDom::new_with_props(app, loaded.clone()).rebuild_in_place();
+    }
+    // Signals in the same dom share the fallback storage, but each dom starts without the values saved by the last
+    // dom on this thread
+    assert_eq!(*loaded.borrow(), [0, 1, 0, 1]);
+}
+
+#[test]
+fn values_saved_by_newer_versions_are_kept() {
+    let storage = MemoryStorage::new();
+    let newer = r#"{"version":2,"value":{"name":"dark","contrast":"high"}}"#;
+    storage.set("theme", newer);
+
+    fn app((storage, loaded): (MemoryStorage, Rc<RefCell<Vec<String>>>)) -> Element {
+        let mut theme = use_persistent_with(
+            PersistentOptions::new("theme")
+                .with_version(1)
+                .with_storage(storage),
+            || "light".to_string(),
+        );
+        loaded.borrow_mut().push(theme());
+        use_hook(|| theme.set("blue".to_string()));
+        rsx! {}
+    }
+
+    let loaded = Rc::new(RefCell::new(Vec::new()));
+    for _ in 0..2 {
+        VirtualDom::new_with_props(app, (storage.clone(), loaded.clone())).rebuild_in_place();
+    }
+
+    // The older version keeps its own value next to the value of the newer version
+    assert_eq!(*loaded.borrow(), ["light", "blue"]);
+    assert_eq!(storage.get("theme").as_deref(), Some(newer));
+    assert_eq!(
+        storage.get("theme.v1").as_deref(),
+        Some(r#"{"version":1,"value":"blue"}"#)
+    );
+}
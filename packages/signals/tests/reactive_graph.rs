@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::{NoOpMutations, ReactiveContextKind, ReactiveGraph, ReactiveValueKind};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn graph_records_reads_writes_and_runs() {
+    let signal = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |signal: Rc<RefCell<Option<Signal<i32>>>>| {
+            let count = use_signal(|| 0);
+            let doubled = use_memo(move || count() * 2);
+            signal.borrow_mut().replace(count);
+            rsx! { "{doubled}" }
+        },
+        signal.clone(),
+    );
+    dom.rebuild_in_place();
+
+    let mut count = signal.borrow().unwrap();
+    dom.in_runtime(|| ScopeId::APP.in_runtime(|| count += 1));
+    dom.render_immediate(&mut NoOpMutations);
+
+    let graph = ReactiveGraph::snapshot();
+    // Other tests may record values at the same time, so only look at the contexts in this file
+    let in_this_file = |origin: &Option<String>| {
+        origin
+            .as_deref()
+            .is_some_and(|origin| origin.contains("reactive_graph.rs"))
+    };
+
+    let signal = graph
+        .values
+        .iter()
+        .find(|value| value.kind == ReactiveValueKind::Signal && in_this_file(&value.origin))
+        .unwrap();
+    assert_eq!(signal.writes, 1);
+
+    let memo_context = graph
+        .contexts
+        .iter()
+        .find(|context| context.kind == ReactiveContextKind::Memo && in_this_file(&context.origin))
+        .unwrap();
+    assert_eq!(signal.subscribers, [memo_context.id]);
+    assert_eq!(memo_context.runs, 2);
+    assert_eq!(memo_context.last_dirtied_by, Some(signal.id));
+
+    let memo = graph
+        .values
+        .iter()
+        .find(|value| Some(value.id) == memo_context.output)
+        .unwrap();
+    assert_eq!(memo.kind, ReactiveValueKind::Memo);
+    assert_eq!(memo.writes, 1);
+
+    // The component re-rendered because the memo changed
+    let [component] = memo.subscribers[..] else {
+        panic!("expected the memo to have one subscriber");
+    };
+    let component = graph
+        .contexts
+        .iter()
+        .find(|context| context.id == component)
+        .unwrap();
+    assert_eq!(component.kind, ReactiveContextKind::Scope);
+    assert_eq!(component.scope, ScopeId::APP);
+    assert_eq!(component.runs, 2);
+    assert_eq!(component.last_dirtied_by, Some(memo.id));
+
+    // Dropping the dom removes everything it created from the graph
+    drop(dom);
+    let graph = ReactiveGraph::snapshot();
+    assert!(!graph
+        .contexts
+        .iter()
+        .any(|context| context.id == memo_context.id || context.id == component.id));
+    assert!(!graph.values.iter().any(|value| value.id == signal.id));
+}
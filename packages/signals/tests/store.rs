@@ -0,0 +1,182 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+#[derive(Store)]
+struct TodoList {
+    title: String,
+    todos: Vec<Todo>,
+    tags: HashMap<String, u32>,
+}
+
+#[derive(Store)]
+struct Todo {
+    label: String,
+    done: bool,
+}
+
+/// How many times each view rendered
+type Renders = Rc<RefCell<HashMap<String, usize>>>;
+
+#[derive(Clone)]
+struct TestState {
+    renders: Renders,
+    store: Rc<RefCell<Option<Store<TodoList>>>>,
+}
+
+fn render(renders: &Renders, name: impl Into<String>) {
+    *renders.borrow_mut().entry(name.into()).or_default() += 1;
+}
+
+fn app(state: TestState) -> Element {
+    let store = use_store(|| TodoList {
+        title: "Groceries".to_string(),
+        todos: vec![
+            Todo {
+                label: "Milk".to_string(),
+                done: false,
+            },
+            Todo {
+                label: "Eggs".to_string(),
+                done: false,
+            },
+        ],
+        tags: HashMap::from([("dairy".to_string(), 1)]),
+    });
+    state.store.borrow_mut().replace(store.clone());
+    let renders = state.renders.clone();
+    render(&renders, "app");
+
+    rsx! {
+        Title { title: store.title(), renders: renders.clone() }
+        Count { todos: store.todos(), renders: renders.clone() }
+        for (index, todo) in store.todos().iter().enumerate() {
+            TodoView { key: "{index}", todo, renders: renders.clone() }
+        }
+        Tag { tags: store.tags(), renders: renders.clone() }
+    }
+}
+
+#[component]
+fn Title(title: Store<String>, renders: Renders) -> Element {
+    render(&renders, "title");
+    rsx! { "{title}" }
+}
+
+#[component]
+fn Count(todos: Store<Vec<Todo>>, renders: Renders) -> Element {
+    render(&renders, "count");
+    rsx! { "{todos.len()}" }
+}
+
+#[component]
+fn TodoView(todo: Store<Todo>, renders: Renders) -> Element {
+    render(&renders, todo.label().cloned());
+    rsx! { "{todo.done()}" }
+}
+
+#[component]
+fn Tag(tags: Store<HashMap<String, u32>>, renders: Renders) -> Element {
+    render(&renders, "tag");
+    let dairy = tags.get("dairy".to_string()).map(|count| count.cloned());
+    rsx! { "{dairy:?}" }
+}
+
+fn setup() -> (VirtualDom, TestState) {
+    let state = TestState {
+        renders: Default::default(),
+        store: Default::default(),
+    };
+    let mut dom = VirtualDom::new_with_props(app, state.clone());
+    dom.rebuild_in_place();
+    state.renders.borrow_mut().clear();
+    (dom, state)
+}
+
+fn update(dom: &mut VirtualDom, state: &TestState, f: impl FnOnce(Store<TodoList>)) -> Vec<String> {
+    let store = state.store.borrow().clone().unwrap();
+    dom.in_runtime(|| ScopeId::APP.in_runtime(|| f(store)));
+    dom.render_immediate(&mut NoOpMutations);
+    let mut renders: Vec<_> = state
+        .renders
+        .borrow_mut()
+        .drain()
+        .map(|(name, _)| name)
+        .collect();
+    renders.sort();
+    renders
+}
+
+#[test]
+fn writing_a_field_only_notifies_readers_of_that_field() {
+    let (mut dom, state) = setup();
+
+    let renders = update(&mut dom, &state, |store| {
+        store.todos().index(1).done().set(true);
+    });
+    assert_eq!(renders, ["Eggs"]);
+
+    let renders = update(&mut dom, &state, |store| {
+        store.title().set("Shopping".to_string());
+    });
+    assert_eq!(renders, ["title"]);
+}
+
+#[test]
+fn writing_a_parent_notifies_readers_of_every_child() {
+    let (mut dom, state) = setup();
+
+    let renders = update(&mut dom, &state, |store| {
+        for todo in store.todos().write().iter_mut() {
+            todo.done = true;
+        }
+    });
+    assert_eq!(renders, ["Eggs", "Milk", "app", "count"]);
+}
+
+#[test]
+fn pushing_only_notifies_readers_of_the_length() {
+    let (mut dom, state) = setup();
+
+    let renders = update(&mut dom, &state, |store| {
+        store.todos().push(Todo {
+            label: "Bread".to_string(),
+            done: false,
+        });
+    });
+    // The app iterates over the todos, so it reruns and renders the new todo
+    assert_eq!(renders, ["Bread", "app", "count"]);
+}
+
+#[test]
+fn map_entries_are_tracked_by_key() {
+    let (mut dom, state) = setup();
+
+    let renders = update(&mut dom, &state, |store| {
+        store.tags().insert("produce".to_string(), 2);
+    });
+    assert_eq!(renders, Vec::<String>::new());
+
+    let renders = update(&mut dom, &state, |store| {
+        store.tags().remove(&"dairy".to_string());
+    });
+    assert_eq!(renders, ["tag"]);
+}
+
+#[test]
+fn btree_maps_select_values_by_key() {
+    let mut dom = VirtualDom::new(|| {
+        let scores = use_store(|| BTreeMap::from([("a", 1), ("b", 2)]));
+        let mut b = scores.get("b").unwrap();
+        b += 1;
+        let total: i32 = scores.iter().map(|(_, score)| score()).sum();
+        assert_eq!(total, 4);
+        assert_eq!(scores.get("c"), None);
+        rsx! {}
+    });
+    dom.rebuild_in_place();
+}
@@ -0,0 +1,100 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use futures_util::StreamExt;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+type CounterProps = (Arc<AtomicUsize>, Rc<RefCell<Option<SyncSignal<i32>>>>);
+
+/// Render a component that creates a sync signal and counts its renders
+fn render_counter(renders: Arc<AtomicUsize>) -> (VirtualDom, SyncSignal<i32>) {
+    let signal = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |(renders, signal): CounterProps| {
+            let count = use_signal_sync(|| 0);
+            use_hook(|| *signal.borrow_mut() = Some(count));
+            renders.fetch_add(1, Ordering::SeqCst);
+            rsx! { "{count}" }
+        },
+        (renders, signal.clone()),
+    );
+    dom.rebuild_in_place();
+    let signal = signal.borrow().expect("the signal to be created");
+    (dom, signal)
+}
+
+#[tokio::test]
+async fn writes_from_other_threads_wake_the_dom() {
+    let renders = Arc::new(AtomicUsize::new(0));
+    let (mut dom, mut count) = render_counter(renders.clone());
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+    std::thread::spawn(move || count.set(1)).join().unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), dom.wait_for_work())
+        .await
+        .expect("the write to wake the dom");
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(renders.load(Ordering::SeqCst), 2);
+    assert_eq!(*count.peek(), 1);
+}
+
+#[tokio::test]
+async fn streams_yield_the_latest_value() {
+    let (_dom, mut count) = render_counter(Default::default());
+    let mut changes = count.subscribe();
+    assert_eq!(changes.next().await, Some(0));
+
+    // Writes before the stream is polled are combined
+    count.set(1);
+    count.set(2);
+    assert_eq!(changes.next().await, Some(2));
+
+    let writer = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(10));
+        count.set(3);
+    });
+    let next = tokio::time::timeout(Duration::from_secs(5), changes.next()).await;
+    assert_eq!(next.expect("the write to wake the stream"), Some(3));
+    writer.join().unwrap();
+}
+
+#[tokio::test]
+async fn streams_can_be_created_on_other_threads() {
+    let (dom, mut count) = render_counter(Default::default());
+    let mut changes = std::thread::spawn(move || count.subscribe())
+        .join()
+        .unwrap();
+    assert_eq!(changes.next().await, Some(0));
+
+    count.set(1);
+    assert_eq!(changes.next().await, Some(1));
+
+    // Once the signal is dropped, the stream ends
+    drop(dom);
+    assert_eq!(changes.next().await, None);
+}
+
+#[tokio::test]
+async fn streams_end_when_the_dom_is_dropped() {
+    let (dom, count) = render_counter(Default::default());
+    let mut changes = dom.in_runtime(|| count.subscribe());
+    assert_eq!(changes.next().await, Some(0));
+
+    // Wait for a change that never comes
+    let pending = tokio::spawn(async move { changes.next().await });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!pending.is_finished());
+
+    drop(dom);
+    let next = tokio::time::timeout(Duration::from_secs(5), pending).await;
+    assert_eq!(
+        next.expect("dropping the dom to wake the stream").unwrap(),
+        None
+    );
+}
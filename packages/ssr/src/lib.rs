@@ -5,6 +5,7 @@
 mod cache;
 pub mod config;
 pub mod renderer;
+pub mod stream;
 pub mod template;
 
 use dioxus_core::{Element, VirtualDom};
@@ -11,11 +11,13 @@ type ComponentRenderCallback = Arc<
 >;
 
 /// A virtualdom renderer that caches the templates it has seen for faster rendering
-#[derive(Default)]
 pub struct Renderer {
     /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
     pub pre_render: bool,
 
+    /// The number of bytes the async renderers buffer before sending a chunk to the output. Defaults to 8KiB
+    pub chunk_size: usize,
+
     /// A callback used to render components. You can set this callback to control what components are rendered and add wrappers around components that are not present in CSR
     render_components: Option<ComponentRenderCallback>,
 
@@ -26,6 +28,20 @@ pub struct Renderer {
     dynamic_node_id: usize,
 }
 
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            pre_render: false,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            render_components: None,
+            template_cache: Default::default(),
+            dynamic_node_id: 0,
+        }
+    }
+}
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
 impl Renderer {
     pub fn new() -> Self {
         Self::default()
@@ -88,6 +104,17 @@ impl Renderer {
         self.dynamic_node_id = 0;
     }
 
+    /// The hydration id the next dynamic node gets when pre-rendering
+    pub fn hydration_id(&self) -> usize {
+        self.dynamic_node_id
+    }
+
+    /// Set the hydration id the next dynamic node gets when pre-rendering. This lets a render callback write html
+    /// that was rendered before instead of rendering the scope again.
+    pub fn set_hydration_id(&mut self, id: usize) {
+        self.dynamic_node_id = id;
+    }
+
     pub fn render_scope<W: Write + ?Sized>(
         &mut self,
         buf: &mut W,
@@ -102,168 +129,249 @@ impl Renderer {
 
     fn render_template<W: Write + ?Sized>(
         &mut self,
-        mut buf: &mut W,
+        buf: &mut W,
         dom: &VirtualDom,
         template: &VNode,
     ) -> std::fmt::Result {
+        let mut stack = vec![self.template_frame(template)];
+        self.render_frames(buf, dom, &mut stack, |_| false)
+    }
+
+    /// Start rendering a template. The frame is pushed onto the stack passed to [`Self::render_frames`]
+    pub(crate) fn template_frame<'a>(&mut self, template: &'a VNode) -> RenderFrame<'a> {
         let entry = self
             .template_cache
             .entry(template.template)
             .or_insert_with(move || Arc::new(StringCache::from_template(template).unwrap()))
             .clone();
 
-        let mut inner_html = None;
+        RenderFrame::Template(TemplateFrame {
+            template,
+            entry,
+            index: 0,
+            inner_html: None,
+            accumulated_dynamic_styles: Vec::new(),
+            accumulated_listeners: Vec::new(),
+        })
+    }
+
+    /// Render the frames on the stack one segment at a time until the stack is empty or `pause` returns true.
+    ///
+    /// If rendering is paused, calling this again with the same stack picks up where the last call stopped.
+    pub(crate) fn render_frames<'a, W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &'a VirtualDom,
+        stack: &mut Vec<RenderFrame<'a>>,
+        mut pause: impl FnMut(&W) -> bool,
+    ) -> std::fmt::Result {
+        while let Some(frame) = stack.last_mut() {
+            let child = match frame {
+                RenderFrame::Template(frame) => match self.render_segment(buf, dom, frame)? {
+                    SegmentOutput::Written => None,
+                    SegmentOutput::Child(child) => Some(child),
+                    SegmentOutput::Finished => {
+                        stack.pop();
+                        None
+                    }
+                },
+                RenderFrame::Fragment(children) => match children.next() {
+                    Some(child) => Some(self.template_frame(child)),
+                    None => {
+                        stack.pop();
+                        None
+                    }
+                },
+            };
+
+            if let Some(child) = child {
+                stack.push(child);
+            }
 
-        // We need to keep track of the dynamic styles so we can insert them into the right place
-        let mut accumulated_dynamic_styles = Vec::new();
+            if pause(buf) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
 
-        // We need to keep track of the listeners so we can insert them into the right place
-        let mut accumulated_listeners = Vec::new();
+    /// Write the next segment of a template
+    fn render_segment<'a, W: Write + ?Sized>(
+        &mut self,
+        mut buf: &mut W,
+        dom: &'a VirtualDom,
+        frame: &mut TemplateFrame<'a>,
+    ) -> Result<SegmentOutput<'a>, std::fmt::Error> {
+        let template = frame.template;
+        let Some(segment) = frame.entry.segments.get(frame.index) else {
+            return Ok(SegmentOutput::Finished);
+        };
 
         // We keep track of the index we are on manually so that we can jump forward to a new section quickly without iterating every item
-        let mut index = 0;
-
-        while let Some(segment) = entry.segments.get(index) {
-            match segment {
-                Segment::HydrationOnlySection(jump_to) => {
-                    // If we are not prerendering, we don't need to write the content of the hydration only section
-                    // Instead we can jump to the next section
-                    if !self.pre_render {
-                        index = *jump_to;
-                        continue;
-                    }
+        frame.index += 1;
+
+        match segment {
+            Segment::HydrationOnlySection(jump_to) => {
+                // If we are not prerendering, we don't need to write the content of the hydration only section
+                // Instead we can jump to the next section
+                if !self.pre_render {
+                    frame.index = *jump_to;
                 }
-                Segment::Attr(idx) => {
-                    let attrs = &*template.dynamic_attrs[*idx];
-                    for attr in attrs {
-                        if attr.name == "dangerous_inner_html" {
-                            inner_html = Some(attr);
-                        } else if attr.namespace == Some("style") {
-                            accumulated_dynamic_styles.push(attr);
-                        } else if BOOL_ATTRS.contains(&attr.name) {
-                            if truthy(&attr.value) {
-                                write_attribute(buf, attr)?;
-                            }
-                        } else {
+            }
+            Segment::Attr(idx) => {
+                let attrs = &*template.dynamic_attrs[*idx];
+                for attr in attrs {
+                    if attr.name == "dangerous_inner_html" {
+                        frame.inner_html = Some(attr);
+                    } else if attr.namespace == Some("style") {
+                        frame.accumulated_dynamic_styles.push(attr);
+                    } else if BOOL_ATTRS.contains(&attr.name) {
+                        if truthy(&attr.value) {
                             write_attribute(buf, attr)?;
                         }
+                    } else {
+                        write_attribute(buf, attr)?;
+                    }
 
-                        if self.pre_render {
-                            if let AttributeValue::Listener(_) = &attr.value {
-                                // The onmounted event doesn't need a DOM listener
-                                if attr.name != "onmounted" {
-                                    accumulated_listeners.push(attr.name);
-                                }
+                    if self.pre_render {
+                        if let AttributeValue::Listener(_) = &attr.value {
+                            // The onmounted event doesn't need a DOM listener
+                            if attr.name != "onmounted" {
+                                frame.accumulated_listeners.push(attr.name);
                             }
                         }
                     }
                 }
-                Segment::Node(idx) => match &template.dynamic_nodes[*idx] {
-                    DynamicNode::Component(node) => {
-                        if let Some(render_components) = self.render_components.clone() {
-                            let scope_id = node.mounted_scope_id(*idx, template, dom).unwrap();
-
-                            render_components(self, &mut buf, dom, scope_id)?;
-                        } else {
-                            let scope = node.mounted_scope(*idx, template, dom).unwrap();
-                            let node = scope.root_node();
-                            self.render_template(buf, dom, node)?
-                        }
+            }
+            Segment::Node(idx) => match &template.dynamic_nodes[*idx] {
+                DynamicNode::Component(node) => {
+                    if let Some(render_components) = self.render_components.clone() {
+                        let scope_id = node.mounted_scope_id(*idx, template, dom).unwrap();
+
+                        render_components(self, &mut buf, dom, scope_id)?;
+                    } else {
+                        let scope = node.mounted_scope(*idx, template, dom).unwrap();
+                        let node = scope.root_node();
+                        return Ok(SegmentOutput::Child(self.template_frame(node)));
+                    }
+                }
+                DynamicNode::Text(text) => {
+                    // in SSR, we are concerned that we can't hunt down the right text node since they might get merged
+                    if self.pre_render {
+                        write!(buf, "<!--node-id{}-->", self.dynamic_node_id)?;
+                        self.dynamic_node_id += 1;
                     }
-                    DynamicNode::Text(text) => {
-                        // in SSR, we are concerned that we can't hunt down the right text node since they might get merged
-                        if self.pre_render {
-                            write!(buf, "<!--node-id{}-->", self.dynamic_node_id)?;
-                            self.dynamic_node_id += 1;
-                        }
 
-                        write!(
-                            buf,
-                            "{}",
-                            askama_escape::escape(&text.value, askama_escape::Html)
-                        )?;
+                    write!(
+                        buf,
+                        "{}",
+                        askama_escape::escape(&text.value, askama_escape::Html)
+                    )?;
 
-                        if self.pre_render {
-                            write!(buf, "<!--#-->")?;
-                        }
-                    }
-                    DynamicNode::Fragment(nodes) => {
-                        for child in nodes {
-                            self.render_template(buf, dom, child)?;
-                        }
+                    if self.pre_render {
+                        write!(buf, "<!--#-->")?;
                     }
+                }
+                DynamicNode::Fragment(nodes) => {
+                    return Ok(SegmentOutput::Child(RenderFrame::Fragment(nodes.iter())));
+                }
 
-                    DynamicNode::Placeholder(_) => {
-                        if self.pre_render {
-                            write!(buf, "<!--placeholder{}-->", self.dynamic_node_id)?;
-                            self.dynamic_node_id += 1;
-                        }
+                DynamicNode::Placeholder(_) => {
+                    if self.pre_render {
+                        write!(buf, "<!--placeholder{}-->", self.dynamic_node_id)?;
+                        self.dynamic_node_id += 1;
                     }
-                },
-
-                Segment::PreRendered(contents) => write!(buf, "{contents}")?,
+                }
+            },
 
-                Segment::StyleMarker { inside_style_tag } => {
-                    if !accumulated_dynamic_styles.is_empty() {
-                        // if we are inside a style tag, we don't need to write the style attribute
-                        if !*inside_style_tag {
-                            write!(buf, " style=\"")?;
-                        }
-                        for attr in &accumulated_dynamic_styles {
-                            write!(buf, "{}:", attr.name)?;
-                            write_value_unquoted(buf, &attr.value)?;
-                            write!(buf, ";")?;
-                        }
-                        if !*inside_style_tag {
-                            write!(buf, "\"")?;
-                        }
+            Segment::PreRendered(contents) => write!(buf, "{contents}")?,
 
-                        // clear the accumulated styles
-                        accumulated_dynamic_styles.clear();
+            Segment::StyleMarker { inside_style_tag } => {
+                if !frame.accumulated_dynamic_styles.is_empty() {
+                    // if we are inside a style tag, we don't need to write the style attribute
+                    if !*inside_style_tag {
+                        write!(buf, " style=\"")?;
                     }
-                }
-
-                Segment::InnerHtmlMarker => {
-                    if let Some(inner_html) = inner_html.take() {
-                        let inner_html = &inner_html.value;
-                        match inner_html {
-                            AttributeValue::Text(value) => write!(buf, "{}", value)?,
-                            AttributeValue::Bool(value) => write!(buf, "{}", value)?,
-                            AttributeValue::Float(f) => write!(buf, "{}", f)?,
-                            AttributeValue::Int(i) => write!(buf, "{}", i)?,
-                            _ => {}
-                        }
+                    for attr in &frame.accumulated_dynamic_styles {
+                        write!(buf, "{}:", attr.name)?;
+                        write_value_unquoted(buf, &attr.value)?;
+                        write!(buf, ";")?;
                     }
+                    if !*inside_style_tag {
+                        write!(buf, "\"")?;
+                    }
+
+                    // clear the accumulated styles
+                    frame.accumulated_dynamic_styles.clear();
                 }
+            }
 
-                Segment::AttributeNodeMarker => {
-                    // first write the id
-                    write!(buf, "{}", self.dynamic_node_id)?;
-                    self.dynamic_node_id += 1;
-                    // then write any listeners
-                    for name in accumulated_listeners.drain(..) {
-                        write!(buf, ",{}:", &name[2..])?;
-                        write!(
-                            buf,
-                            "{}",
-                            dioxus_core_types::event_bubbles(&name[2..]) as u8
-                        )?;
+            Segment::InnerHtmlMarker => {
+                if let Some(inner_html) = frame.inner_html.take() {
+                    let inner_html = &inner_html.value;
+                    match inner_html {
+                        AttributeValue::Text(value) => write!(buf, "{}", value)?,
+                        AttributeValue::Bool(value) => write!(buf, "{}", value)?,
+                        AttributeValue::Float(f) => write!(buf, "{}", f)?,
+                        AttributeValue::Int(i) => write!(buf, "{}", i)?,
+                        _ => {}
                     }
                 }
+            }
 
-                Segment::RootNodeMarker => {
-                    write!(buf, "{}", self.dynamic_node_id)?;
-                    self.dynamic_node_id += 1
+            Segment::AttributeNodeMarker => {
+                // first write the id
+                write!(buf, "{}", self.dynamic_node_id)?;
+                self.dynamic_node_id += 1;
+                // then write any listeners
+                for name in frame.accumulated_listeners.drain(..) {
+                    write!(buf, ",{}:", &name[2..])?;
+                    write!(
+                        buf,
+                        "{}",
+                        dioxus_core_types::event_bubbles(&name[2..]) as u8
+                    )?;
                 }
             }
 
-            index += 1;
+            Segment::RootNodeMarker => {
+                write!(buf, "{}", self.dynamic_node_id)?;
+                self.dynamic_node_id += 1
+            }
         }
 
-        Ok(())
+        Ok(SegmentOutput::Written)
     }
 }
 
+/// A template or fragment that is partially written to the output
+pub(crate) enum RenderFrame<'a> {
+    Template(TemplateFrame<'a>),
+    Fragment(std::slice::Iter<'a, VNode>),
+}
+
+pub(crate) struct TemplateFrame<'a> {
+    template: &'a VNode,
+    entry: Arc<StringCache>,
+    /// The index of the next segment to write
+    index: usize,
+    inner_html: Option<&'a Attribute>,
+    // We need to keep track of the dynamic styles so we can insert them into the right place
+    accumulated_dynamic_styles: Vec<&'a Attribute>,
+    // We need to keep track of the listeners so we can insert them into the right place
+    accumulated_listeners: Vec<&'static str>,
+}
+
+enum SegmentOutput<'a> {
+    /// The segment was written to the output
+    Written,
+    /// The segment contains another template that needs to be rendered before the rest of this template
+    Child(RenderFrame<'a>),
+    /// Every segment of the template has been written
+    Finished,
+}
+
 #[test]
 fn to_string_works() {
     use dioxus::prelude::*;
@@ -0,0 +1,151 @@
+//! Render a VirtualDom into an async output chunk by chunk.
+//!
+//! The synchronous renderer writes the whole page into a buffer before anything can be sent. The async renderers
+//! in this module write [`Renderer::chunk_size`] bytes at a time instead and wait for the output to accept each
+//! chunk before rendering more of the page. Large pages start flushing to the client immediately and the memory
+//! used while rendering stays bounded by the chunk size.
+
+use bytes::Bytes;
+use dioxus_core::prelude::*;
+use futures_util::{Sink, SinkExt};
+
+use crate::renderer::RenderFrame;
+use crate::Renderer;
+
+/// An error that occurred while rendering into an async output.
+#[derive(Debug)]
+pub enum StreamRenderError<E> {
+    /// A component failed to render.
+    Render(std::fmt::Error),
+    /// The output failed to accept a chunk.
+    Output(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for StreamRenderError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamRenderError::Render(err) => write!(f, "Failed to render: {err}"),
+            StreamRenderError::Output(err) => write!(f, "Failed to write to output: {err}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for StreamRenderError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamRenderError::Render(err) => Some(err),
+            StreamRenderError::Output(err) => Some(err),
+        }
+    }
+}
+
+impl Renderer {
+    /// Render the VirtualDom into a [`Sink`] of [`Bytes`].
+    ///
+    /// Each chunk is sent with [`SinkExt::send`], so rendering pauses until the sink is ready for more data.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use futures_util::{future::join, StreamExt};
+    /// fn app() -> Element {
+    ///     rsx! {
+    ///         table {
+    ///             for i in 0..1000 {
+    ///                 tr { td { "row {i}" } }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    ///
+    /// let mut renderer = dioxus_ssr::Renderer::new();
+    /// renderer.chunk_size = 1024;
+    ///
+    /// // A channel with room for a single chunk. The renderer waits for the receiver to catch up
+    /// let (mut tx, rx) = futures_channel::mpsc::channel(0);
+    /// let render = async move {
+    ///     renderer.render_to_sink(&mut tx, &dom).await.unwrap();
+    /// };
+    /// let (_, chunks) = join(render, rx.collect::<Vec<_>>()).await;
+    /// assert!(chunks.len() > 1);
+    /// # }
+    /// ```
+    pub async fn render_to_sink<S>(
+        &mut self,
+        sink: &mut S,
+        dom: &VirtualDom,
+    ) -> Result<(), StreamRenderError<S::Error>>
+    where
+        S: Sink<Bytes> + Unpin + ?Sized,
+    {
+        let mut chunks = ChunkedRender::new(self, dom);
+        while let Some(chunk) = chunks.next_chunk(self, dom) {
+            let chunk = chunk.map_err(StreamRenderError::Render)?;
+            sink.send(Bytes::from(chunk))
+                .await
+                .map_err(StreamRenderError::Output)?;
+        }
+        Ok(())
+    }
+
+    /// Render the VirtualDom into a [`tokio::io::AsyncWrite`].
+    ///
+    /// Each chunk is written and flushed before the next chunk is rendered, so rendering pauses until the writer is ready for more data.
+    #[cfg(feature = "tokio")]
+    pub async fn render_to_async_write<W>(
+        &mut self,
+        writer: &mut W,
+        dom: &VirtualDom,
+    ) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let mut chunks = ChunkedRender::new(self, dom);
+        while let Some(chunk) = chunks.next_chunk(self, dom) {
+            let chunk = chunk.map_err(std::io::Error::other)?;
+            writer.write_all(chunk.as_bytes()).await?;
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// A render of the root scope that is split into chunks of roughly [`Renderer::chunk_size`] bytes
+struct ChunkedRender<'a> {
+    stack: Vec<RenderFrame<'a>>,
+}
+
+impl<'a> ChunkedRender<'a> {
+    fn new(renderer: &mut Renderer, dom: &'a VirtualDom) -> Self {
+        renderer.reset_hydration();
+        let node = dom.get_scope(ScopeId::ROOT).unwrap().root_node();
+        Self {
+            stack: vec![renderer.template_frame(node)],
+        }
+    }
+
+    /// Render the next chunk. Chunks are never empty, and may be larger than the chunk size if a single segment is larger than the chunk size
+    fn next_chunk(
+        &mut self,
+        renderer: &mut Renderer,
+        dom: &'a VirtualDom,
+    ) -> Option<Result<String, std::fmt::Error>> {
+        let chunk_size = renderer.chunk_size;
+        let mut chunk = String::new();
+        while chunk.is_empty() && !self.stack.is_empty() {
+            if let Err(err) = renderer.render_frames(&mut chunk, dom, &mut self.stack, |chunk| {
+                chunk.len() >= chunk_size
+            }) {
+                self.stack.clear();
+                return Some(Err(err));
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
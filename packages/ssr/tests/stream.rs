@@ -0,0 +1,127 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use futures_util::{future::join, StreamExt};
+
+fn Table() -> Element {
+    rsx! {
+        table {
+            for i in 0..500 {
+                Row { i }
+            }
+        }
+    }
+}
+
+#[component]
+fn Row(i: usize) -> Element {
+    rsx! {
+        tr { class: "row-{i}",
+            td { "{i}" }
+            if i % 2 == 0 {
+                td { "even" }
+            }
+        }
+    }
+}
+
+fn table_dom() -> VirtualDom {
+    let mut dom = VirtualDom::new(Table);
+    dom.rebuild_in_place();
+    dom
+}
+
+#[tokio::test]
+async fn sink_matches_sync_render() {
+    let dom = table_dom();
+    let expected = dioxus_ssr::render(&dom);
+
+    for pre_render in [false, true] {
+        let expected = if pre_render {
+            dioxus_ssr::pre_render(&dom)
+        } else {
+            expected.clone()
+        };
+
+        let mut renderer = dioxus_ssr::Renderer::new();
+        renderer.pre_render = pre_render;
+        renderer.chunk_size = 256;
+
+        let (mut tx, rx) = futures_channel::mpsc::channel(0);
+        let render = async {
+            renderer.render_to_sink(&mut tx, &dom).await.unwrap();
+            drop(tx);
+        };
+        let (_, chunks) = join(render, rx.collect::<Vec<_>>()).await;
+
+        assert!(chunks.len() > 10);
+        assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+        // A single segment is small, so no chunk should grow much past the chunk size
+        assert!(chunks.iter().all(|chunk| chunk.len() < 512));
+        assert_eq!(chunks.concat(), expected.as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn sink_applies_back_pressure() {
+    let dom = table_dom();
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.chunk_size = 256;
+
+    // Nobody reads from the channel, so the render stops once the channel is full
+    let (mut tx, mut rx) = futures_channel::mpsc::channel(0);
+    let render = renderer.render_to_sink(&mut tx, &dom);
+    let timed_out = tokio::time::timeout(std::time::Duration::from_millis(50), render).await;
+    assert!(timed_out.is_err());
+
+    let mut buffered = 0;
+    while let Ok(Some(_)) = rx.try_next() {
+        buffered += 1;
+    }
+    assert!(buffered <= 2);
+}
+
+#[tokio::test]
+async fn empty_render_sends_nothing() {
+    fn app() -> Element {
+        rsx! {}
+    }
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+
+    let (mut tx, rx) = futures_channel::mpsc::unbounded();
+    dioxus_ssr::Renderer::new()
+        .render_to_sink(&mut tx, &dom)
+        .await
+        .unwrap();
+    drop(tx);
+    assert!(rx.collect::<Vec<_>>().await.is_empty());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn async_write_matches_sync_render() {
+    use tokio::io::AsyncReadExt;
+
+    let dom = table_dom();
+    let expected = dioxus_ssr::render(&dom);
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.chunk_size = 256;
+
+    // The duplex buffer is much smaller than the page, so the render must wait for the reader
+    let (mut writer, mut reader) = tokio::io::duplex(64);
+    let render = async {
+        renderer
+            .render_to_async_write(&mut writer, &dom)
+            .await
+            .unwrap();
+        drop(writer);
+    };
+    let mut html = String::new();
+    let read = reader.read_to_string(&mut html);
+    let (_, read) = join(render, read).await;
+    read.unwrap();
+
+    assert_eq!(html, expected);
+}
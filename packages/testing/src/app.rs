@@ -0,0 +1,262 @@
+//! A VirtualDom mounted into a [`TestDom`] that tests can drive with events.
+
+use std::{any::Any, collections::HashMap, rc::Rc};
+
+use dioxus_core::{prelude::*, ElementId, Event, VirtualDom};
+use dioxus_core_types::event_bubbles;
+use dioxus_html::{
+    prelude::{Code, Key, Location, Modifiers},
+    set_event_converter, FormValue, PlatformEventData, SerializedFormData,
+    SerializedHtmlEventConverter, SerializedKeyboardData, SerializedMouseData,
+};
+use futures_util::future::{select, Either};
+
+use crate::{Node, NodeId, Query, TestDom};
+
+/// The number of times [`TestApp::wait_until_idle`] yields to the executor before it decides no more work is coming
+const IDLE_YIELDS: usize = 16;
+
+/// A [`VirtualDom`] rendered into an in-memory [`TestDom`].
+///
+/// Events fired at nodes are dispatched with the serialized event types from `dioxus-html`, so components handle
+/// them exactly like events from a real renderer. The dom is re-rendered after every event.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_testing::{Query, TestApp};
+///
+/// fn counter() -> Element {
+///     let mut count = use_signal(|| 0);
+///     rsx! {
+///         button { onclick: move |_| count += 1, "Count: {count}" }
+///     }
+/// }
+///
+/// let mut app = TestApp::new(counter);
+/// let button = app.get(&Query::tag("button"));
+/// app.click(button);
+/// app.click(button);
+/// assert_eq!(app.node(button).text_content(), "Count: 2");
+/// ```
+pub struct TestApp {
+    vdom: VirtualDom,
+    dom: TestDom,
+}
+
+impl TestApp {
+    /// Mount a component without props
+    pub fn new(app: fn() -> Element) -> Self {
+        Self::from_virtual_dom(VirtualDom::new(app))
+    }
+
+    /// Mount a component with props
+    pub fn new_with_props<P: Clone + 'static, M: 'static>(
+        root: impl ComponentFunction<P, M>,
+        root_props: P,
+    ) -> Self {
+        Self::from_virtual_dom(VirtualDom::new_with_props(root, root_props))
+    }
+
+    /// Mount an existing [`VirtualDom`]. This is useful if you need to provide root contexts before the app is built.
+    pub fn from_virtual_dom(mut vdom: VirtualDom) -> Self {
+        // Events are converted into the serialized types from dioxus-html
+        set_event_converter(Box::new(SerializedHtmlEventConverter));
+
+        let mut dom = TestDom::new();
+        vdom.rebuild(&mut dom);
+        let mut app = Self { vdom, dom };
+        app.render();
+        app
+    }
+
+    /// The VirtualDom that is rendered into the dom
+    pub fn vdom(&self) -> &VirtualDom {
+        &self.vdom
+    }
+
+    /// The VirtualDom that is rendered into the dom
+    pub fn vdom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.vdom
+    }
+
+    /// The dom the app is rendered into
+    pub fn dom(&self) -> &TestDom {
+        &self.dom
+    }
+
+    /// Get a view of a node in the dom
+    pub fn node(&self, id: NodeId) -> Node<'_> {
+        self.dom.node(id)
+    }
+
+    /// Serialize the dom into html
+    pub fn html(&self) -> String {
+        self.dom.html()
+    }
+
+    /// Find every node that matches the query. See [`TestDom::query_all`].
+    pub fn query_all(&self, query: &Query) -> Vec<NodeId> {
+        self.dom.query_all(query)
+    }
+
+    /// Find the first node that matches the query. See [`TestDom::query`].
+    pub fn query(&self, query: &Query) -> Option<NodeId> {
+        self.dom.query(query)
+    }
+
+    /// Find the single node that matches the query. See [`TestDom::get`].
+    #[track_caller]
+    pub fn get(&self, query: &Query) -> NodeId {
+        self.dom.get(query)
+    }
+
+    /// Apply any work that is ready without waiting, like dirty components and tasks that were woken
+    pub fn render(&mut self) {
+        self.vdom.render_immediate(&mut self.dom);
+    }
+
+    /// Fire an event with the given name at a node and render the result.
+    ///
+    /// `data` is the platform data of the event. It must be one of the serialized event types from `dioxus-html`,
+    /// like [`SerializedMouseData`] for `click`. Bubbling events are sent to the closest node with a listener, the
+    /// same way the browser dispatches them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node was removed from the dom.
+    #[track_caller]
+    pub fn fire_event(&mut self, node: NodeId, name: &str, data: impl Any) {
+        let target = self.node(node);
+        assert!(
+            target.is_connected(),
+            "Cannot fire {name:?} at {node:?} because it was removed from the dom"
+        );
+
+        let bubbles = event_bubbles(name);
+        if let Some(element) = self.event_target(node, name, bubbles) {
+            let event = Event::new(
+                Rc::new(PlatformEventData::new(Box::new(data))) as Rc<dyn Any>,
+                bubbles,
+            );
+            self.vdom.runtime().handle_event(name, event, element);
+        }
+
+        self.render();
+    }
+
+    /// Click a node
+    #[track_caller]
+    pub fn click(&mut self, node: NodeId) {
+        self.fire_event(node, "click", SerializedMouseData::default());
+    }
+
+    /// Fire an `input` event at a node with a new value
+    #[track_caller]
+    pub fn input(&mut self, node: NodeId, value: impl ToString) {
+        let data = SerializedFormData::new(value.to_string(), HashMap::new());
+        self.fire_event(node, "input", data);
+    }
+
+    /// Fire a `keydown` event at a node
+    #[track_caller]
+    pub fn keydown(&mut self, node: NodeId, key: Key) {
+        let data = SerializedKeyboardData::new(
+            key,
+            Code::Unidentified,
+            Location::Standard,
+            false,
+            Modifiers::empty(),
+            false,
+        );
+        self.fire_event(node, "keydown", data);
+    }
+
+    /// Fire a `submit` event at a node with the values of the form
+    #[track_caller]
+    pub fn submit(
+        &mut self,
+        node: NodeId,
+        values: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) {
+        let values = values
+            .into_iter()
+            .map(|(name, value)| (name.into(), FormValue(vec![value.into()])))
+            .collect();
+        self.fire_event(
+            node,
+            "submit",
+            SerializedFormData::new(String::new(), values),
+        );
+    }
+
+    /// Wait for the VirtualDom to have work, like a task that was woken or a signal written to outside of an event,
+    /// and render it.
+    ///
+    /// If there is nothing that will create work, this will wait forever. Wrap the future in a timeout if the work
+    /// might never arrive.
+    pub async fn wait_for_update(&mut self) {
+        self.vdom.wait_for_work().await;
+        self.render();
+    }
+
+    /// Wait and render updates until the condition is true
+    pub async fn wait_until(&mut self, mut condition: impl FnMut(&TestDom) -> bool) {
+        while !condition(&self.dom) {
+            self.wait_for_update().await;
+        }
+    }
+
+    /// Render updates until every suspense boundary is resolved and no work is ready.
+    ///
+    /// Pending tasks are given a chance to run between renders, but a task that is waiting on something like a
+    /// timer doesn't keep the app busy. Use [`TestApp::wait_until`] to wait for the results of those tasks.
+    pub async fn wait_until_idle(&mut self) {
+        loop {
+            self.render();
+
+            // Suspense always finishes eventually, so we can wait for as long as it takes
+            if self.vdom.suspended_tasks_remaining() {
+                self.vdom.wait_for_work().await;
+                continue;
+            }
+
+            // Otherwise give ready tasks and futures on the same executor a chance to create more work
+            let work = std::pin::pin!(self.vdom.wait_for_work());
+            match select(work, std::pin::pin!(yield_now(IDLE_YIELDS))).await {
+                Either::Left(_) => continue,
+                Either::Right(_) => break,
+            }
+        }
+    }
+
+    /// Find the element the VirtualDom should dispatch the event to
+    fn event_target(&self, node: NodeId, name: &str, bubbles: bool) -> Option<ElementId> {
+        let node = self.node(node);
+        if !bubbles {
+            return node.has_listener(name).then(|| node.element_id()).flatten();
+        }
+
+        // Bubbling events are handled by the closest node that listens to any event. The VirtualDom walks up from
+        // there to find the listeners for this event
+        std::iter::successors(Some(node), |node| node.parent())
+            .find(|node| node.tag().is_some() && !node.listeners().is_empty())
+            .and_then(|node| node.element_id())
+    }
+}
+
+/// Yield to the executor the given number of times
+async fn yield_now(times: usize) {
+    for _ in 0..times {
+        let mut yielded = false;
+        std::future::poll_fn(|cx| {
+            if yielded {
+                std::task::Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        })
+        .await;
+    }
+}
@@ -0,0 +1,688 @@
+//! An in-memory DOM that applies the mutations of a [`VirtualDom`](dioxus_core::VirtualDom).
+//!
+//! The tree follows the same stack machine as the javascript interpreter: templates are cloned onto a stack, the
+//! dynamic nodes inside them are resolved by path from the top of the stack, and finished nodes are moved from the
+//! stack into the tree.
+
+use std::fmt::Write;
+
+use dioxus_core::{
+    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+
+use crate::Query;
+
+/// The id of a node in a [`TestDom`].
+///
+/// Ids are never reused, so an id to a node that was removed from the tree stays valid. It just won't be
+/// [connected](Node::is_connected) anymore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The id of the root node the app is mounted into
+    pub const ROOT: NodeId = NodeId(0);
+}
+
+/// The kind of a node in a [`TestDom`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    /// The root node the app is mounted into
+    Root,
+    /// An element like `div`
+    Element {
+        /// The tag of the element
+        tag: String,
+        /// The namespace of the element, like the svg namespace
+        namespace: Option<String>,
+    },
+    /// A text node
+    Text(String),
+    /// A placeholder for a node that renders nothing
+    Placeholder,
+}
+
+/// An attribute set on an element in a [`TestDom`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    /// The name of the attribute
+    pub name: String,
+    /// The namespace of the attribute. Styles set with attributes like `color: "red"` are in the `style` namespace.
+    pub namespace: Option<String>,
+    /// The value of the attribute
+    pub value: String,
+}
+
+#[derive(Debug)]
+struct NodeData {
+    kind: NodeKind,
+    element_id: Option<ElementId>,
+    attributes: Vec<Attribute>,
+    listeners: Vec<&'static str>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+impl NodeData {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            element_id: None,
+            attributes: Vec::new(),
+            listeners: Vec::new(),
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// An in-memory DOM tree that implements [`WriteMutations`].
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_testing::{Query, TestDom};
+///
+/// fn app() -> Element {
+///     rsx! { div { class: "greeting", "Hello world" } }
+/// }
+///
+/// let mut vdom = VirtualDom::new(app);
+/// let mut dom = TestDom::new();
+/// vdom.rebuild(&mut dom);
+///
+/// let greeting = dom.get(&Query::text("Hello world"));
+/// assert_eq!(dom.node(greeting).attribute("class"), Some("greeting"));
+/// assert_eq!(dom.html(), r#"<div class="greeting">Hello world</div>"#);
+/// ```
+#[derive(Debug)]
+pub struct TestDom {
+    nodes: Vec<NodeData>,
+    /// The node each [`ElementId`] currently points to, indexed by the id
+    element_ids: Vec<Option<NodeId>>,
+    stack: Vec<NodeId>,
+}
+
+impl Default for TestDom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestDom {
+    /// Create a new dom with just the root node
+    pub fn new() -> Self {
+        let mut root = NodeData::new(NodeKind::Root);
+        root.element_id = Some(ElementId(0));
+        Self {
+            nodes: vec![root],
+            element_ids: vec![Some(NodeId::ROOT)],
+            stack: Vec::new(),
+        }
+    }
+
+    /// Get a view of the node with the given id
+    pub fn node(&self, id: NodeId) -> Node<'_> {
+        assert!(id.0 < self.nodes.len(), "{id:?} is not part of this dom");
+        Node { dom: self, id }
+    }
+
+    /// Get a view of the root node
+    pub fn root(&self) -> Node<'_> {
+        self.node(NodeId::ROOT)
+    }
+
+    /// Get the node an [`ElementId`] from the VirtualDom currently points to
+    pub fn node_by_element_id(&self, id: ElementId) -> Option<Node<'_>> {
+        let node = (*self.element_ids.get(id.0)?)?;
+        Some(self.node(node))
+    }
+
+    /// Serialize the tree into html
+    pub fn html(&self) -> String {
+        self.root().inner_html()
+    }
+
+    /// Find every connected node that matches the query in document order
+    pub fn query_all(&self, query: &Query) -> Vec<NodeId> {
+        self.root()
+            .descendants()
+            .filter(|node| query.matches(node))
+            .map(|node| node.id())
+            .collect()
+    }
+
+    /// Find the first connected node that matches the query in document order
+    pub fn query(&self, query: &Query) -> Option<NodeId> {
+        self.root()
+            .descendants()
+            .find(|node| query.matches(node))
+            .map(|node| node.id())
+    }
+
+    /// Find the single connected node that matches the query.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no node or more than one node matches the query. The panic message includes the html of the dom.
+    #[track_caller]
+    pub fn get(&self, query: &Query) -> NodeId {
+        match self.query_all(query).as_slice() {
+            [node] => *node,
+            [] => panic!("No node matches {query}. The dom is:\n{}", self.html()),
+            nodes => panic!(
+                "{} nodes match {query}. Use `query_all` to find more than one node. The dom is:\n{}",
+                nodes.len(),
+                self.html()
+            ),
+        }
+    }
+
+    fn data(&self, id: NodeId) -> &NodeData {
+        &self.nodes[id.0]
+    }
+
+    fn data_mut(&mut self, id: NodeId) -> &mut NodeData {
+        &mut self.nodes[id.0]
+    }
+
+    fn create_node(&mut self, kind: NodeKind) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(NodeData::new(kind));
+        id
+    }
+
+    fn create_template_node(&mut self, node: &TemplateNode) -> NodeId {
+        match node {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let id = self.create_node(NodeKind::Element {
+                    tag: tag.to_string(),
+                    namespace: namespace.map(str::to_string),
+                });
+                for attr in attrs.iter() {
+                    if let TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } = attr
+                    {
+                        self.set_attribute_value(id, name, *namespace, Some(value));
+                    }
+                }
+                for child in children.iter() {
+                    let child = self.create_template_node(child);
+                    self.append_child(id, child);
+                }
+                id
+            }
+            TemplateNode::Text { text } => self.create_node(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.create_node(NodeKind::Placeholder),
+        }
+    }
+
+    fn assign_element_id(&mut self, node: NodeId, id: ElementId) {
+        if self.element_ids.len() <= id.0 {
+            self.element_ids.resize(id.0 + 1, None);
+        }
+        self.element_ids[id.0] = Some(node);
+        self.data_mut(node).element_id = Some(id);
+    }
+
+    fn element(&self, id: ElementId) -> NodeId {
+        self.element_ids
+            .get(id.0)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| panic!("{id:?} was never assigned to a node"))
+    }
+
+    /// Resolve a path of child indexes from the top of the stack
+    fn load_child(&self, path: &[u8]) -> NodeId {
+        let mut node = *self.stack.last().expect("the stack is empty");
+        for index in path {
+            node = self.data(node).children[*index as usize];
+        }
+        node
+    }
+
+    fn take_from_stack(&mut self, m: usize) -> Vec<NodeId> {
+        let start = self.stack.len() - m;
+        self.stack.split_off(start)
+    }
+
+    fn detach(&mut self, node: NodeId) {
+        if let Some(parent) = self.data_mut(node).parent.take() {
+            self.data_mut(parent)
+                .children
+                .retain(|child| *child != node);
+        }
+    }
+
+    fn append_child(&mut self, parent: NodeId, child: NodeId) {
+        self.detach(child);
+        self.data_mut(child).parent = Some(parent);
+        self.data_mut(parent).children.push(child);
+    }
+
+    /// Insert nodes next to a sibling. If `after` is true, the nodes are inserted after the sibling.
+    fn insert_siblings(&mut self, sibling: NodeId, nodes: Vec<NodeId>, after: bool) {
+        for node in &nodes {
+            self.detach(*node);
+        }
+        let parent = self
+            .data(sibling)
+            .parent
+            .expect("cannot insert a sibling next to a detached node");
+        let index = self.index_in_parent(sibling) + after as usize;
+        for node in &nodes {
+            self.data_mut(*node).parent = Some(parent);
+        }
+        self.data_mut(parent).children.splice(index..index, nodes);
+    }
+
+    fn replace_with(&mut self, old: NodeId, nodes: Vec<NodeId>) {
+        self.insert_siblings(old, nodes, true);
+        self.detach(old);
+    }
+
+    fn index_in_parent(&self, node: NodeId) -> usize {
+        let parent = self.data(node).parent.expect("node is detached");
+        self.data(parent)
+            .children
+            .iter()
+            .position(|child| *child == node)
+            .unwrap()
+    }
+
+    /// Set or remove an attribute the same way the interpreter does. Falsy boolean attributes are removed.
+    fn set_attribute_value(
+        &mut self,
+        node: NodeId,
+        name: &str,
+        namespace: Option<&str>,
+        value: Option<&str>,
+    ) {
+        let value = value.filter(|value| *value == "true" || !is_bool_attr(name));
+        let attributes = &mut self.data_mut(node).attributes;
+        let existing = attributes
+            .iter()
+            .position(|attr| attr.name == name && attr.namespace.as_deref() == namespace);
+        match (existing, value) {
+            (Some(index), Some(value)) => attributes[index].value = value.to_string(),
+            (Some(index), None) => {
+                attributes.remove(index);
+            }
+            (None, Some(value)) => attributes.push(Attribute {
+                name: name.to_string(),
+                namespace: namespace.map(str::to_string),
+                value: value.to_string(),
+            }),
+            (None, None) => {}
+        }
+    }
+
+    fn write_html(&self, node: NodeId, out: &mut String) {
+        let data = self.data(node);
+        match &data.kind {
+            NodeKind::Root => {
+                for child in &data.children {
+                    self.write_html(*child, out);
+                }
+            }
+            NodeKind::Element { tag, .. } => {
+                out.push('<');
+                out.push_str(tag);
+                let mut styles = String::new();
+                let mut inner_html = None;
+                for attr in &data.attributes {
+                    match (attr.namespace.as_deref(), attr.name.as_str()) {
+                        (Some("style"), name) => _ = write!(styles, "{name}:{};", attr.value),
+                        (_, "dangerous_inner_html") => inner_html = Some(&attr.value),
+                        (_, name) => {
+                            _ = write!(
+                                out,
+                                " {name}=\"{}\"",
+                                askama_escape::escape(&attr.value, askama_escape::Html)
+                            )
+                        }
+                    }
+                }
+                if !styles.is_empty() {
+                    _ = write!(
+                        out,
+                        " style=\"{}\"",
+                        askama_escape::escape(&styles, askama_escape::Html)
+                    );
+                }
+
+                if data.children.is_empty() && inner_html.is_none() && is_self_closing(tag) {
+                    out.push_str("/>");
+                    return;
+                }
+                out.push('>');
+                match inner_html {
+                    Some(inner_html) => out.push_str(inner_html),
+                    None => {
+                        for child in &data.children {
+                            self.write_html(*child, out);
+                        }
+                    }
+                }
+                _ = write!(out, "</{tag}>");
+            }
+            NodeKind::Text(text) => {
+                _ = write!(out, "{}", askama_escape::escape(text, askama_escape::Html));
+            }
+            NodeKind::Placeholder => {}
+        }
+    }
+}
+
+impl WriteMutations for TestDom {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        let parent = self.element(id);
+        for child in self.take_from_stack(m) {
+            self.append_child(parent, child);
+        }
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        let node = self.load_child(path);
+        self.assign_element_id(node, id);
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        let node = self.create_node(NodeKind::Placeholder);
+        self.assign_element_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        let node = self.create_node(NodeKind::Text(value.to_string()));
+        self.assign_element_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        let node = self.create_template_node(&template.roots[index]);
+        self.assign_element_id(node, id);
+        self.stack.push(node);
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        let old = self.element(id);
+        let nodes = self.take_from_stack(m);
+        self.replace_with(old, nodes);
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        // The nodes are taken off the stack before the path is resolved from the new top of the stack
+        let nodes = self.take_from_stack(m);
+        let old = self.load_child(path);
+        self.replace_with(old, nodes);
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        let sibling = self.element(id);
+        let nodes = self.take_from_stack(m);
+        self.insert_siblings(sibling, nodes, true);
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        let sibling = self.element(id);
+        let nodes = self.take_from_stack(m);
+        self.insert_siblings(sibling, nodes, false);
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        let node = self.element(id);
+        let value = match value {
+            AttributeValue::Text(text) => Some(text.clone()),
+            AttributeValue::Float(f) => Some(f.to_string()),
+            AttributeValue::Int(n) => Some(n.to_string()),
+            AttributeValue::Bool(b) => Some(b.to_string()),
+            AttributeValue::None => None,
+            // Listeners and arbitrary values are not sent to the dom
+            AttributeValue::Listener(_) | AttributeValue::Any(_) => return,
+        };
+        self.set_attribute_value(node, name, ns, value.as_deref());
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        let node = self.element(id);
+        self.data_mut(node).kind = NodeKind::Text(value.to_string());
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.element(id);
+        self.data_mut(node).listeners.push(name);
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        let node = self.element(id);
+        let listeners = &mut self.data_mut(node).listeners;
+        if let Some(index) = listeners.iter().position(|listener| *listener == name) {
+            listeners.remove(index);
+        }
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.detach(node);
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        let node = self.element(id);
+        self.stack.push(node);
+    }
+}
+
+/// A view of a node in a [`TestDom`].
+#[derive(Clone, Copy)]
+pub struct Node<'a> {
+    dom: &'a TestDom,
+    id: NodeId,
+}
+
+impl std::fmt::Debug for Node<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("id", &self.id)
+            .field("html", &self.outer_html())
+            .finish()
+    }
+}
+
+impl<'a> Node<'a> {
+    /// The id of the node
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// The kind of the node
+    pub fn kind(&self) -> &'a NodeKind {
+        &self.data().kind
+    }
+
+    /// The tag of the node if it is an element
+    pub fn tag(&self) -> Option<&'a str> {
+        match self.kind() {
+            NodeKind::Element { tag, .. } => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// The id the VirtualDom assigned to this node, if it has one
+    pub fn element_id(&self) -> Option<ElementId> {
+        self.data().element_id
+    }
+
+    /// Every attribute set on the node
+    pub fn attributes(&self) -> &'a [Attribute] {
+        &self.data().attributes
+    }
+
+    /// Get the value of an attribute without a namespace
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes()
+            .iter()
+            .find(|attr| attr.name == name && attr.namespace.is_none())
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// The names of the events the node listens to, like `click`
+    pub fn listeners(&self) -> &'a [&'static str] {
+        &self.data().listeners
+    }
+
+    /// Check if the node has a listener for the event with the given name, like `click`
+    pub fn has_listener(&self, event: &str) -> bool {
+        self.data().listeners.contains(&event)
+    }
+
+    /// The parent of the node if it is in the tree
+    pub fn parent(&self) -> Option<Node<'a>> {
+        self.data().parent.map(|parent| self.dom.node(parent))
+    }
+
+    /// The children of the node
+    pub fn children(&self) -> impl Iterator<Item = Node<'a>> + 'a {
+        let dom = self.dom;
+        self.data()
+            .children
+            .iter()
+            .map(move |child| dom.node(*child))
+    }
+
+    /// Every descendant of the node in document order, not including the node itself
+    pub fn descendants(&self) -> impl Iterator<Item = Node<'a>> + 'a {
+        let dom = self.dom;
+        let mut stack: Vec<NodeId> = self.data().children.iter().rev().copied().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(dom.data(node).children.iter().rev());
+            Some(dom.node(node))
+        })
+    }
+
+    /// Check if the node is still part of the tree that is mounted to the root
+    pub fn is_connected(&self) -> bool {
+        let mut node = *self;
+        loop {
+            if node.id == NodeId::ROOT {
+                return true;
+            }
+            match node.parent() {
+                Some(parent) => node = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// The text of the node and all of its descendants
+    pub fn text_content(&self) -> String {
+        match self.kind() {
+            NodeKind::Text(text) => text.clone(),
+            _ => self
+                .descendants()
+                .filter_map(|node| match node.kind() {
+                    NodeKind::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// The text of the text nodes that are direct children of this node
+    pub fn own_text(&self) -> String {
+        self.children()
+            .filter_map(|node| match node.kind() {
+                NodeKind::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Serialize the node and its children into html
+    pub fn outer_html(&self) -> String {
+        let mut html = String::new();
+        self.dom.write_html(self.id, &mut html);
+        html
+    }
+
+    /// Serialize the children of the node into html
+    pub fn inner_html(&self) -> String {
+        let mut html = String::new();
+        for child in &self.data().children {
+            self.dom.write_html(*child, &mut html);
+        }
+        html
+    }
+
+    fn data(&self) -> &'a NodeData {
+        self.dom.data(self.id)
+    }
+}
+
+fn is_bool_attr(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "allowpaymentrequest"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+            | "truespeed"
+            | "webkitdirectory"
+    )
+}
+
+pub(crate) fn is_self_closing(tag: &str) -> bool {
+    matches!(
+        tag,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
@@ -0,0 +1,13 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![warn(missing_docs)]
+
+mod app;
+pub mod dom;
+pub mod query;
+pub mod snapshot;
+
+pub use app::TestApp;
+pub use dom::{Attribute, Node, NodeId, NodeKind, TestDom};
+pub use query::Query;
@@ -0,0 +1,89 @@
+//! Queries that find nodes in a [`TestDom`](crate::TestDom).
+
+use crate::Node;
+
+/// A query that matches nodes in a [`TestDom`](crate::TestDom).
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_testing::{Query, TestApp};
+///
+/// fn app() -> Element {
+///     rsx! {
+///         form {
+///             label { r#for: "name", "Name" }
+///             input { id: "name", name: "name" }
+///             button { r#type: "submit", "Save" }
+///         }
+///     }
+/// }
+///
+/// let app = TestApp::new(app);
+/// let input = app.get(&Query::id("name"));
+/// assert_eq!(app.query(&Query::attribute("name", "name")), Some(input));
+/// assert_eq!(app.query_all(&Query::tag("button")).len(), 1);
+/// assert!(app.query(&Query::text("Delete")).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Query {
+    /// Match elements with the given tag
+    Tag(String),
+    /// Match elements whose own text (the text nodes directly inside them), with surrounding whitespace trimmed, is
+    /// equal to the given text
+    Text(String),
+    /// Match elements with an attribute set to the given value
+    Attribute {
+        /// The name of the attribute
+        name: String,
+        /// The value of the attribute
+        value: String,
+    },
+}
+
+impl Query {
+    /// Match elements with the given tag
+    pub fn tag(tag: impl Into<String>) -> Self {
+        Self::Tag(tag.into())
+    }
+
+    /// Match elements whose own text is equal to the given text
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Match elements with an attribute set to the given value
+    pub fn attribute(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::Attribute {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Match the element with the given `id` attribute
+    pub fn id(id: impl Into<String>) -> Self {
+        Self::attribute("id", id)
+    }
+
+    /// Check if a node matches the query
+    pub fn matches(&self, node: &Node) -> bool {
+        if node.tag().is_none() {
+            return false;
+        }
+        match self {
+            Query::Tag(tag) => node.tag() == Some(tag.as_str()),
+            Query::Text(text) => node.own_text().trim() == text.trim(),
+            Query::Attribute { name, value } => node.attribute(name) == Some(value.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Query::Tag(tag) => write!(f, "tag {tag:?}"),
+            Query::Text(text) => write!(f, "text {text:?}"),
+            Query::Attribute { name, value } => write!(f, "attribute {name}={value:?}"),
+        }
+    }
+}
@@ -0,0 +1,570 @@
+//! Compare the html a component renders against snapshot files on disk.
+//!
+//! Snapshots are rendered with [`dioxus_ssr::Renderer`] and normalized into one node per line with sorted
+//! attributes, so a change to an `rsx!` template shows up as a small, readable diff of the snapshot file in code
+//! review.
+//!
+//! The easiest way to check a snapshot is the [`assert_snapshot!`](crate::assert_snapshot) macro, which stores
+//! snapshots in the `tests/snapshots` directory of the crate being tested:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus_testing::{assert_snapshot, TestApp};
+//!
+//! fn app() -> Element {
+//!     rsx! { div { class: "card", h1 { "Hello world" } } }
+//! }
+//!
+//! let app = TestApp::new(app);
+//! // Compares against tests/snapshots/card.html
+//! assert_snapshot!("card", app);
+//! ```
+//!
+//! # Reviewing snapshots
+//!
+//! What happens when a snapshot is missing or doesn't match is controlled by the `DIOXUS_SNAPSHOTS` environment
+//! variable:
+//!
+//! - `review` (the default): the new render is written next to the snapshot as `<name>.html.new` and the test fails
+//!   with a diff. Review the pending file and rename it over the snapshot to accept it.
+//! - `accept`: the snapshot is overwritten with the new render and the test passes. `dx test --accept-snapshots`
+//!   runs the tests in this mode.
+//! - `check`: nothing is written and the test fails with a diff. This is the default when the `CI` environment
+//!   variable is set.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use dioxus_core::VirtualDom;
+
+use crate::{dom::is_self_closing, TestApp};
+
+/// Renders a [`VirtualDom`] into a normalized snapshot.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// use dioxus_testing::snapshot::SnapshotRenderer;
+///
+/// fn app() -> Element {
+///     rsx! { div { id: "main", class: "card", "Hello {\"world\"}" } }
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+///
+/// assert_eq!(
+///     SnapshotRenderer::new().render(&dom),
+///     "<div class=\"card\" id=\"main\">Hello world</div>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotRenderer {
+    hydration_ids: bool,
+}
+
+impl SnapshotRenderer {
+    /// Create a renderer that strips hydration ids
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the hydration ids and markers that fullstack uses to hydrate the page. Defaults to false.
+    pub fn hydration_ids(mut self, hydration_ids: bool) -> Self {
+        self.hydration_ids = hydration_ids;
+        self
+    }
+
+    /// Render the VirtualDom into a normalized snapshot
+    pub fn render(&self, dom: &VirtualDom) -> String {
+        let mut renderer = dioxus_ssr::Renderer::new();
+        renderer.pre_render = self.hydration_ids;
+        normalize_html(&renderer.render(dom))
+    }
+}
+
+/// A value that can be rendered into a snapshot
+pub trait Snapshot {
+    /// Render the value into a normalized snapshot
+    fn snapshot(&self) -> String;
+}
+
+impl Snapshot for VirtualDom {
+    fn snapshot(&self) -> String {
+        SnapshotRenderer::new().render(self)
+    }
+}
+
+impl Snapshot for TestApp {
+    fn snapshot(&self) -> String {
+        self.vdom().snapshot()
+    }
+}
+
+impl Snapshot for str {
+    fn snapshot(&self) -> String {
+        normalize_html(self)
+    }
+}
+
+impl Snapshot for String {
+    fn snapshot(&self) -> String {
+        normalize_html(self)
+    }
+}
+
+impl<T: Snapshot + ?Sized> Snapshot for &T {
+    fn snapshot(&self) -> String {
+        T::snapshot(self)
+    }
+}
+
+/// What to do when a snapshot is missing or doesn't match the new render
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    /// Write the new render next to the snapshot as `<name>.html.new` and fail
+    Review,
+    /// Overwrite the snapshot with the new render and pass
+    Accept,
+    /// Fail without writing anything
+    Check,
+}
+
+impl SnapshotMode {
+    /// Read the mode from the `DIOXUS_SNAPSHOTS` environment variable. If it isn't set, the mode is
+    /// [`SnapshotMode::Check`] on CI and [`SnapshotMode::Review`] everywhere else.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the environment variable is set to an unknown mode.
+    pub fn from_env() -> Self {
+        match std::env::var("DIOXUS_SNAPSHOTS").as_deref() {
+            Ok("review") => Self::Review,
+            Ok("accept") => Self::Accept,
+            Ok("check") => Self::Check,
+            Ok(other) => panic!(
+                "Unknown snapshot mode DIOXUS_SNAPSHOTS={other:?}. Expected review, accept or check"
+            ),
+            Err(_) if std::env::var_os("CI").is_some() => Self::Check,
+            Err(_) => Self::Review,
+        }
+    }
+}
+
+/// A snapshot that is missing or doesn't match the new render
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotMismatch {
+    /// The path of the snapshot file
+    pub path: PathBuf,
+    /// The contents of the snapshot file, if it exists
+    pub expected: Option<String>,
+    /// The new render
+    pub actual: String,
+    /// The path the new render was written to for review, if it was written
+    pub pending: Option<PathBuf>,
+}
+
+impl std::fmt::Display for SnapshotMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.expected {
+            Some(expected) => {
+                writeln!(f, "Snapshot {} does not match:", self.path.display())?;
+                write!(f, "{}", diff_lines(expected, &self.actual))?;
+            }
+            None => {
+                writeln!(
+                    f,
+                    "Snapshot {} does not exist. New snapshot:",
+                    self.path.display()
+                )?;
+                for line in self.actual.lines() {
+                    writeln!(f, "+{line}")?;
+                }
+            }
+        }
+        match &self.pending {
+            Some(pending) => write!(
+                f,
+                "The new snapshot was written to {}. Move it over the snapshot to accept it, or run the tests with DIOXUS_SNAPSHOTS=accept to accept every new snapshot.",
+                pending.display()
+            ),
+            None => write!(
+                f,
+                "Run the tests with DIOXUS_SNAPSHOTS=accept to accept the new snapshot."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotMismatch {}
+
+/// Compare a snapshot against the file `<name>.html` in a directory.
+///
+/// If the snapshot matches, any pending snapshot left over from an earlier run is removed.
+///
+/// # Panics
+///
+/// Panics if the snapshot files can't be read or written.
+pub fn check_snapshot(
+    dir: impl AsRef<Path>,
+    name: &str,
+    actual: &str,
+    mode: SnapshotMode,
+) -> Result<(), SnapshotMismatch> {
+    let path = dir.as_ref().join(format!("{name}.html"));
+    let pending_path = dir.as_ref().join(format!("{name}.html.new"));
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => Some(expected),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => panic!("Failed to read snapshot {}: {err}", path.display()),
+    };
+
+    if expected.as_deref() == Some(actual) {
+        match std::fs::remove_file(&pending_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                panic!("Failed to remove {}: {err}", pending_path.display())
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    let write = |path: &Path| {
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(path, actual))
+            .unwrap_or_else(|err| panic!("Failed to write {}: {err}", path.display()));
+    };
+
+    match mode {
+        SnapshotMode::Accept => {
+            write(&path);
+            _ = std::fs::remove_file(&pending_path);
+            Ok(())
+        }
+        SnapshotMode::Review => {
+            write(&pending_path);
+            Err(SnapshotMismatch {
+                path,
+                expected,
+                actual: actual.to_string(),
+                pending: Some(pending_path),
+            })
+        }
+        SnapshotMode::Check => Err(SnapshotMismatch {
+            path,
+            expected,
+            actual: actual.to_string(),
+            pending: None,
+        }),
+    }
+}
+
+/// Assert that a snapshot matches the file `<name>.html` in a directory. The [`SnapshotMode`] is read from the
+/// environment.
+///
+/// Most tests should use [`assert_snapshot!`](crate::assert_snapshot) instead, which finds the snapshot directory of
+/// the crate for you.
+#[track_caller]
+pub fn assert_snapshot_in(dir: impl AsRef<Path>, name: &str, snapshot: &impl Snapshot) {
+    if let Err(mismatch) = check_snapshot(dir, name, &snapshot.snapshot(), SnapshotMode::from_env())
+    {
+        panic!("{mismatch}");
+    }
+}
+
+/// Assert that the html a component renders matches a snapshot in the `tests/snapshots` directory of the current
+/// crate.
+///
+/// The second argument can be a [`TestApp`], a [`VirtualDom`], or html that was already rendered. See the
+/// [`snapshot`](crate::snapshot) module for how to review and accept new snapshots.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_testing::{assert_snapshot, snapshot::SnapshotRenderer};
+///
+/// fn app() -> Element {
+///     rsx! { "Hello world" }
+/// }
+///
+/// let mut dom = VirtualDom::new(app);
+/// dom.rebuild_in_place();
+/// assert_snapshot!("hello", dom);
+///
+/// // Keep the hydration ids
+/// assert_snapshot!("hello_hydrated", SnapshotRenderer::new().hydration_ids(true).render(&dom));
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $snapshot:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot_in(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots"),
+            $name,
+            &$snapshot,
+        )
+    };
+}
+
+/// Normalize html into one node per line with sorted attributes and collapsed whitespace.
+///
+/// Elements that only contain text are kept on a single line.
+///
+/// ```rust
+/// use dioxus_testing::snapshot::normalize_html;
+///
+/// assert_eq!(
+///     normalize_html(r#"<ul id="list" class="items"><li>  One  </li><li>Two<br/></li></ul>"#),
+///     "<ul class=\"items\" id=\"list\">\n  <li>One</li>\n  <li>\n    Two\n    <br/>\n  </li>\n</ul>\n"
+/// );
+/// ```
+pub fn normalize_html(html: &str) -> String {
+    let nodes = parse_html(html);
+    let mut out = String::new();
+    for node in &nodes {
+        write_node(node, 0, &mut out);
+    }
+    out
+}
+
+#[derive(Debug)]
+enum HtmlNode<'a> {
+    Element {
+        tag: &'a str,
+        attributes: Attributes<'a>,
+        self_closing: bool,
+        children: Vec<HtmlNode<'a>>,
+    },
+    Text(String),
+    Comment(&'a str),
+}
+
+type Attributes<'a> = Vec<(&'a str, Option<&'a str>)>;
+
+/// An element that is still being parsed
+struct OpenElement<'a> {
+    tag: &'a str,
+    attributes: Attributes<'a>,
+    children: Vec<HtmlNode<'a>>,
+}
+
+impl<'a> OpenElement<'a> {
+    fn close(self) -> HtmlNode<'a> {
+        HtmlNode::Element {
+            tag: self.tag,
+            attributes: self.attributes,
+            self_closing: false,
+            children: self.children,
+        }
+    }
+}
+
+/// Parse the html the ssr renderer writes into a tree. Unknown syntax is kept as text.
+fn parse_html(html: &str) -> Vec<HtmlNode<'_>> {
+    // The first entry holds the root nodes
+    let mut stack = vec![OpenElement {
+        tag: "",
+        attributes: Vec::new(),
+        children: Vec::new(),
+    }];
+    let close = |stack: &mut Vec<OpenElement<'_>>| {
+        let element = stack.pop().unwrap().close();
+        stack.last_mut().unwrap().children.push(element);
+    };
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            let siblings = &mut stack.last_mut().unwrap().children;
+            siblings.push(HtmlNode::Comment(&comment[..end]));
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if let Some(close_tag) = rest.strip_prefix("</") {
+            let end = close_tag.find('>').unwrap_or(close_tag.len());
+            let tag = close_tag[..end].trim();
+            rest = close_tag.get(end + 1..).unwrap_or_default();
+            // Close every element up to and including the matching open element
+            if stack.iter().skip(1).any(|open| open.tag == tag) {
+                while stack.last().unwrap().tag != tag {
+                    close(&mut stack);
+                }
+                close(&mut stack);
+            }
+        } else if let Some((tag, attributes, self_closing, after)) = parse_open_tag(rest) {
+            rest = after;
+            if self_closing || is_self_closing(tag) {
+                stack.last_mut().unwrap().children.push(HtmlNode::Element {
+                    tag,
+                    attributes,
+                    self_closing: true,
+                    children: Vec::new(),
+                });
+            } else if matches!(tag, "script" | "style") {
+                // The contents of raw text elements are not html
+                let end = rest.find(&format!("</{tag}")).unwrap_or(rest.len());
+                let children = match rest[..end].trim() {
+                    "" => Vec::new(),
+                    text => vec![HtmlNode::Text(text.to_string())],
+                };
+                rest = &rest[end..];
+                stack.push(OpenElement {
+                    tag,
+                    attributes,
+                    children,
+                });
+            } else {
+                stack.push(OpenElement {
+                    tag,
+                    attributes,
+                    children: Vec::new(),
+                });
+            }
+        } else {
+            // Text runs until the next tag. A `<` that doesn't start a tag is part of the text
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map(|end| end + first)
+                .unwrap_or(rest.len());
+            let text = rest[..end].split_whitespace().collect::<Vec<_>>().join(" ");
+            rest = &rest[end..];
+            if text.is_empty() {
+                continue;
+            }
+            // Merge text that was split by a stray `<`
+            let siblings = &mut stack.last_mut().unwrap().children;
+            match siblings.last_mut() {
+                Some(HtmlNode::Text(previous)) => {
+                    previous.push(' ');
+                    previous.push_str(&text);
+                }
+                _ => siblings.push(HtmlNode::Text(text)),
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    stack.pop().unwrap().children
+}
+
+type OpenTag<'a> = (&'a str, Attributes<'a>, bool, &'a str);
+
+/// Parse an opening tag like `<div class="a">`, returning the tag, attributes, whether the tag closes itself and the
+/// rest of the input
+fn parse_open_tag(html: &str) -> Option<OpenTag<'_>> {
+    let mut rest = html.strip_prefix('<')?;
+    let tag_end = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    let tag = &rest[..tag_end];
+    if tag.is_empty() || !tag.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    rest = &rest[tag_end..];
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return Some((tag, attributes, true, after));
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return Some((tag, attributes, false, after));
+        }
+
+        let name_end = rest.find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))?;
+        let name = &rest[..name_end];
+        if name.is_empty() {
+            return None;
+        }
+        rest = rest[name_end..].trim_start();
+
+        let value = match rest.strip_prefix('=') {
+            Some(value) => {
+                let value = value.trim_start();
+                let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+                let value = &value[1..];
+                let end = value.find(quote)?;
+                rest = &value[end + 1..];
+                Some(&value[..end])
+            }
+            None => None,
+        };
+        attributes.push((name, value));
+    }
+}
+
+fn write_node(node: &HtmlNode, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    match node {
+        HtmlNode::Text(text) => _ = writeln!(out, "{indent}{text}"),
+        HtmlNode::Comment(comment) => _ = writeln!(out, "{indent}<!--{comment}-->"),
+        HtmlNode::Element {
+            tag,
+            attributes,
+            self_closing,
+            children,
+        } => {
+            let mut attributes = attributes.clone();
+            attributes.sort();
+            _ = write!(out, "{indent}<{tag}");
+            for (name, value) in attributes {
+                match value {
+                    Some(value) => _ = write!(out, " {name}=\"{value}\""),
+                    None => _ = write!(out, " {name}"),
+                }
+            }
+
+            match children.as_slice() {
+                [] if *self_closing => _ = writeln!(out, "/>"),
+                [] => _ = writeln!(out, "></{tag}>"),
+                [HtmlNode::Text(text)] => _ = writeln!(out, ">{text}</{tag}>"),
+                children => {
+                    out.push_str(">\n");
+                    for child in children {
+                        write_node(child, depth + 1, out);
+                    }
+                    _ = writeln!(out, "{indent}</{tag}>");
+                }
+            }
+        }
+    }
+}
+
+/// A line by line diff of two snapshots. Removed lines start with `-`, added lines with `+` and unchanged lines with
+/// a space.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    // The length of the longest common subsequence of the suffixes starting at each pair of lines
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            _ = writeln!(diff, " {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            _ = writeln!(diff, "-{}", expected[i]);
+            i += 1;
+        } else {
+            _ = writeln!(diff, "+{}", actual[j]);
+            j += 1;
+        }
+    }
+    diff
+}
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_testing::{Query, TestApp};
+
+#[test]
+fn queries_find_nodes() {
+    fn app() -> Element {
+        rsx! {
+            div { id: "main",
+                h1 { "Title" }
+                p { class: "body", "First" }
+                p { class: "body", "Second" }
+            }
+        }
+    }
+
+    let app = TestApp::new(app);
+    let main = app.get(&Query::id("main"));
+    assert_eq!(app.node(main).tag(), Some("div"));
+    assert_eq!(app.query_all(&Query::tag("p")).len(), 2);
+    assert_eq!(app.query_all(&Query::attribute("class", "body")).len(), 2);
+
+    let second = app.get(&Query::text("Second"));
+    assert_eq!(app.node(second).parent().unwrap().id(), main);
+    assert_eq!(app.node(main).text_content(), "TitleFirstSecond");
+    assert!(app.query(&Query::tag("span")).is_none());
+}
+
+#[test]
+#[should_panic(expected = "2 nodes match tag \"p\"")]
+fn get_panics_on_multiple_matches() {
+    let app = TestApp::new(|| rsx! { p {} p {} });
+    app.get(&Query::tag("p"));
+}
+
+#[test]
+fn click_bubbles_to_the_listener() {
+    fn app() -> Element {
+        let mut count = use_signal(|| 0);
+        rsx! {
+            button { onclick: move |_| count += 1,
+                span { "Count: {count}" }
+            }
+        }
+    }
+
+    let mut app = TestApp::new(app);
+    let label = app.get(&Query::tag("span"));
+    app.click(label);
+    app.click(label);
+    assert_eq!(app.html(), "<button><span>Count: 2</span></button>");
+}
+
+#[test]
+fn input_keydown_and_submit() {
+    fn app() -> Element {
+        let mut draft = use_signal(String::new);
+        let mut todos = use_signal(Vec::<String>::new);
+        let mut submitted = use_signal(String::new);
+
+        rsx! {
+            form { onsubmit: move |event| submitted.set(event.values()["title"].as_value()),
+                input {
+                    name: "title",
+                    value: "{draft}",
+                    oninput: move |event| draft.set(event.value()),
+                    onkeydown: move |event| {
+                        if event.key() == Key::Enter {
+                            todos.push(draft.take());
+                        }
+                    },
+                }
+            }
+            ul {
+                for todo in todos.iter() {
+                    li { "{todo}" }
+                }
+            }
+            p { "Submitted: {submitted}" }
+        }
+    }
+
+    let mut app = TestApp::new(app);
+    let input = app.get(&Query::tag("input"));
+
+    app.input(input, "first");
+    assert_eq!(app.node(input).attribute("value"), Some("first"));
+    app.keydown(input, Key::Enter);
+    app.input(input, "second");
+    app.keydown(input, Key::Enter);
+    assert_eq!(app.query_all(&Query::tag("li")).len(), 2);
+    assert_eq!(app.node(input).attribute("value"), Some(""));
+
+    // Other keys are ignored
+    app.keydown(input, Key::Escape);
+    assert_eq!(app.query_all(&Query::tag("li")).len(), 2);
+
+    let form = app.get(&Query::tag("form"));
+    app.submit(form, [("title", "from the form")]);
+    assert!(app
+        .query(&Query::text("Submitted: from the form"))
+        .is_some());
+}
+
+#[test]
+fn removed_nodes_are_disconnected() {
+    fn app() -> Element {
+        let mut show = use_signal(|| true);
+        rsx! {
+            button { onclick: move |_| show.toggle(), "Toggle" }
+            if show() {
+                p { autofocus: false, hidden: true, "Now you see me" }
+            }
+        }
+    }
+
+    let mut app = TestApp::new(app);
+    let paragraph = app.get(&Query::tag("p"));
+    assert_eq!(
+        app.node(paragraph).outer_html(),
+        "<p hidden=\"true\">Now you see me</p>"
+    );
+
+    let button = app.get(&Query::tag("button"));
+    app.click(button);
+    assert!(!app.node(paragraph).is_connected());
+    assert!(app.query(&Query::tag("p")).is_none());
+
+    app.click(button);
+    assert!(app.query(&Query::tag("p")).is_some());
+}
+
+#[test]
+fn keyed_lists_keep_their_order() {
+    fn app() -> Element {
+        let mut items = use_signal(|| vec![1, 2, 3, 4]);
+        rsx! {
+            button { onclick: move |_| items.write().reverse(), "Reverse" }
+            button { onclick: move |_| items.write().retain(|i| i % 2 == 0), "Evens" }
+            button { onclick: move |_| items.write().insert(1, 5), "Insert" }
+            ul {
+                for item in items.iter() {
+                    li { key: "{item}", "{item}" }
+                }
+            }
+        }
+    }
+
+    let mut app = TestApp::new(app);
+    let list = app.get(&Query::tag("ul"));
+    let items = |app: &TestApp| app.node(list).text_content();
+    assert_eq!(items(&app), "1234");
+
+    app.click(app.get(&Query::text("Reverse")));
+    assert_eq!(items(&app), "4321");
+    app.click(app.get(&Query::text("Evens")));
+    assert_eq!(items(&app), "42");
+    app.click(app.get(&Query::text("Insert")));
+    assert_eq!(items(&app), "452");
+}
+
+#[tokio::test]
+async fn wait_until_idle_resolves_suspense() {
+    fn app() -> Element {
+        rsx! {
+            SuspenseBoundary { fallback: |_| rsx! { "Loading..." },
+                Profile {}
+            }
+        }
+    }
+
+    #[component]
+    fn Profile() -> Element {
+        let name = use_resource(|| async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "Ferris"
+        })
+        .suspend()?;
+        rsx! { h1 { "{name}" } }
+    }
+
+    let mut app = TestApp::new(app);
+    assert_eq!(app.html(), "Loading...");
+    app.wait_until_idle().await;
+    assert_eq!(app.html(), "<h1>Ferris</h1>");
+}
+
+#[tokio::test]
+async fn wait_until_idle_runs_ready_tasks() {
+    fn app() -> Element {
+        let mut message = use_signal(|| "waiting");
+        use_future(move || async move {
+            // Yielding doesn't keep the app busy forever
+            tokio::task::yield_now().await;
+            message.set("done");
+        });
+        rsx! { "{message}" }
+    }
+
+    let mut app = TestApp::new(app);
+    app.wait_until_idle().await;
+    assert_eq!(app.html(), "done");
+}
+
+#[tokio::test]
+async fn wait_until_timer_tasks_finish() {
+    fn app() -> Element {
+        let mut ticks = use_signal(|| 0);
+        use_future(move || async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                ticks += 1;
+            }
+        });
+        rsx! { "Ticks: {ticks}" }
+    }
+
+    let mut app = TestApp::new(app);
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        app.wait_until(|dom| dom.html() == "Ticks: 3"),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+use dioxus_testing::{
+    assert_snapshot,
+    snapshot::{check_snapshot, normalize_html, SnapshotMode, SnapshotRenderer},
+    Query, TestApp,
+};
+
+fn todo_list() -> Element {
+    let mut todos = use_signal(|| vec!["Write tests".to_string()]);
+    rsx! {
+        section { id: "todos", class: "todo-list",
+            h1 { "Todos" }
+            ul {
+                for todo in todos.iter() {
+                    li { class: "todo", "{todo}" }
+                }
+            }
+            button { onclick: move |_| todos.push("Review snapshots".to_string()), "Add" }
+        }
+    }
+}
+
+#[test]
+fn snapshots_match_files() {
+    let mut app = TestApp::new(todo_list);
+    assert_snapshot!("todo_list", app);
+
+    app.click(app.get(&Query::tag("button")));
+    assert_snapshot!("todo_list_after_add", app);
+}
+
+#[test]
+fn hydration_ids_are_stripped_by_default() {
+    let mut dom = VirtualDom::new(todo_list);
+    dom.rebuild_in_place();
+
+    let stripped = SnapshotRenderer::new().render(&dom);
+    assert!(!stripped.contains("data-node-hydration"));
+
+    let hydrated = SnapshotRenderer::new().hydration_ids(true).render(&dom);
+    assert!(hydrated.contains("data-node-hydration"));
+}
+
+#[test]
+fn normalizes_whitespace_and_attribute_order() {
+    let compact =
+        r#"<div id="a" class="b"><p>Hello   world</p><input value="x" type="text"/></div>"#;
+    let spaced = r#"
+        <div class="b"  id="a">
+            <p>
+                Hello
+                world
+            </p>
+            <input type="text" value="x">
+        </div>
+    "#;
+    assert_eq!(normalize_html(compact), normalize_html(spaced));
+    assert_eq!(
+        normalize_html(compact),
+        "<div class=\"b\" id=\"a\">\n  <p>Hello world</p>\n  <input type=\"text\" value=\"x\"/>\n</div>\n"
+    );
+}
+
+#[test]
+fn text_can_start_with_non_ascii_characters() {
+    fn app() -> Element {
+        rsx! {
+            p { "é" }
+            p { "🦀 < 🐍" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(
+        SnapshotRenderer::new().render(&dom),
+        "<p>é</p>\n<p>🦀 &lt; 🐍</p>\n"
+    );
+    assert_eq!(
+        normalize_html("<p>é<br/>🦀 < 🐍</p>"),
+        "<p>\n  é\n  <br/>\n  🦀 < 🐍\n</p>\n"
+    );
+}
+
+#[test]
+fn review_mode_writes_pending_snapshots() {
+    let dir = std::env::temp_dir().join(format!("dioxus-testing-review-{}", std::process::id()));
+    _ = std::fs::remove_dir_all(&dir);
+
+    // Missing snapshots are written for review
+    let mismatch = check_snapshot(&dir, "page", "<p>One</p>\n", SnapshotMode::Review).unwrap_err();
+    assert_eq!(mismatch.expected, None);
+    let pending = dir.join("page.html.new");
+    assert_eq!(mismatch.pending.as_ref(), Some(&pending));
+    assert_eq!(std::fs::read_to_string(&pending).unwrap(), "<p>One</p>\n");
+
+    // Accepting the pending file makes the snapshot pass and cleans up the pending file
+    std::fs::rename(&pending, dir.join("page.html")).unwrap();
+    check_snapshot(&dir, "page", "<p>One</p>\n", SnapshotMode::Review).unwrap();
+
+    // Changes are reported as a diff
+    let mismatch = check_snapshot(&dir, "page", "<p>Two</p>\n", SnapshotMode::Check).unwrap_err();
+    assert!(mismatch.pending.is_none());
+    assert!(!pending.exists());
+    assert!(mismatch.to_string().contains("-<p>One</p>\n+<p>Two</p>\n"));
+
+    // Accept mode overwrites the snapshot
+    check_snapshot(&dir, "page", "<p>Two</p>\n", SnapshotMode::Accept).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("page.html")).unwrap(),
+        "<p>Two</p>\n"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
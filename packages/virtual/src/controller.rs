@@ -0,0 +1,41 @@
+use dioxus_lib::prelude::*;
+
+/// A handle that scrolls a [`VirtualList`](crate::VirtualList) or [`VirtualGrid`](crate::VirtualGrid) from outside
+/// of the component.
+///
+/// Create a controller with [`use_virtual_controller`] and pass it to the `controller` prop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VirtualController {
+    pub(crate) scroll_request: Signal<Option<usize>>,
+}
+
+impl VirtualController {
+    /// Scroll the item with the given index into view. Items that are not rendered yet are rendered first.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        self.scroll_request.set(Some(index));
+    }
+}
+
+/// Create a [`VirtualController`] to scroll a virtualized component.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_virtual::{use_virtual_controller, VirtualList};
+///
+/// fn app() -> Element {
+///     let mut controller = use_virtual_controller();
+///     rsx! {
+///         button { onclick: move |_| controller.scroll_to_index(9_999), "Jump to the end" }
+///         VirtualList {
+///             style: "height: 400px",
+///             count: 10_000,
+///             controller,
+///             item: move |index| rsx! { "Row {index}" },
+///         }
+///     }
+/// }
+/// ```
+pub fn use_virtual_controller() -> VirtualController {
+    let scroll_request = use_signal(|| None);
+    VirtualController { scroll_request }
+}
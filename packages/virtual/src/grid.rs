@@ -0,0 +1,159 @@
+use dioxus_lib::prelude::*;
+
+use crate::scroll::{use_virtual_scroll, VirtualScrollOptions};
+use crate::VirtualController;
+
+/// The properties for a [`VirtualGrid`].
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualGridProps {
+    /// The number of items in the grid.
+    pub count: usize,
+
+    /// The number of items in each row.
+    pub columns: usize,
+
+    /// Render the item at an index.
+    pub item: Callback<usize, Element>,
+
+    /// A stable key for the item at an index. Defaults to the index of the item.
+    #[props(default)]
+    pub item_key: Option<Callback<usize, String>>,
+
+    /// The height of rows that haven't been measured yet, in pixels. Defaults to 120.
+    #[props(default = 120.0)]
+    pub estimated_row_height: f64,
+
+    /// The number of extra rows to render above and below the visible rows. Defaults to 2.
+    #[props(default = 2)]
+    pub overscan: usize,
+
+    /// The number of rows to render before the grid is mounted and its height is known, like during server side
+    /// rendering. Defaults to 5.
+    #[props(default = 5)]
+    pub initial_rows: usize,
+
+    /// A header that stays at the top of the grid while it scrolls.
+    #[props(default)]
+    pub header: Option<Element>,
+
+    /// The gap between items, like `8px`. Defaults to no gap.
+    #[props(default)]
+    pub gap: Option<String>,
+
+    /// A controller that can scroll the grid to an item. Create one with
+    /// [`use_virtual_controller`](crate::use_virtual_controller).
+    #[props(default)]
+    pub controller: Option<VirtualController>,
+
+    /// Additional attributes for the scroll container. The container must have a fixed height, for example with the
+    /// `style` or `class` attributes.
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A grid that only renders the rows that are visible in its scroll container.
+///
+/// Items are laid out in rows of `columns` items with equal widths. Rows can have different heights and are measured
+/// once they mount, like the items of a [`VirtualList`](crate::VirtualList).
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_virtual::VirtualGrid;
+///
+/// fn app() -> Element {
+///     rsx! {
+///         VirtualGrid {
+///             style: "height: 100vh",
+///             count: 10_000,
+///             columns: 4,
+///             gap: "8px",
+///             item: move |index| rsx! { img { src: "/photos/{index}.jpg" } },
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualGrid(props: VirtualGridProps) -> Element {
+    let VirtualGridProps {
+        count,
+        columns,
+        item,
+        item_key,
+        estimated_row_height,
+        overscan,
+        initial_rows,
+        header,
+        gap,
+        controller,
+        attributes,
+    } = props;
+    let columns = columns.max(1);
+
+    let scroll = use_virtual_scroll(VirtualScrollOptions {
+        rows: count.div_ceil(columns),
+        estimated_row_height,
+        overscan,
+        initial_rows,
+        items_per_row: columns,
+        // Rows hold several items, so they are keyed by their index
+        row_keys: None,
+        controller,
+    });
+
+    let rows = scroll.rendered_rows();
+    let offset = scroll.offset(rows.start);
+    let total_height = scroll.total_height();
+    let gap = gap.unwrap_or_else(|| "0".to_string());
+
+    let key = move |index: usize| match item_key {
+        Some(item_key) => item_key(index),
+        None => index.to_string(),
+    };
+
+    rsx! {
+        div {
+            overflow_y: "auto",
+            position: "relative",
+            onmounted: move |event| scroll.container_mounted(event),
+            onscroll: move |_| scroll.container_scrolled(),
+            onresize: move |event| scroll.container_resized(event),
+            ..attributes,
+            if let Some(header) = header {
+                div {
+                    position: "sticky",
+                    top: "0",
+                    z_index: "1",
+                    onmounted: move |event| scroll.header_mounted(event),
+                    onresize: move |event| scroll.header_resized(event),
+                    {header}
+                }
+            }
+            div { position: "relative", height: "{total_height}px",
+                div {
+                    position: "absolute",
+                    top: "{offset}px",
+                    left: "0",
+                    right: "0",
+                    for row in rows {
+                        div {
+                            key: "{row}",
+                            "data-row": row,
+                            display: "grid",
+                            grid_template_columns: "repeat({columns}, minmax(0, 1fr))",
+                            // The gap between rows is part of the measured row height
+                            column_gap: "{gap}",
+                            padding_bottom: "{gap}",
+                            onmounted: move |event| scroll.row_mounted(row, event),
+                            onresize: move |event| scroll.row_resized(row, event),
+                            for index in row * columns..((row + 1) * columns).min(count) {
+                                div { key: "{key(index)}", "data-index": index, {item(index)} }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
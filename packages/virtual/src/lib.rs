@@ -0,0 +1,17 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+// cannot use forbid, because props derive macro generates #[allow(missing_docs)]
+#![deny(missing_docs)]
+#![allow(non_snake_case)]
+
+mod controller;
+mod grid;
+mod list;
+mod scroll;
+mod virtualizer;
+
+pub use controller::{use_virtual_controller, VirtualController};
+pub use grid::{VirtualGrid, VirtualGridProps};
+pub use list::{VirtualList, VirtualListProps};
+pub use virtualizer::Virtualizer;
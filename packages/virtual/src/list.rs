@@ -0,0 +1,180 @@
+use dioxus_lib::prelude::*;
+use std::rc::Rc;
+
+use crate::scroll::{use_virtual_scroll, VirtualScrollOptions};
+use crate::VirtualController;
+
+/// The properties for a [`VirtualList`].
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualListProps {
+    /// The number of items in the list.
+    pub count: usize,
+
+    /// Render the item at an index.
+    pub item: Callback<usize, Element>,
+
+    /// A stable key for the item at an index. If items can be inserted, removed or reordered, the key lets the list
+    /// keep the state and measured height of each item. Defaults to the index of the item.
+    #[props(default)]
+    pub item_key: Option<Callback<usize, String>>,
+
+    /// The height of items that haven't been measured yet, in pixels. Items are measured after they mount, so the
+    /// estimate only needs to be close. Defaults to 40.
+    #[props(default = 40.0)]
+    pub estimated_item_height: f64,
+
+    /// The number of extra items to render above and below the visible items. Defaults to 3.
+    #[props(default = 3)]
+    pub overscan: usize,
+
+    /// The number of items to render before the list is mounted and its height is known, like during server side
+    /// rendering. Defaults to 20.
+    #[props(default = 20)]
+    pub initial_items: usize,
+
+    /// A header that stays at the top of the list while it scrolls.
+    #[props(default)]
+    pub header: Option<Element>,
+
+    /// The sorted indexes of items that stick to the top of the list while the items after them are scrolled
+    /// through, like section headers.
+    #[props(default)]
+    pub sticky_items: Vec<usize>,
+
+    /// A controller that can scroll the list to an item. Create one with
+    /// [`use_virtual_controller`](crate::use_virtual_controller).
+    #[props(default)]
+    pub controller: Option<VirtualController>,
+
+    /// Additional attributes for the scroll container. The container must have a fixed height, for example with the
+    /// `style` or `class` attributes.
+    #[props(extends = GlobalAttributes)]
+    pub attributes: Vec<Attribute>,
+}
+
+/// A list that only renders the items that are visible in its scroll container.
+///
+/// Items can have different heights. Each item is measured once it mounts, and the scroll height of the list is
+/// adjusted as items are measured. Before the list is mounted, like during server side rendering, the first
+/// `initial_items` items are rendered so the page has content without javascript.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// use dioxus_virtual::VirtualList;
+///
+/// fn app() -> Element {
+///     let messages = use_signal(|| (0..50_000).map(|i| format!("Message {i}")).collect::<Vec<_>>());
+///     rsx! {
+///         VirtualList {
+///             style: "height: 600px",
+///             count: messages.len(),
+///             header: rsx! { h2 { "Inbox" } },
+///             item: move |index| rsx! { p { "{messages.read()[index]}" } },
+///         }
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualList(props: VirtualListProps) -> Element {
+    let VirtualListProps {
+        count,
+        item,
+        item_key,
+        estimated_item_height,
+        overscan,
+        initial_items,
+        header,
+        sticky_items,
+        controller,
+        attributes,
+    } = props;
+
+    // The keys are only collected again when the items change, not every time the list scrolls
+    let keys = use_memo(use_reactive!(|(count, item_key)| {
+        item_key.map(|item_key| {
+            (0..count)
+                .map(|index| item_key.call(index))
+                .collect::<Rc<[String]>>()
+        })
+    }));
+
+    let scroll = use_virtual_scroll(VirtualScrollOptions {
+        rows: count,
+        estimated_row_height: estimated_item_height,
+        overscan,
+        initial_rows: initial_items,
+        items_per_row: 1,
+        row_keys: keys(),
+        controller,
+    });
+
+    let rows = scroll.rendered_rows();
+    let offset = scroll.offset(rows.start);
+    let total_height = scroll.total_height();
+    let header_height = scroll.header_height();
+
+    // The last sticky item at or above the top of the viewport
+    let first_visible = scroll.first_visible_row();
+    let sticky = sticky_items
+        .iter()
+        .copied()
+        .take_while(|index| *index <= first_visible)
+        .last()
+        .filter(|index| *index < count);
+
+    let key = move |index: usize| match item_key {
+        Some(item_key) => item_key(index),
+        None => index.to_string(),
+    };
+
+    rsx! {
+        div {
+            overflow_y: "auto",
+            position: "relative",
+            onmounted: move |event| scroll.container_mounted(event),
+            onscroll: move |_| scroll.container_scrolled(),
+            onresize: move |event| scroll.container_resized(event),
+            ..attributes,
+            if let Some(header) = header {
+                div {
+                    position: "sticky",
+                    top: "0",
+                    z_index: "2",
+                    onmounted: move |event| scroll.header_mounted(event),
+                    onresize: move |event| scroll.header_resized(event),
+                    {header}
+                }
+            }
+            if let Some(sticky) = sticky {
+                // The sticky item is drawn over the items below it without taking up space
+                div {
+                    position: "sticky",
+                    top: "{header_height}px",
+                    height: "0",
+                    overflow: "visible",
+                    z_index: "1",
+                    div { {item(sticky)} }
+                }
+            }
+            div { position: "relative", height: "{total_height}px",
+                div {
+                    position: "absolute",
+                    top: "{offset}px",
+                    left: "0",
+                    right: "0",
+                    for index in rows {
+                        div {
+                            key: "{key(index)}",
+                            "data-index": index,
+                            onmounted: move |event| scroll.row_mounted(index, event),
+                            onresize: move |event| scroll.row_resized(index, event),
+                            {item(index)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
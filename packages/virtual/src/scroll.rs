@@ -0,0 +1,243 @@
+//! The scroll state shared by the virtualized components
+
+use std::{collections::HashMap, ops::Range, rc::Rc};
+
+use dioxus_lib::prelude::*;
+
+use crate::{use_virtual_controller, VirtualController, Virtualizer};
+
+/// The state of a virtualized scroll container. Every field is a signal or copy value, so the state can be moved
+/// into event handlers.
+#[derive(Clone, Copy)]
+pub(crate) struct VirtualScroll {
+    /// The layout is not reactive so it can be kept in sync with the props while rendering. Changes are published
+    /// through `layout_version`.
+    layout: CopyValue<Virtualizer>,
+    layout_version: Signal<usize>,
+    scroll_top: Signal<f64>,
+    /// The height of the container. This is None until the container is mounted, which never happens during SSR
+    viewport_height: Signal<Option<f64>>,
+    header_height: Signal<f64>,
+    container: Signal<Option<Rc<MountedData>>>,
+    /// The rows that are currently mounted
+    mounted_rows: CopyValue<HashMap<usize, Rc<MountedData>>>,
+    /// A row that should be scrolled into view as soon as it is mounted
+    pending_scroll: CopyValue<Option<usize>>,
+    initial_rows: usize,
+}
+
+/// The options shared by the virtualized components
+pub(crate) struct VirtualScrollOptions {
+    pub rows: usize,
+    pub estimated_row_height: f64,
+    pub overscan: usize,
+    pub initial_rows: usize,
+    pub items_per_row: usize,
+    /// The key of each row, so measured heights follow their row when rows are reordered
+    pub row_keys: Option<Rc<[String]>>,
+    pub controller: Option<VirtualController>,
+}
+
+pub(crate) fn use_virtual_scroll(options: VirtualScrollOptions) -> VirtualScroll {
+    let VirtualScrollOptions {
+        rows,
+        estimated_row_height,
+        overscan,
+        initial_rows,
+        items_per_row,
+        row_keys,
+        controller,
+    } = options;
+
+    let layout =
+        use_hook(|| CopyValue::new(Virtualizer::new(rows, estimated_row_height, overscan)));
+    // Keep the layout in sync with the props
+    {
+        let mut layout = layout.write_unchecked();
+        match row_keys {
+            Some(keys) => layout.set_keys(keys),
+            None => layout.set_count(rows),
+        }
+        layout.set_estimated_height(estimated_row_height);
+        layout.set_overscan(overscan);
+    }
+
+    let scroll = VirtualScroll {
+        layout,
+        layout_version: use_signal(|| 0),
+        scroll_top: use_signal(|| 0.0),
+        viewport_height: use_signal(|| None),
+        header_height: use_signal(|| 0.0),
+        container: use_signal(|| None),
+        mounted_rows: use_hook(|| CopyValue::new(HashMap::new())),
+        pending_scroll: use_hook(|| CopyValue::new(None)),
+        initial_rows,
+    };
+
+    let controller = controller.unwrap_or(use_virtual_controller());
+    use_effect(move || {
+        let mut request = controller.scroll_request;
+        if request.read().is_none() {
+            return;
+        }
+        let index = request.write().take();
+        if let Some(index) = index {
+            scroll.scroll_to_row(index / items_per_row.max(1));
+        }
+    });
+
+    scroll
+}
+
+impl VirtualScroll {
+    /// The rows that should be rendered. Reading the range subscribes to changes in the layout and scroll position.
+    pub fn rendered_rows(&self) -> Range<usize> {
+        self.layout_version.read();
+        let layout = self.layout.read();
+        match (self.viewport_height)() {
+            Some(height) => {
+                layout.visible_range((self.scroll_top)() - (self.header_height)(), height)
+            }
+            None => 0..self.initial_rows.min(layout.count()),
+        }
+    }
+
+    /// The first row that is at least partly visible, not including overscan
+    pub fn first_visible_row(&self) -> usize {
+        self.layout_version.read();
+        let layout = self.layout.read();
+        layout.row_at((self.scroll_top)() - (self.header_height)())
+    }
+
+    /// The total height of every row
+    pub fn total_height(&self) -> f64 {
+        self.layout_version.read();
+        self.layout.read().total_height()
+    }
+
+    /// The height of the sticky header
+    pub fn header_height(&self) -> f64 {
+        (self.header_height)()
+    }
+
+    /// The offset of the top of a row
+    pub fn offset(&self, row: usize) -> f64 {
+        self.layout_version.read();
+        self.layout.read().offset(row)
+    }
+
+    pub fn container_mounted(self, event: MountedEvent) {
+        let mut this = self;
+        let container = event.data();
+        this.container.set(Some(container.clone()));
+        spawn(async move {
+            if let Ok(rect) = container.get_client_rect().await {
+                this.viewport_height.set(Some(rect.height()));
+            }
+        });
+    }
+
+    pub fn container_scrolled(self) {
+        let mut this = self;
+        let Some(container) = this.container.peek().clone() else {
+            return;
+        };
+        spawn(async move {
+            if let Ok(offset) = container.get_scroll_offset().await {
+                this.scroll_top.set(offset.y);
+            }
+        });
+    }
+
+    pub fn container_resized(self, event: ResizeEvent) {
+        let mut this = self;
+        if let Ok(size) = event.get_content_box_size() {
+            if *this.viewport_height.peek() != Some(size.height) {
+                this.viewport_height.set(Some(size.height));
+            }
+        }
+    }
+
+    pub fn header_mounted(self, event: MountedEvent) {
+        let mut this = self;
+        spawn(async move {
+            if let Ok(rect) = event.data().get_client_rect().await {
+                this.header_height.set(rect.height());
+            }
+        });
+    }
+
+    pub fn header_resized(self, event: ResizeEvent) {
+        let mut this = self;
+        if let Ok(size) = event.get_border_box_size() {
+            if *this.header_height.peek() != size.height {
+                this.header_height.set(size.height);
+            }
+        }
+    }
+
+    pub fn row_mounted(self, row: usize, event: MountedEvent) {
+        let mounted = event.data();
+        let mut mounted_rows = self.mounted_rows;
+        let rendered = self.rendered_rows_untracked();
+        let mut mounted_rows = mounted_rows.write();
+        // Forget rows that were scrolled out of view. They have been unmounted
+        mounted_rows.retain(|row, _| rendered.contains(row));
+        mounted_rows.insert(row, mounted.clone());
+        drop(mounted_rows);
+
+        let mut pending_scroll = self.pending_scroll;
+        if *pending_scroll.read() == Some(row) {
+            pending_scroll.set(None);
+            spawn(Self::scroll_into_view(mounted.clone()));
+        }
+
+        spawn(async move {
+            if let Ok(rect) = mounted.get_client_rect().await {
+                self.set_row_height(row, rect.height());
+            }
+        });
+    }
+
+    pub fn row_resized(self, row: usize, event: ResizeEvent) {
+        if let Ok(size) = event.get_border_box_size() {
+            self.set_row_height(row, size.height);
+        }
+    }
+
+    fn set_row_height(self, row: usize, height: f64) {
+        let mut layout = self.layout;
+        if layout.write().set_height(row, height) {
+            let mut version = self.layout_version;
+            version += 1;
+        }
+    }
+
+    fn scroll_to_row(self, row: usize) {
+        let mut this = self;
+        if let Some(mounted) = this.mounted_rows.read().get(&row).cloned() {
+            spawn(Self::scroll_into_view(mounted));
+            return;
+        }
+        // Render the window around the row. The row scrolls itself into view once it is mounted
+        this.pending_scroll.set(Some(row));
+        let offset = this.layout.read().offset(row) + *this.header_height.peek();
+        this.scroll_top.set(offset);
+    }
+
+    async fn scroll_into_view(row: Rc<MountedData>) {
+        if let Err(err) = row.scroll_to(ScrollBehavior::Instant).await {
+            tracing::error!("Failed to scroll a virtualized row into view: {err}");
+        }
+    }
+
+    fn rendered_rows_untracked(&self) -> Range<usize> {
+        let layout = self.layout.read();
+        match *self.viewport_height.peek() {
+            Some(height) => {
+                layout.visible_range(*self.scroll_top.peek() - *self.header_height.peek(), height)
+            }
+            None => 0..self.initial_rows.min(layout.count()),
+        }
+    }
+}
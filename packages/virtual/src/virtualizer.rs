@@ -0,0 +1,232 @@
+use std::{collections::HashMap, ops::Range, rc::Rc};
+
+/// The layout math behind [`VirtualList`](crate::VirtualList) and [`VirtualGrid`](crate::VirtualGrid).
+///
+/// The virtualizer tracks the height of every row. Rows that haven't been measured yet use the estimated height.
+/// Use it directly to virtualize a custom layout.
+///
+/// ```rust
+/// use dioxus_virtual::Virtualizer;
+///
+/// let mut rows = Virtualizer::new(1000, 20.0, 2);
+/// assert_eq!(rows.total_height(), 20_000.0);
+///
+/// // Row 1 turned out to be taller than estimated
+/// rows.set_height(1, 50.0);
+/// assert_eq!(rows.offset(2), 70.0);
+///
+/// // The rows visible in a 100px viewport scrolled to the top, plus two rows of overscan
+/// assert_eq!(rows.visible_range(0.0, 100.0), 0..6);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Virtualizer {
+    estimated_height: f64,
+    overscan: usize,
+    count: usize,
+    /// The key of each row, or None if rows are keyed by their index
+    keys: Option<Rc<[String]>>,
+    /// The measured heights of rows by their index, used while rows don't have keys
+    measured_rows: HashMap<usize, f64>,
+    /// The measured heights of rows by their key
+    measured_keys: HashMap<String, f64>,
+    /// A Fenwick tree over the row heights, so offsets can be found and updated in O(log n)
+    sums: Vec<f64>,
+}
+
+impl Virtualizer {
+    /// Create a virtualizer for `count` rows with an estimated height. `overscan` extra rows are rendered on each
+    /// side of the visible rows, so fast scrolling doesn't reveal empty space.
+    pub fn new(count: usize, estimated_height: f64, overscan: usize) -> Self {
+        let mut virtualizer = Self {
+            estimated_height,
+            overscan,
+            count,
+            keys: None,
+            measured_rows: HashMap::new(),
+            measured_keys: HashMap::new(),
+            sums: Vec::new(),
+        };
+        virtualizer.update_offsets();
+        virtualizer
+    }
+
+    /// The number of rows
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Change the number of rows and key the rows by their index. Measured heights of the rows that remain are kept.
+    pub fn set_count(&mut self, count: usize) {
+        if self.count == count && self.keys.is_none() {
+            return;
+        }
+        self.keys = None;
+        self.measured_keys.clear();
+        self.measured_rows.retain(|index, _| *index < count);
+        self.count = count;
+        self.update_offsets();
+    }
+
+    /// Set the key of every row. The number of rows is the number of keys. Measured heights follow their key when
+    /// rows are inserted, removed or reordered, and the heights of keys that were removed are forgotten.
+    ///
+    /// Setting the same keys again doesn't change the layout.
+    ///
+    /// ```rust
+    /// use dioxus_virtual::Virtualizer;
+    ///
+    /// let mut rows = Virtualizer::new(0, 20.0, 0);
+    /// rows.set_keys(["a", "b", "c"].map(String::from).into());
+    /// rows.set_height(0, 50.0);
+    ///
+    /// // The measured row moves to the end of the list
+    /// rows.set_keys(["b", "c", "a"].map(String::from).into());
+    /// assert_eq!(rows.height(2), 50.0);
+    /// assert_eq!(rows.offset(2), 40.0);
+    /// ```
+    pub fn set_keys(&mut self, keys: Rc<[String]>) {
+        if matches!(&self.keys, Some(old) if Rc::ptr_eq(old, &keys)) {
+            return;
+        }
+        self.measured_rows.clear();
+        let mut measured = std::mem::take(&mut self.measured_keys);
+        self.measured_keys = keys
+            .iter()
+            .filter_map(|key| measured.remove_entry(key.as_str()))
+            .collect();
+        self.count = keys.len();
+        self.keys = Some(keys);
+        self.update_offsets();
+    }
+
+    /// Change the height used for rows that haven't been measured
+    pub fn set_estimated_height(&mut self, estimated_height: f64) {
+        if self.estimated_height != estimated_height {
+            self.estimated_height = estimated_height;
+            self.update_offsets();
+        }
+    }
+
+    /// Change the number of extra rows rendered on each side of the visible rows
+    pub fn set_overscan(&mut self, overscan: usize) {
+        self.overscan = overscan;
+    }
+
+    /// Record the measured height of a row. Returns true if the height changed.
+    pub fn set_height(&mut self, index: usize, height: f64) -> bool {
+        if index >= self.count {
+            return false;
+        }
+        let old = self.height(index);
+        let old_measurement = match &self.keys {
+            Some(keys) => self.measured_keys.insert(keys[index].clone(), height),
+            None => self.measured_rows.insert(index, height),
+        };
+        if old_measurement == Some(height) {
+            return false;
+        }
+
+        // Move the end of every row after this one
+        let difference = height - old;
+        let mut node = index + 1;
+        while node < self.sums.len() {
+            self.sums[node] += difference;
+            node += lowest_bit(node);
+        }
+        true
+    }
+
+    /// The measured or estimated height of a row
+    pub fn height(&self, index: usize) -> f64 {
+        let measured = match &self.keys {
+            Some(keys) => keys
+                .get(index)
+                .and_then(|key| self.measured_keys.get(key.as_str())),
+            None => self.measured_rows.get(&index),
+        };
+        measured.copied().unwrap_or(self.estimated_height)
+    }
+
+    /// The offset of the top of a row from the top of the first row. Indexes past the end are clamped to the total
+    /// height.
+    pub fn offset(&self, index: usize) -> f64 {
+        let mut node = index.min(self.count);
+        let mut offset = 0.0;
+        while node > 0 {
+            offset += self.sums[node];
+            node -= lowest_bit(node);
+        }
+        offset
+    }
+
+    /// The height of every row combined
+    pub fn total_height(&self) -> f64 {
+        self.offset(self.count())
+    }
+
+    /// The row at an offset from the top of the first row. Offsets past the end return the last row.
+    pub fn row_at(&self, offset: f64) -> usize {
+        // The number of rows that end at or above the offset is the index of the first row that ends below it
+        self.rows_while(|end| end <= offset)
+            .min(self.count().saturating_sub(1))
+    }
+
+    /// The rows that overlap a viewport of the given height that is scrolled `scroll_top` pixels down, plus the
+    /// overscan rows on each side
+    pub fn visible_range(&self, scroll_top: f64, viewport_height: f64) -> Range<usize> {
+        let count = self.count();
+        if count == 0 {
+            return 0..0;
+        }
+        let scroll_top = scroll_top.max(0.0);
+        let first = self.row_at(scroll_top);
+        // The first row that starts at or below the bottom of the viewport
+        let bottom = scroll_top + viewport_height;
+        let last = match bottom > 0.0 {
+            true => (self.rows_while(|end| end < bottom) + 1).min(count),
+            false => 0,
+        }
+        .max(first + 1);
+
+        first.saturating_sub(self.overscan)..(last + self.overscan).min(count)
+    }
+
+    /// The largest number of rows whose combined height still passes the check. The check must pass for every
+    /// height below a height that passes.
+    fn rows_while(&self, check: impl Fn(f64) -> bool) -> usize {
+        let mut rows = 0;
+        let mut height = 0.0;
+        let mut step = match self.count {
+            0 => 0,
+            count => 1 << count.ilog2(),
+        };
+        while step > 0 {
+            if rows + step <= self.count && check(height + self.sums[rows + step]) {
+                rows += step;
+                height += self.sums[rows];
+            }
+            step >>= 1;
+        }
+        rows
+    }
+
+    /// Rebuild the offsets of every row
+    fn update_offsets(&mut self) {
+        self.sums.clear();
+        self.sums.push(0.0);
+        for row in 0..self.count {
+            let height = self.height(row);
+            self.sums.push(height);
+        }
+        for node in 1..self.sums.len() {
+            let parent = node + lowest_bit(node);
+            if parent < self.sums.len() {
+                self.sums[parent] += self.sums[node];
+            }
+        }
+    }
+}
+
+fn lowest_bit(node: usize) -> usize {
+    node & node.wrapping_neg()
+}
@@ -0,0 +1,152 @@
+use dioxus::prelude::*;
+use dioxus_testing::{Query, TestApp};
+use dioxus_virtual::{VirtualGrid, VirtualList, Virtualizer};
+
+#[test]
+fn measured_heights_move_later_rows() {
+    let mut rows = Virtualizer::new(10, 10.0, 0);
+    assert_eq!(rows.total_height(), 100.0);
+    assert_eq!(rows.visible_range(25.0, 20.0), 2..5);
+
+    assert!(rows.set_height(0, 30.0));
+    assert!(!rows.set_height(0, 30.0));
+    assert_eq!(rows.offset(1), 30.0);
+    assert_eq!(rows.total_height(), 120.0);
+    assert_eq!(rows.row_at(35.0), 1);
+    assert_eq!(rows.visible_range(25.0, 20.0), 0..3);
+
+    // Shrinking the list keeps the heights of the remaining rows
+    rows.set_count(2);
+    assert_eq!(rows.total_height(), 40.0);
+    assert_eq!(rows.visible_range(1000.0, 20.0), 1..2);
+    assert_eq!(Virtualizer::new(0, 10.0, 2).visible_range(0.0, 100.0), 0..0);
+}
+
+#[test]
+fn measured_heights_follow_their_key() {
+    let keys = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect();
+    let mut rows = Virtualizer::new(0, 10.0, 0);
+    rows.set_keys(keys(&["a", "b", "c"]));
+    assert!(rows.set_height(1, 30.0));
+    assert_eq!(rows.total_height(), 50.0);
+
+    // "b" moved to the front
+    rows.set_keys(keys(&["b", "c", "a"]));
+    assert_eq!(rows.height(0), 30.0);
+    assert_eq!(rows.offset(1), 30.0);
+    assert_eq!(rows.row_at(35.0), 1);
+
+    // Removing "b" forgets its height, even if it comes back
+    rows.set_keys(keys(&["c", "a"]));
+    assert_eq!(rows.total_height(), 20.0);
+    rows.set_keys(keys(&["b", "c", "a"]));
+    assert_eq!(rows.height(0), 10.0);
+}
+
+#[test]
+fn offsets_match_the_sum_of_the_heights() {
+    let mut rows = Virtualizer::new(100, 10.0, 1);
+    let mut heights = vec![10.0; 100];
+    // A small deterministic sequence of measurements
+    let mut seed = 7usize;
+    for _ in 0..500 {
+        seed = (seed * 1103515245 + 12345) % (1 << 31);
+        let (index, height) = (seed % 100, (seed % 64) as f64);
+        rows.set_height(index, height);
+        heights[index] = height;
+    }
+
+    let offsets: Vec<f64> = (0..=100).map(|row| heights[..row].iter().sum()).collect();
+    for (row, offset) in offsets.iter().enumerate() {
+        assert_eq!(rows.offset(row), *offset);
+    }
+    for scroll_top in (0..offsets[100] as usize + 50).step_by(7) {
+        let scroll_top = scroll_top as f64;
+        let first = offsets[1..]
+            .partition_point(|end| *end <= scroll_top)
+            .min(99);
+        assert_eq!(rows.row_at(scroll_top), first);
+        let last = offsets[..100]
+            .partition_point(|start| *start < scroll_top + 45.0)
+            .max(first + 1);
+        assert_eq!(
+            rows.visible_range(scroll_top, 45.0),
+            first.saturating_sub(1)..(last + 1).min(100)
+        );
+    }
+}
+
+#[test]
+fn ssr_renders_the_initial_window() {
+    fn app() -> Element {
+        rsx! {
+            VirtualList {
+                style: "height: 200px",
+                count: 1000,
+                initial_items: 5,
+                estimated_item_height: 20.0,
+                header: rsx! { h2 { "Header" } },
+                item: move |index| rsx! { p { "Item {index}" } },
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let html = dioxus_ssr::render(&dom);
+
+    assert!(html.contains("Header"));
+    assert!(html.contains("Item 4"));
+    assert!(!html.contains("Item 5"));
+    // The spacer reserves room for every item
+    assert!(html.contains("height:20000px"));
+}
+
+#[test]
+fn items_are_keyed() {
+    fn app() -> Element {
+        let mut names = use_signal(|| vec!["a", "b", "c"]);
+        rsx! {
+            button { onclick: move |_| names.write().reverse(), "reverse" }
+            VirtualList {
+                count: names.len(),
+                item_key: move |index: usize| names.read()[index].to_string(),
+                item: move |index| rsx! { span { "{names.read()[index]}" } },
+            }
+        }
+    }
+
+    let mut app = TestApp::new(app);
+    let first = app.get(&Query::text("a"));
+    let button = app.get(&Query::tag("button"));
+    app.click(button);
+
+    let items = app.query_all(&Query::tag("span"));
+    let text: Vec<_> = items
+        .iter()
+        .map(|id| app.node(*id).text_content())
+        .collect();
+    assert_eq!(text, ["c", "b", "a"]);
+    // The node for "a" was moved instead of recreated
+    assert_eq!(app.get(&Query::text("a")), first);
+}
+
+#[test]
+fn grid_renders_rows_of_items() {
+    fn app() -> Element {
+        rsx! {
+            VirtualGrid {
+                count: 10,
+                columns: 4,
+                initial_rows: 2,
+                item: move |index| rsx! { span { "Cell {index}" } },
+            }
+        }
+    }
+
+    let app = TestApp::new(app);
+    assert!(app.query(&Query::attribute("data-row", "1")).is_some());
+    assert!(app.query(&Query::attribute("data-row", "2")).is_none());
+    assert_eq!(app.query_all(&Query::tag("span")).len(), 8);
+    assert!(app.query(&Query::text("Cell 8")).is_none());
+}
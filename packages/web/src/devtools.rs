@@ -3,13 +3,14 @@
 //! This sets up a websocket connection to the devserver and handles messages from it.
 //! We also set up a little recursive timer that will attempt to reconnect if the connection is lost.
 
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
 use std::time::Duration;
 
 use dioxus_core::prelude::RuntimeGuard;
-use dioxus_core::{Runtime, ScopeId};
-use dioxus_devtools::{DevserverMsg, HotReloadMsg};
+use dioxus_core::{ReactiveGraph, Runtime, ScopeId};
+use dioxus_devtools::{ClientMsg, DevserverMsg, HotReloadMsg};
 use dioxus_document::eval;
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use js_sys::JsString;
@@ -25,6 +26,11 @@ const POLL_INTERVAL_SCALE_FACTOR: i32 = 2;
 const TOAST_TIMEOUT: Duration = Duration::from_secs(5);
 const TOAST_TIMEOUT_LONG: Duration = Duration::from_secs(3600); // Duration::MAX is too long for JS.
 
+thread_local! {
+    /// The current connection to the devserver
+    static SOCKET: RefCell<Option<WebSocket>> = const { RefCell::new(None) };
+}
+
 pub(crate) fn init(runtime: Rc<Runtime>) -> UnboundedReceiver<HotReloadMsg> {
     // Create the tx/rx pair that we'll use for the top-level future in the dioxus loop
     let (tx, rx) = unbounded();
@@ -55,6 +61,7 @@ fn make_ws(
     );
 
     let ws = WebSocket::new(&url).unwrap();
+    SOCKET.with(|socket| socket.replace(Some(ws.clone())));
 
     // Set the onmessage handler to bounce messages off to the main dioxus loop
     let tx_ = tx.clone();
@@ -182,6 +189,27 @@ fn make_ws(
     }
 }
 
+/// Send a snapshot of the reactive graph to the devserver if the `reactive-graph` feature is enabled
+pub(crate) fn send_reactive_graph() {
+    if !ReactiveGraph::is_enabled() {
+        return;
+    }
+
+    SOCKET.with(|socket| {
+        let socket = socket.borrow();
+        let Some(socket) = socket
+            .as_ref()
+            .filter(|socket| socket.ready_state() == WebSocket::OPEN)
+        else {
+            return;
+        };
+        if let Ok(msg) = serde_json::to_string(&ClientMsg::ReactiveGraph(ReactiveGraph::snapshot()))
+        {
+            _ = socket.send_with_str(&msg);
+        }
+    });
+}
+
 /// Represents what color the toast should have.
 enum ToastLevel {
     /// Green
@@ -7,8 +7,7 @@ use crate::dom::WebsysDom;
 use crate::with_server_data;
 use crate::HTMLDataCursor;
 use dioxus_core::prelude::*;
-use dioxus_core::AttributeValue;
-use dioxus_core::{DynamicNode, ElementId};
+use dioxus_core::HydrationIds;
 use futures_channel::mpsc::UnboundedReceiver;
 use std::fmt::Write;
 use RehydrationError::*;
@@ -192,16 +191,17 @@ impl WebsysDom {
         dom: &VirtualDom,
         under: Vec<web_sys::Node>,
     ) -> Result<(), RehydrationError> {
-        let mut ids = Vec::new();
-        let mut to_mount = Vec::new();
+        // Collect the ids of the nodes under the scope. Pending suspense boundaries are hydrated later.
+        let suspense_hydration_ids = &mut self.suspense_hydration_ids;
+        let hydration = HydrationIds::collect(dom, scope, |id| {
+            suspense_hydration_ids.add_suspense_boundary(id)
+        })
+        .ok_or(VNodeNotInitialized)?;
 
-        // Recursively rehydrate the nodes under the scope
-        self.rehydrate_scope(scope, dom, &mut ids, &mut to_mount)?;
-
-        self.interpreter.base().hydrate(ids, under);
+        self.interpreter.base().hydrate(hydration.ids, under);
 
         #[cfg(feature = "mounted")]
-        for id in to_mount {
+        for id in hydration.mounted {
             self.send_mount_event(id);
         }
 
@@ -240,136 +240,6 @@ impl WebsysDom {
 
         Ok(rx)
     }
-
-    fn rehydrate_scope(
-        &mut self,
-        scope: &ScopeState,
-        dom: &VirtualDom,
-        ids: &mut Vec<u32>,
-        to_mount: &mut Vec<ElementId>,
-    ) -> Result<(), RehydrationError> {
-        // If this scope is a suspense boundary that is pending, add it to the list of pending suspense boundaries
-        if let Some(suspense) =
-            SuspenseContext::downcast_suspense_boundary_from_scope(&dom.runtime(), scope.id())
-        {
-            if suspense.has_suspended_tasks() {
-                self.suspense_hydration_ids
-                    .add_suspense_boundary(scope.id());
-            }
-        }
-
-        self.rehydrate_vnode(dom, scope.root_node(), ids, to_mount)
-    }
-
-    fn rehydrate_vnode(
-        &mut self,
-        dom: &VirtualDom,
-        vnode: &VNode,
-        ids: &mut Vec<u32>,
-        to_mount: &mut Vec<ElementId>,
-    ) -> Result<(), RehydrationError> {
-        for (i, root) in vnode.template.roots.iter().enumerate() {
-            self.rehydrate_template_node(
-                dom,
-                vnode,
-                root,
-                ids,
-                to_mount,
-                Some(vnode.mounted_root(i, dom).ok_or(VNodeNotInitialized)?),
-            )?;
-        }
-        Ok(())
-    }
-
-    fn rehydrate_template_node(
-        &mut self,
-        dom: &VirtualDom,
-        vnode: &VNode,
-        node: &TemplateNode,
-        ids: &mut Vec<u32>,
-        to_mount: &mut Vec<ElementId>,
-        root_id: Option<ElementId>,
-    ) -> Result<(), RehydrationError> {
-        match node {
-            TemplateNode::Element {
-                children, attrs, ..
-            } => {
-                let mut mounted_id = root_id;
-                for attr in *attrs {
-                    if let dioxus_core::TemplateAttribute::Dynamic { id } = attr {
-                        let attributes = &*vnode.dynamic_attrs[*id];
-                        let id = vnode
-                            .mounted_dynamic_attribute(*id, dom)
-                            .ok_or(VNodeNotInitialized)?;
-                        for attribute in attributes {
-                            let value = &attribute.value;
-                            mounted_id = Some(id);
-                            if let AttributeValue::Listener(_) = value {
-                                if attribute.name == "onmounted" {
-                                    to_mount.push(id);
-                                }
-                            }
-                        }
-                    }
-                }
-                if let Some(id) = mounted_id {
-                    ids.push(id.0 as u32);
-                }
-                if !children.is_empty() {
-                    for child in *children {
-                        self.rehydrate_template_node(dom, vnode, child, ids, to_mount, None)?;
-                    }
-                }
-            }
-            TemplateNode::Dynamic { id } => self.rehydrate_dynamic_node(
-                dom,
-                &vnode.dynamic_nodes[*id],
-                *id,
-                vnode,
-                ids,
-                to_mount,
-            )?,
-            TemplateNode::Text { .. } => {
-                if let Some(id) = root_id {
-                    ids.push(id.0 as u32);
-                }
-            }
-        }
-        Ok(())
-    }
-
-    fn rehydrate_dynamic_node(
-        &mut self,
-        dom: &VirtualDom,
-        dynamic: &DynamicNode,
-        dynamic_node_index: usize,
-        vnode: &VNode,
-        ids: &mut Vec<u32>,
-        to_mount: &mut Vec<ElementId>,
-    ) -> Result<(), RehydrationError> {
-        match dynamic {
-            dioxus_core::DynamicNode::Text(_) | dioxus_core::DynamicNode::Placeholder(_) => {
-                ids.push(
-                    vnode
-                        .mounted_dynamic_node(dynamic_node_index, dom)
-                        .ok_or(VNodeNotInitialized)?
-                        .0 as u32,
-                );
-            }
-            dioxus_core::DynamicNode::Component(comp) => {
-                let scope = comp
-                    .mounted_scope(dynamic_node_index, vnode, dom)
-                    .ok_or(VNodeNotInitialized)?;
-                self.rehydrate_scope(scope, dom, ids, to_mount)?;
-            }
-            dioxus_core::DynamicNode::Fragment(fragment) => {
-                for vnode in fragment {
-                    self.rehydrate_vnode(dom, vnode, ids, to_mount)?;
-                }
-            }
-        }
-        Ok(())
-    }
 }
 
 fn write_comma_separated(id: &[u32], into: &mut String) {
@@ -228,5 +228,8 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         // work_loop.wait_for_raf().await;
 
         websys_dom.flush_edits();
+
+        #[cfg(all(feature = "devtools", debug_assertions))]
+        crate::devtools::send_reactive_graph();
     }
 }